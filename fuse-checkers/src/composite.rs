@@ -0,0 +1,104 @@
+//! Composite spec evaluation
+//!
+//! Host-side mirror of the guest program's composite evaluator. Each leaf is
+//! routed through the registry with a standalone spec built from the parent.
+
+use fuse_core::composite::{combine, ClaimNode, NodeResult, SubClaim};
+use fuse_core::{ComplianceResult, ComplianceSpec, ErrorCode, Result, Score, VceError};
+use crate::CheckerRegistry;
+
+impl CheckerRegistry {
+    /// Evaluate a composite tree, returning every node's result in pre-order
+    ///
+    /// The first entry is the root, whose result is the aggregate. A leaf
+    /// whose checker errors is recorded as inconclusive with the checker's
+    /// error code rather than failing the whole evaluation, matching the
+    /// guest program. A leaf with no checker for its claim is inconclusive
+    /// too.
+    pub fn check_composite(
+        &self,
+        spec: &ComplianceSpec,
+        node: &ClaimNode,
+        system_data: &str,
+    ) -> Result<Vec<NodeResult>> {
+        let data: serde_json::Value = serde_json::from_str(system_data)?;
+        let mut results = Vec::new();
+        self.evaluate_node(spec, node, &data, "/".to_string(), &mut results)?;
+        Ok(results)
+    }
+
+    fn evaluate_node(
+        &self,
+        spec: &ComplianceSpec,
+        node: &ClaimNode,
+        data: &serde_json::Value,
+        path: String,
+        results: &mut Vec<NodeResult>,
+    ) -> Result<ComplianceResult> {
+        let Some((children, required)) = node.children() else {
+            let ClaimNode::Check(leaf) = node else { unreachable!("only leaves have no children") };
+            let (result, error_code, score) = self.evaluate_leaf(spec, leaf, data)?;
+            results.push(NodeResult { path, label: node.label(), result, error_code, score: score.unwrap_or_default() });
+            return Ok(result);
+        };
+
+        // Reserve this node's slot so results stay in pre-order
+        let slot = results.len();
+        results.push(NodeResult {
+            path: path.clone(),
+            label: node.label(),
+            result: ComplianceResult::Error,
+            error_code: ErrorCode::None,
+            score: Score::default(),
+        });
+
+        let mut child_results = Vec::with_capacity(children.len());
+        for (i, child) in children.iter().enumerate() {
+            let child_path = if path == "/" { format!("/{i}") } else { format!("{path}/{i}") };
+            child_results.push(self.evaluate_node(spec, child, data, child_path, results)?);
+        }
+
+        let result = combine(&child_results, required);
+        results[slot].result = result;
+        if result == ComplianceResult::Error {
+            results[slot].error_code = ErrorCode::SubClaimError;
+        }
+        Ok(result)
+    }
+
+    /// Run a leaf's checker once, recording an error as the guest program
+    /// does: with the checker's own error code
+    fn evaluate_leaf(
+        &self,
+        spec: &ComplianceSpec,
+        leaf: &SubClaim,
+        data: &serde_json::Value,
+    ) -> Result<(ComplianceResult, ErrorCode, Option<Score>)> {
+        let mut sub_spec = spec.clone();
+        sub_spec.claim.clone_from(&leaf.claim);
+        sub_spec.constraints.clone_from(&leaf.constraints);
+        sub_spec.pass_threshold.clone_from(&leaf.pass_threshold);
+        sub_spec.rule.clone_from(&leaf.rule);
+        sub_spec.composite = None;
+
+        let data = match &leaf.data_key {
+            Some(key) => match data.get(key) {
+                Some(data) => data,
+                None => return Ok((ComplianceResult::Error, ErrorCode::MissingField, None)),
+            },
+            None => data,
+        };
+        let data = serde_json::to_string(data)?;
+
+        // An unknown claim or malformed evidence makes the sub-claim
+        // inconclusive, not the whole tree
+        let Ok(checker) = self.checker_for(&sub_spec) else {
+            return Ok((ComplianceResult::Error, ErrorCode::CheckerNotInGuest, None));
+        };
+        match checker.check_scored(&sub_spec, &data) {
+            Ok((result, score)) => Ok((result, ErrorCode::None, score)),
+            Err(VceError::Inconclusive(code, _)) => Ok((ComplianceResult::Error, code, None)),
+            Err(_) => Ok((ComplianceResult::Error, ErrorCode::SubClaimError, None)),
+        }
+    }
+}
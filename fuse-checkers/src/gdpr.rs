@@ -1,46 +1,66 @@
 //! GDPR compliance checker
 
-use fuse_core::{ComplianceSpec, ComplianceResult, Result};
+use fuse_core::{ComplianceSpec, ComplianceResult, Result, Score, ErrorCode};
 use crate::ComplianceChecker;
 use serde_json::Value;
 
 pub struct GdprDataResidencyChecker;
 
-impl ComplianceChecker for GdprDataResidencyChecker {
-    fn check(&self, spec: &ComplianceSpec, system_data: &str) -> Result<ComplianceResult> {
+impl GdprDataResidencyChecker {
+    fn evaluate(spec: &ComplianceSpec, system_data: &str) -> Result<(ComplianceResult, Option<Score>)> {
+        let threshold = crate::pass_threshold(spec)?;
+
         let data: Value = serde_json::from_str(system_data)
-            .map_err(|e| fuse_core::VceError::InvalidSpec(
-                format!("Failed to parse system data: {e}")
+            .map_err(|e| crate::inconclusive(
+                ErrorCode::SystemDataParse,
+                format!("Failed to parse system data: {e}"),
             ))?;
 
         // Extract required region from constraints
         let required_region = spec.constraints
             .get("data_region")
-            .ok_or_else(|| fuse_core::VceError::InvalidSpec(
-                "GDPR checker requires 'data_region' constraint".to_string()
+            .ok_or_else(|| crate::inconclusive(
+                ErrorCode::MissingConstraint,
+                "GDPR checker requires 'data_region' constraint".to_string(),
             ))?;
 
         // Check data storage locations
         let storage_locations = data.get("storage_locations")
             .and_then(|v| v.as_array())
-            .ok_or_else(|| fuse_core::VceError::InvalidSpec(
-                "System data must contain 'storage_locations' array".to_string()
+            .ok_or_else(|| crate::inconclusive(
+                ErrorCode::MissingField,
+                "System data must contain 'storage_locations' array".to_string(),
             ))?;
 
-        // Verify all storage is in the required region
+        // Count storage locations in the required region
+        let mut in_region = 0;
         for location in storage_locations {
             let region = location.get("region")
                 .and_then(|v| v.as_str())
-                .ok_or_else(|| fuse_core::VceError::InvalidSpec(
-                    "Each storage location must have a 'region' field".to_string()
+                .ok_or_else(|| crate::inconclusive(
+                    ErrorCode::MissingField,
+                    "Each storage location must have a 'region' field".to_string(),
                 ))?;
 
-            if region != required_region {
-                return Ok(ComplianceResult::Fail);
+            if region == required_region {
+                in_region += 1;
             }
         }
 
-        Ok(ComplianceResult::Pass)
+        Ok(crate::graded(in_region, storage_locations.len() as u64, threshold))
     }
 }
 
+impl ComplianceChecker for GdprDataResidencyChecker {
+    fn check(&self, spec: &ComplianceSpec, system_data: &str) -> Result<ComplianceResult> {
+        Ok(Self::evaluate(spec, system_data)?.0)
+    }
+
+    fn score(&self, spec: &ComplianceSpec, system_data: &str) -> Result<Option<Score>> {
+        Ok(Self::evaluate(spec, system_data)?.1)
+    }
+
+    fn check_scored(&self, spec: &ComplianceSpec, system_data: &str) -> Result<(ComplianceResult, Option<Score>)> {
+        Self::evaluate(spec, system_data)
+    }
+}
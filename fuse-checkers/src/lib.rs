@@ -1,6 +1,6 @@
 //! Compliance checkers for various standards
 
-use fuse_core::{ComplianceSpec, ComplianceResult, Result};
+use fuse_core::{ComplianceSpec, ComplianceResult, ErrorCode, Result, Score, VceError};
 use std::collections::HashMap;
 
 pub mod soc2;
@@ -10,11 +10,46 @@ pub mod ml_model;
 pub mod ed25519;
 pub mod c2pa;
 pub mod json_only;
+pub mod composite;
+pub mod rule;
 
 /// Trait for compliance checkers
 pub trait ComplianceChecker {
     /// Check compliance against a specification
     fn check(&self, spec: &ComplianceSpec, system_data: &str) -> Result<ComplianceResult>;
+
+    /// Graded score for checkers that count conforming records
+    fn score(&self, _spec: &ComplianceSpec, _system_data: &str) -> Result<Option<Score>> {
+        Ok(None)
+    }
+
+    /// Result and score together, running the check once where the
+    /// checker computes both in one pass
+    fn check_scored(&self, spec: &ComplianceSpec, system_data: &str) -> Result<(ComplianceResult, Option<Score>)> {
+        Ok((self.check(spec, system_data)?, self.score(spec, system_data)?))
+    }
+}
+
+/// Error for a check that could not decide, carrying the code the guest
+/// program commits in the same case
+fn inconclusive(code: ErrorCode, message: String) -> VceError {
+    VceError::Inconclusive(code, message)
+}
+
+/// Read the spec's pass threshold; without one every record must conform
+fn pass_threshold(spec: &ComplianceSpec) -> Result<(u64, u64)> {
+    match &spec.pass_threshold {
+        None => Ok((1, 1)),
+        Some(threshold) => fuse_core::score::parse_threshold(threshold).ok_or_else(|| {
+            inconclusive(ErrorCode::InvalidThreshold, format!("Invalid pass threshold: {threshold}"))
+        }),
+    }
+}
+
+/// Turn a count of conforming records into a result and score
+fn graded(conforming: u64, total: u64, threshold: (u64, u64)) -> (ComplianceResult, Option<Score>) {
+    let score = Score::new(conforming, total, threshold);
+    (score.result(), Some(score).filter(Score::is_graded))
 }
 
 /// Registry of available checkers
@@ -67,6 +102,15 @@ impl CheckerRegistry {
             format!("No checker found for claim: {claim}"),
         ))
     }
+
+    /// Checker for a spec: its rule expression if it has one, otherwise
+    /// the checker registered for its claim
+    pub fn checker_for(&self, spec: &ComplianceSpec) -> Result<&dyn ComplianceChecker> {
+        if spec.rule.is_some() {
+            return Ok(&rule::RuleChecker);
+        }
+        self.get_checker(&spec.claim)
+    }
 }
 
 impl Default for CheckerRegistry {
@@ -1,16 +1,19 @@
 //! ML model usage constraint checker
 
-use fuse_core::{ComplianceSpec, ComplianceResult, Result};
+use fuse_core::{ComplianceSpec, ComplianceResult, Result, Score, ErrorCode};
 use crate::ComplianceChecker;
 use serde_json::Value;
 
 pub struct MlModelChecker;
 
-impl ComplianceChecker for MlModelChecker {
-    fn check(&self, spec: &ComplianceSpec, system_data: &str) -> Result<ComplianceResult> {
+impl MlModelChecker {
+    fn evaluate(spec: &ComplianceSpec, system_data: &str) -> Result<(ComplianceResult, Option<Score>)> {
+        let threshold = crate::pass_threshold(spec)?;
+
         let data: Value = serde_json::from_str(system_data)
-            .map_err(|e| fuse_core::VceError::InvalidSpec(
-                format!("Failed to parse system data: {e}")
+            .map_err(|e| crate::inconclusive(
+                ErrorCode::SystemDataParse,
+                format!("Failed to parse system data: {e}"),
             ))?;
 
         // Extract usage constraints
@@ -27,31 +30,49 @@ impl ComplianceChecker for MlModelChecker {
         // Check model usage
         let usage_logs = data.get("usage_logs")
             .and_then(|v| v.as_array())
-            .ok_or_else(|| fuse_core::VceError::InvalidSpec(
-                "System data must contain 'usage_logs' array".to_string()
+            .ok_or_else(|| crate::inconclusive(
+                ErrorCode::MissingField,
+                "System data must contain 'usage_logs' array".to_string(),
             ))?;
 
         // Check total usage count
         if usage_logs.len() as u64 > max_usage {
-            return Ok(ComplianceResult::Fail);
+            return Ok((ComplianceResult::Fail, None));
         }
 
         // Check domain restrictions if specified
-        if !allowed_domains.is_empty() {
-            for log in usage_logs {
-                let domain = log.get("domain")
-                    .and_then(|v| v.as_str())
-                    .ok_or_else(|| fuse_core::VceError::InvalidSpec(
-                        "Each usage log must have a 'domain' field".to_string()
-                    ))?;
-
-                if !allowed_domains.contains(&domain) {
-                    return Ok(ComplianceResult::Fail);
-                }
+        if allowed_domains.is_empty() {
+            return Ok((ComplianceResult::Pass, None));
+        }
+
+        let mut in_domain = 0;
+        for log in usage_logs {
+            let domain = log.get("domain")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| crate::inconclusive(
+                    ErrorCode::MissingField,
+                    "Each usage log must have a 'domain' field".to_string(),
+                ))?;
+
+            if allowed_domains.contains(&domain) {
+                in_domain += 1;
             }
         }
 
-        Ok(ComplianceResult::Pass)
+        Ok(crate::graded(in_domain, usage_logs.len() as u64, threshold))
     }
 }
 
+impl ComplianceChecker for MlModelChecker {
+    fn check(&self, spec: &ComplianceSpec, system_data: &str) -> Result<ComplianceResult> {
+        Ok(Self::evaluate(spec, system_data)?.0)
+    }
+
+    fn score(&self, spec: &ComplianceSpec, system_data: &str) -> Result<Option<Score>> {
+        Ok(Self::evaluate(spec, system_data)?.1)
+    }
+
+    fn check_scored(&self, spec: &ComplianceSpec, system_data: &str) -> Result<(ComplianceResult, Option<Score>)> {
+        Self::evaluate(spec, system_data)
+    }
+}
@@ -0,0 +1,34 @@
+//! Rule expression checker
+//!
+//! Evaluates the spec's `rule` with the same `fuse-rules` engine the guest
+//! program uses, so host and zkVM results agree.
+
+use crate::ComplianceChecker;
+use fuse_core::{ComplianceResult, ComplianceSpec, ErrorCode, Result};
+use fuse_rules::RuleError;
+
+pub struct RuleChecker;
+
+impl ComplianceChecker for RuleChecker {
+    fn check(&self, spec: &ComplianceSpec, system_data: &str) -> Result<ComplianceResult> {
+        let rule = spec.rule.as_deref().ok_or_else(|| {
+            crate::inconclusive(ErrorCode::InvalidRule, "Spec has no rule expression".to_string())
+        })?;
+        let data: serde_json::Value = serde_json::from_str(system_data)
+            .map_err(|e| crate::inconclusive(ErrorCode::SystemDataParse, format!("Failed to parse system data: {e}")))?;
+
+        let passed = fuse_rules::evaluate(rule, &data)
+            .map_err(|e| crate::inconclusive(error_code(&e), format!("Rule evaluation failed: {e}")))?;
+
+        Ok(if passed { ComplianceResult::Pass } else { ComplianceResult::Fail })
+    }
+}
+
+/// Journal error code the guest program commits for a rule error
+fn error_code(error: &RuleError) -> ErrorCode {
+    match error {
+        RuleError::Parse { .. } => ErrorCode::InvalidRule,
+        RuleError::MissingPath(_) => ErrorCode::MissingField,
+        RuleError::Type(_) => ErrorCode::RuleType,
+    }
+}
@@ -1,17 +1,20 @@
 //! SOC2 compliance checker
 
-use fuse_core::{ComplianceSpec, ComplianceResult, Result};
+use fuse_core::{ComplianceSpec, ComplianceResult, Result, Score, ErrorCode};
 use crate::ComplianceChecker;
 use serde_json::Value;
 
 pub struct Soc2ControlXChecker;
 
-impl ComplianceChecker for Soc2ControlXChecker {
-    fn check(&self, spec: &ComplianceSpec, system_data: &str) -> Result<ComplianceResult> {
+impl Soc2ControlXChecker {
+    fn evaluate(spec: &ComplianceSpec, system_data: &str) -> Result<(ComplianceResult, Option<Score>)> {
+        let threshold = crate::pass_threshold(spec)?;
+
         // Parse system data (expected to be JSON with access logs)
         let data: Value = serde_json::from_str(system_data)
-            .map_err(|e| fuse_core::VceError::InvalidSpec(
-                format!("Failed to parse system data: {e}")
+            .map_err(|e| crate::inconclusive(
+                ErrorCode::SystemDataParse,
+                format!("Failed to parse system data: {e}"),
             ))?;
 
         // Extract sampling constraint
@@ -21,34 +24,61 @@ impl ComplianceChecker for Soc2ControlXChecker {
             .and_then(|s| s.parse::<usize>().ok())
             .unwrap_or(1000);
 
+        // Fields every log must carry (e.g. "timestamp,user,mfa")
+        let required_fields = spec.constraints
+            .get("required_fields")
+            .map_or_else(|| vec!["timestamp", "user"], |s| s.split(',').map(str::trim).collect());
+
         // Check if we have access logs
         let logs = data.get("access_logs")
             .and_then(|v| v.as_array())
-            .ok_or_else(|| fuse_core::VceError::InvalidSpec(
-                "System data must contain 'access_logs' array".to_string()
+            .ok_or_else(|| crate::inconclusive(
+                ErrorCode::MissingField,
+                "System data must contain 'access_logs' array".to_string(),
             ))?;
 
         // Verify we have at least the required sample size
         if logs.len() < sample_size {
-            return Ok(ComplianceResult::Fail);
+            return Ok((ComplianceResult::Fail, None));
         }
 
-        // Check that all logs in the sample conform to control X
+        // Count the logs in the sample that conform to control X
         // For MVP, we check that each log has required fields
+        let mut conforming = 0;
+        let mut sampled = 0;
         for log in logs.iter().take(sample_size) {
-            if !log.is_object() {
-                return Ok(ComplianceResult::Fail);
-            }
+            let obj = log.as_object()
+                .ok_or_else(|| crate::inconclusive(
+                    ErrorCode::MalformedRecord,
+                    "Each access log must be a JSON object".to_string(),
+                ))?;
 
-            // Basic validation: ensure required fields exist
-            // In production, this would be more sophisticated
-            let obj = log.as_object().unwrap();
-            if !obj.contains_key("timestamp") || !obj.contains_key("user") {
-                return Ok(ComplianceResult::Fail);
+            // Every required field must be set, so `"mfa": false` does not conform
+            if required_fields.iter().all(|field| obj.get(*field).is_some_and(is_set)) {
+                conforming += 1;
             }
+            sampled += 1;
         }
 
-        Ok(ComplianceResult::Pass)
+        Ok(crate::graded(conforming, sampled, threshold))
     }
 }
 
+/// A log field counts as set unless it is `null`, `false` or `""`
+fn is_set(value: &Value) -> bool {
+    !matches!(value, Value::Null | Value::Bool(false)) && value.as_str() != Some("")
+}
+
+impl ComplianceChecker for Soc2ControlXChecker {
+    fn check(&self, spec: &ComplianceSpec, system_data: &str) -> Result<ComplianceResult> {
+        Ok(Self::evaluate(spec, system_data)?.0)
+    }
+
+    fn score(&self, spec: &ComplianceSpec, system_data: &str) -> Result<Option<Score>> {
+        Ok(Self::evaluate(spec, system_data)?.1)
+    }
+
+    fn check_scored(&self, spec: &ComplianceSpec, system_data: &str) -> Result<(ComplianceResult, Option<Score>)> {
+        Self::evaluate(spec, system_data)
+    }
+}
@@ -1,48 +1,68 @@
 //! Supply chain provenance checker
 
-use fuse_core::{ComplianceSpec, ComplianceResult, Result};
+use fuse_core::{ComplianceSpec, ComplianceResult, Result, Score, ErrorCode};
 use crate::ComplianceChecker;
 use serde_json::Value;
 
 pub struct SupplyChainChecker;
 
-impl ComplianceChecker for SupplyChainChecker {
-    fn check(&self, spec: &ComplianceSpec, system_data: &str) -> Result<ComplianceResult> {
+impl SupplyChainChecker {
+    fn evaluate(spec: &ComplianceSpec, system_data: &str) -> Result<(ComplianceResult, Option<Score>)> {
+        let threshold = crate::pass_threshold(spec)?;
+
         let data: Value = serde_json::from_str(system_data)
-            .map_err(|e| fuse_core::VceError::InvalidSpec(
-                format!("Failed to parse system data: {e}")
+            .map_err(|e| crate::inconclusive(
+                ErrorCode::SystemDataParse,
+                format!("Failed to parse system data: {e}"),
             ))?;
 
         // Extract required provenance chain
         let required_chain = spec.constraints
             .get("provenance_chain")
-            .ok_or_else(|| fuse_core::VceError::InvalidSpec(
-                "Supply chain checker requires 'provenance_chain' constraint".to_string()
+            .ok_or_else(|| crate::inconclusive(
+                ErrorCode::MissingConstraint,
+                "Supply chain checker requires 'provenance_chain' constraint".to_string(),
             ))?;
 
         // Check components
         let components = data.get("components")
             .and_then(|v| v.as_array())
-            .ok_or_else(|| fuse_core::VceError::InvalidSpec(
-                "System data must contain 'components' array".to_string()
+            .ok_or_else(|| crate::inconclusive(
+                ErrorCode::MissingField,
+                "System data must contain 'components' array".to_string(),
             ))?;
 
-        // Verify each component has valid provenance
+        // Count components with valid provenance
+        let mut verified = 0;
         for component in components {
             let provenance = component.get("provenance")
                 .and_then(|v| v.as_str())
-                .ok_or_else(|| fuse_core::VceError::InvalidSpec(
-                    "Each component must have a 'provenance' field".to_string()
+                .ok_or_else(|| crate::inconclusive(
+                    ErrorCode::MissingField,
+                    "Each component must have a 'provenance' field".to_string(),
                 ))?;
 
             // Basic validation: check that provenance matches required chain
             // In production, this would verify cryptographic signatures
-            if !provenance.contains(required_chain) {
-                return Ok(ComplianceResult::Fail);
+            if provenance.contains(required_chain.as_str()) {
+                verified += 1;
             }
         }
 
-        Ok(ComplianceResult::Pass)
+        Ok(crate::graded(verified, components.len() as u64, threshold))
     }
 }
 
+impl ComplianceChecker for SupplyChainChecker {
+    fn check(&self, spec: &ComplianceSpec, system_data: &str) -> Result<ComplianceResult> {
+        Ok(Self::evaluate(spec, system_data)?.0)
+    }
+
+    fn score(&self, spec: &ComplianceSpec, system_data: &str) -> Result<Option<Score>> {
+        Ok(Self::evaluate(spec, system_data)?.1)
+    }
+
+    fn check_scored(&self, spec: &ComplianceSpec, system_data: &str) -> Result<(ComplianceResult, Option<Score>)> {
+        Self::evaluate(spec, system_data)
+    }
+}
@@ -0,0 +1,96 @@
+//! CLI tool for aggregating Verifiable Compliance Envelopes
+//!
+//! Rolls N child envelopes (e.g. one per subsidiary) up into one envelope
+//! for a group-level spec. The guest verifies every child receipt inside
+//! the zkVM, so the aggregate proves each child's result without the
+//! verifier needing the children. The group spec's `pass_threshold` sets
+//! the fraction of children that must pass (all of them by default).
+
+use clap::Parser;
+use fuse_cli::prover::ProverArgs;
+use fuse_core::{ComplianceProof, ComplianceSpec, Result, VceError, VerifiableComplianceEnvelope};
+use std::path::PathBuf;
+
+#[derive(Parser)]
+#[command(name = "fuse-aggregate")]
+#[command(about = "Aggregate Verifiable Compliance Envelopes into one envelope for a group-level spec")]
+struct Args {
+    /// Path to the group-level compliance specification (JSON or YAML)
+    #[arg(short, long)]
+    spec: PathBuf,
+
+    /// Child .vce files to aggregate
+    #[arg(required = true)]
+    children: Vec<PathBuf>,
+
+    /// Output path for the aggregate .vce file
+    #[arg(short, long, default_value = "aggregate.vce")]
+    output: PathBuf,
+
+    #[command(flatten)]
+    proving: ProverArgs,
+}
+
+fn main() -> Result<()> {
+    fuse_cli::logging::init();
+    let args = Args::parse();
+
+    println!("🔍 Loading group specification...");
+    let spec = if matches!(args.spec.extension().and_then(|s| s.to_str()), Some("yaml" | "yml")) {
+        ComplianceSpec::from_yaml_file(&args.spec)?
+    } else {
+        ComplianceSpec::from_json_file(&args.spec)?
+    };
+    println!("   Claim: {}", spec.claim);
+
+    println!("\n📦 Verifying child envelopes...");
+    let mut child_receipts = Vec::with_capacity(args.children.len());
+    for path in &args.children {
+        let mut child = VerifiableComplianceEnvelope::from_file(path)?;
+        if child.proof.is_placeholder() {
+            return Err(VceError::ProofVerificationFailed(format!(
+                "{} has a placeholder proof; only real proofs can be aggregated",
+                path.display()
+            )));
+        }
+        // A batch entry's receipt proves the whole batch, not just this spec
+        if child.proof.batch_entry.is_some() {
+            return Err(VceError::ProofVerificationFailed(format!(
+                "{} is one entry of a batch proof; aggregate specs proved on their own",
+                path.display()
+            )));
+        }
+        child.verify()?;
+        println!("   ✓ {}: {} ({})", path.display(), child.spec.claim, child.proof.result);
+        child_receipts.push(child.proof.proof_data);
+    }
+
+    println!("\n🔐 Generating aggregate proof...");
+    let spec_json = spec.to_proof_json()?;
+    let mut inputs = fuse_core::zkvm::ProofInputs::with_mode(fuse_core::zkvm::ProofMode::Aggregate {
+        spec_json: &spec_json,
+        child_receipts: &child_receipts,
+    });
+    let options = args.proving.options();
+    if let Some(cache) = &options.cache {
+        cache.reuse_seed(&mut inputs, &options);
+    }
+    // Aggregation only means something for real proofs; no placeholder fallback
+    let (receipt_bytes, output, journal) = fuse_core::zkvm::generate_proof_with_inputs(&inputs, options)?;
+    println!("   ✓ Aggregate proof generated");
+    println!("   Result: {}", output.result);
+    if output.score.is_graded() {
+        println!("   Score: {}", output.score);
+    }
+
+    let proof = ComplianceProof::from_risc_zero_receipt(spec.hash(), receipt_bytes, output, journal);
+    let envelope = VerifiableComplianceEnvelope::new(spec, proof);
+
+    println!("\n💾 Saving envelope to {}...", args.output.display());
+    envelope.to_file(&args.output)?;
+
+    println!("\n✅ Success! Aggregate envelope created at {}", args.output.display());
+    println!("   Verify with: fuse-verify {}", args.output.display());
+
+    Ok(())
+}
@@ -0,0 +1,112 @@
+//! CLI tool for auditing the evidence behind a Verifiable Compliance Envelope
+//!
+//! Every proof commits a digest of the spec, the system data and any extra
+//! evidence files it was given. When the prover hands over the evidence
+//! in a dispute, `fuse-audit-evidence` confirms it is exactly what was
+//! proven. The spec is checked against the envelope's own copy.
+
+use clap::Parser;
+use fuse_core::evidence::{self, EvidenceCommitment};
+use fuse_core::{Result, VceError, VerifiableComplianceEnvelope};
+use std::path::PathBuf;
+
+#[derive(Parser)]
+#[command(name = "fuse-audit-evidence")]
+#[command(about = "Confirm evidence files match the digests committed by a Verifiable Compliance Envelope")]
+struct Args {
+    /// Path to the .vce file
+    envelope: PathBuf,
+
+    /// Evidence file as `name=path`, or `path` to use the file name; the
+    /// system data is named `system_data`
+    #[arg(value_name = "[NAME=]PATH")]
+    files: Vec<String>,
+
+    /// Path to the system data the envelope was proved over
+    /// (shorthand for `system_data=PATH`)
+    #[arg(short, long)]
+    system: Option<PathBuf>,
+
+    /// Evidence bundle the envelope was proved over: a directory, a .tar
+    /// archive, or a JSON manifest (as passed to fuse-prove --bundle)
+    #[arg(long)]
+    bundle: Option<PathBuf>,
+}
+
+fn main() -> Result<()> {
+    fuse_cli::logging::init();
+    let args = Args::parse();
+    match audit(&args) {
+        Ok(true) => {
+            println!("\n✅ All supplied evidence matches the proof");
+            Ok(())
+        }
+        Ok(false) => {
+            println!("\n❌ Evidence does not match the proof");
+            std::process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("❌ Evidence audit failed: {e}");
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Check every supplied file against its commitment; false on any mismatch
+fn audit(args: &Args) -> Result<bool> {
+    println!("📦 Loading Verifiable Compliance Envelope...");
+    let mut envelope = VerifiableComplianceEnvelope::from_file(&args.envelope)?;
+    if envelope.proof.is_placeholder() {
+        return Err(VceError::ProofVerificationFailed(
+            "Placeholder proofs carry no evidence commitments".to_string(),
+        ));
+    }
+    envelope.verify()?;
+    println!("   ✓ Envelope verified");
+
+    let commitments = envelope.proof.evidence();
+    if commitments.is_empty() {
+        return Err(VceError::ProofVerificationFailed(
+            "Envelope predates evidence commitments".to_string(),
+        ));
+    }
+
+    // fuse-prove passes the spec to the guest in its serialized form
+    let mut supplied = vec![(evidence::SPEC.to_string(), serde_json::to_vec(&envelope.spec)?)];
+    if let Some(system) = &args.system {
+        supplied.push((evidence::SYSTEM_DATA.to_string(), std::fs::read(system)?));
+    }
+    if let Some(bundle) = &args.bundle {
+        supplied.extend(evidence::load_bundle(bundle)?.into_iter().map(|file| (file.name, file.bytes)));
+    }
+    for arg in &args.files {
+        let file = fuse_cli::evidence::attachment(arg)?;
+        supplied.push((file.name, file.bytes));
+    }
+
+    println!("\n🧾 Auditing evidence...");
+    let mut all_match = true;
+    for (name, bytes) in &supplied {
+        match find(&commitments, name) {
+            Some(commitment) if commitment.matches(bytes) => println!("   ✓ {name}: matches"),
+            Some(_) => {
+                println!("   ✗ {name}: does not match the committed digest");
+                all_match = false;
+            }
+            None => {
+                println!("   ✗ {name}: not committed by this proof");
+                all_match = false;
+            }
+        }
+    }
+    for commitment in &commitments {
+        if !supplied.iter().any(|(name, _)| *name == commitment.name) {
+            println!("   - {}: not supplied", commitment.name);
+        }
+    }
+    Ok(all_match)
+}
+
+fn find<'a>(commitments: &'a [EvidenceCommitment], name: &str) -> Option<&'a EvidenceCommitment> {
+    commitments.iter().find(|commitment| commitment.name == name)
+}
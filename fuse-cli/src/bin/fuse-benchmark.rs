@@ -4,6 +4,8 @@
 //! to establish baseline performance and compare with Ed25519 verification.
 
 use clap::Parser;
+use fuse_core::typed::TypedSystemData;
+use fuse_core::zkvm::ProofMode;
 use fuse_core::{zkvm, ProverType};
 use serde_json::json;
 use std::fs;
@@ -32,6 +34,15 @@ struct Args {
     /// Output results as JSON
     #[arg(short, long)]
     json: bool,
+
+    /// Pass the system data to the guest as typed binary input instead of JSON
+    #[arg(long)]
+    binary_input: bool,
+
+    /// Execute without proving and compare guest cycle counts of the
+    /// checker's direct field reads against a full `serde_json::Value` parse
+    #[arg(long, conflicts_with = "binary_input")]
+    cycles: bool,
 }
 
 struct BenchmarkResult {
@@ -53,6 +64,7 @@ struct BenchmarkSummary {
 }
 
 fn main() -> anyhow::Result<()> {
+    fuse_cli::logging::init();
     let args = Args::parse();
 
     println!("FUSE Benchmark Tool");
@@ -61,6 +73,7 @@ fn main() -> anyhow::Result<()> {
     println!("Spec: {}", args.spec);
     println!("System: {}", args.system);
     println!("Iterations: {}", args.iterations);
+    println!("Input: {}", if args.binary_input { "typed binary" } else { "JSON" });
     println!();
 
     // Read spec and system data
@@ -69,6 +82,21 @@ fn main() -> anyhow::Result<()> {
     let system_json = fs::read_to_string(&args.system)
         .map_err(|e| anyhow::anyhow!("Failed to read system file {}: {}", args.system, e))?;
 
+    let typed_system_data = if args.binary_input {
+        let system_data: serde_json::Value = serde_json::from_str(&system_json)?;
+        Some(TypedSystemData::from_json(&system_data))
+    } else {
+        None
+    };
+    let inputs = match &typed_system_data {
+        Some(system_data) => zkvm::ProofInputs::with_mode(ProofMode::Typed { spec_json: &spec_json, system_data }),
+        None => zkvm::ProofInputs::new(&spec_json, &system_json),
+    };
+
+    if args.cycles {
+        return compare_cycles(&inputs, args.json);
+    }
+
     // Run benchmarks
     let mut results = Vec::new();
 
@@ -76,7 +104,7 @@ fn main() -> anyhow::Result<()> {
         println!("Running iteration {}/{}...", i, args.iterations);
         let start = Instant::now();
 
-        match zkvm::generate_proof(&spec_json, &system_json, ProverType::Local) {
+        match zkvm::generate_proof_with_inputs(&inputs, ProverType::Local) {
             Ok((_receipt_bytes, result, _journal_bytes)) => {
                 let duration = start.elapsed();
                 let duration_secs = duration.as_secs_f64();
@@ -188,3 +216,35 @@ fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
+
+/// Execute the guest reading only the checker's fields, then building the
+/// full JSON tree, and report the cycle counts of each
+fn compare_cycles(inputs: &zkvm::ProofInputs<'_>, json_output: bool) -> anyhow::Result<()> {
+    let ProofMode::Single { spec_json, system_data_json, .. } = inputs.mode else {
+        anyhow::bail!("Cycle comparison needs JSON system data");
+    };
+    let (direct_output, direct) = zkvm::execute(inputs)?;
+    let full = ProofMode::Single { spec_json, system_data_json, parse_full_json: true };
+    let (value_output, value) = zkvm::execute(&zkvm::ProofInputs { mode: full, ..inputs.clone() })?;
+    if direct_output.result != value_output.result {
+        anyhow::bail!("Direct and full parse disagree: {:?} vs {:?}", direct_output.result, value_output.result);
+    }
+
+    let reduction = 100.0 * (1.0 - direct.user_cycles as f64 / value.user_cycles.max(1) as f64);
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&json!({
+            "result": format!("{:?}", direct_output.result),
+            "direct": {"user_cycles": direct.user_cycles, "total_cycles": direct.total_cycles, "segments": direct.segments},
+            "value": {"user_cycles": value.user_cycles, "total_cycles": value.total_cycles, "segments": value.segments},
+            "user_cycle_reduction_percent": reduction
+        }))?);
+    } else {
+        println!("Cycle Comparison");
+        println!("================");
+        println!("Result: {:?}", direct_output.result);
+        println!("Direct reads: {} user cycles ({} total, {} segments)", direct.user_cycles, direct.total_cycles, direct.segments);
+        println!("Value parse:  {} user cycles ({} total, {} segments)", value.user_cycles, value.total_cycles, value.segments);
+        println!("Reduction:    {reduction:.1}% of user cycles");
+    }
+    Ok(())
+}
@@ -0,0 +1,141 @@
+//! CLI tool for managing the proof cache
+//!
+//! `fuse-prove --cache` and `fuse-aggregate --cache` reuse receipts of
+//! identical inputs from an on-disk cache. `fuse-cache ls` lists what is
+//! cached, `fuse-cache prune` removes old entries and those of other guest
+//! program builds, and `fuse-cache verify` re-verifies every receipt.
+
+use clap::{Parser, Subcommand};
+use fuse_core::cache::{self, CacheEntry, ProofCache};
+use fuse_core::registry::{CheckerId, ImageRegistry};
+use fuse_core::{Result, VceError};
+use std::path::PathBuf;
+
+#[derive(Parser)]
+#[command(name = "fuse-cache")]
+#[command(about = "List, prune and verify cached proofs")]
+struct Args {
+    /// Proof cache directory ($FUSE_CACHE_DIR or ~/.cache/fuse/proofs by default)
+    #[arg(long, global = true)]
+    dir: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// List cached proofs, oldest first
+    Ls,
+    /// Remove proofs of other guest program builds, and older ones if asked
+    Prune {
+        /// Also remove proofs cached more than this many days ago
+        #[arg(long)]
+        older_than: Option<u32>,
+
+        /// Remove every cached proof
+        #[arg(long)]
+        all: bool,
+    },
+    /// Re-verify every cached receipt
+    Verify {
+        /// Remove entries that fail verification
+        #[arg(long)]
+        remove: bool,
+    },
+}
+
+fn main() -> Result<()> {
+    fuse_cli::logging::init();
+    let args = Args::parse();
+    let cache = ProofCache::new(args.dir.unwrap_or_else(ProofCache::default_dir));
+    let entries = cache.entries()?;
+
+    match args.command {
+        Command::Ls => {
+            println!("📦 {} cached proofs in {}", entries.len(), cache.dir().display());
+            let registry = ImageRegistry::built();
+            let mut total = 0;
+            for entry in &entries {
+                let checker = match checker(registry, entry) {
+                    Some(checker) => format!("  {checker}"),
+                    None if registry.iter().next().is_some() => " (other guest build)".to_string(),
+                    None => String::new(),
+                };
+                println!("   {}  {}  {:>5}  {}{checker}", cache::short(&entry.key), entry.created_at.format("%Y-%m-%d %H:%M"), entry.result, size(entry.receipt_size));
+                total += entry.receipt_size;
+            }
+            println!("   Total: {}", size(total));
+        }
+        Command::Prune { older_than, all } => {
+            // Without the guest programs there is no telling which entries are stale
+            let registry = ImageRegistry::built();
+            let built = registry.iter().next().is_some();
+            if !built && !all && older_than.is_none() {
+                println!("⚠ Guest programs not built; pass --older-than or --all to prune");
+            }
+            let cutoff = older_than.map(|days| chrono::Utc::now() - chrono::Duration::days(i64::from(days)));
+            let mut removed = 0;
+            for entry in &entries {
+                let stale = built && checker(registry, entry).is_none();
+                if all || stale || cutoff.is_some_and(|cutoff| entry.created_at < cutoff) {
+                    cache.remove(entry)?;
+                    removed += 1;
+                }
+            }
+            println!("🧹 Removed {removed} of {} cached proofs", entries.len());
+        }
+        Command::Verify { remove } => {
+            let registry = ImageRegistry::built();
+            println!("🔍 Verifying {} cached proofs...", entries.len());
+            let failed: Vec<&CacheEntry> = entries
+                .iter()
+                .filter(|entry| match verify(&cache, registry, entry) {
+                    Ok(_) => {
+                        println!("   ✓ {}", cache::short(&entry.key));
+                        false
+                    }
+                    Err(e) => {
+                        println!("   ❌ {}: {e}", cache::short(&entry.key));
+                        true
+                    }
+                })
+                .collect();
+            if failed.is_empty() {
+                println!("\n✅ All cached proofs verify");
+                return Ok(());
+            }
+            if remove {
+                for entry in &failed {
+                    cache.remove(entry)?;
+                }
+                println!("\n🧹 Removed {} cached proofs that failed verification", failed.len());
+                return Ok(());
+            }
+            println!("\n❌ {} cached proofs failed verification; rerun with --remove to drop them", failed.len());
+            std::process::exit(1);
+        }
+    }
+    Ok(())
+}
+
+/// Checker of the registered guest program that proved an entry
+fn checker(registry: &ImageRegistry, entry: &CacheEntry) -> Option<CheckerId> {
+    registry.iter().find(|(_, image_id)| hex::encode(image_id.as_bytes()) == entry.image_id).map(|(checker, _)| checker)
+}
+
+/// Verify an entry against the registered guest program that proved it
+fn verify(cache: &ProofCache, registry: &ImageRegistry, entry: &CacheEntry) -> Result<()> {
+    let image_id = checker(registry, entry)
+        .and_then(|checker| registry.image_id(checker))
+        .ok_or_else(|| VceError::ProofVerificationFailed("Cached proof is from an unknown guest program".to_string()))?;
+    cache.verify(entry, image_id).map(|_| ())
+}
+
+fn size(bytes: u64) -> String {
+    match bytes {
+        0..=1023 => format!("{bytes} B"),
+        1024..=1_048_575 => format!("{:.1} KiB", bytes as f64 / 1024.0),
+        _ => format!("{:.1} MiB", bytes as f64 / 1_048_576.0),
+    }
+}
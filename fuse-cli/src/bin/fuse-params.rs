@@ -0,0 +1,109 @@
+//! CLI tool for private spec parameters
+//!
+//! `fuse-params seal` moves confidential constraint values out of a spec,
+//! replacing them with salted commitments in `private_constraints` and
+//! writing the values and salts to a separate private file for the prover.
+//! `fuse-params check` lets a regulator holding that file confirm which
+//! policy an envelope was proved under.
+
+use clap::{Parser, Subcommand};
+use fuse_core::private_params::{self, PrivateParams};
+use fuse_core::{ComplianceSpec, Result, VceError, VerifiableComplianceEnvelope};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+#[derive(Parser)]
+#[command(name = "fuse-params")]
+#[command(about = "Seal and check private spec parameters")]
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Replace constraints with salted commitments
+    Seal {
+        /// Path to the compliance specification file (JSON)
+        #[arg(short, long)]
+        spec: PathBuf,
+
+        /// Name of a constraint to make private (repeatable)
+        #[arg(short, long = "constraint", required = true)]
+        constraints: Vec<String>,
+
+        /// Output path for the public spec
+        #[arg(long, default_value = "spec.sealed.json")]
+        output_spec: PathBuf,
+
+        /// Output path for the private parameters (keep confidential)
+        #[arg(long, default_value = "private-params.json")]
+        output_params: PathBuf,
+    },
+    /// Check an envelope was proved with these private parameters
+    Check {
+        /// Path to the .vce file
+        #[arg(short, long)]
+        envelope: PathBuf,
+
+        /// Path to the private parameters file
+        params: PathBuf,
+    },
+}
+
+fn main() -> Result<()> {
+    fuse_cli::logging::init();
+    match Args::parse().command {
+        Command::Seal { spec, constraints, output_spec, output_params } => {
+            let mut spec = ComplianceSpec::from_json_file(&spec)?;
+
+            let mut values = BTreeMap::new();
+            for name in constraints {
+                let value = spec.constraints.remove(&name).ok_or_else(|| {
+                    VceError::InvalidSpec(format!("Spec has no constraint '{name}'"))
+                })?;
+                values.insert(name, value);
+            }
+
+            let (commitments, params) = private_params::seal(&values);
+            spec.private_constraints.get_or_insert_with(BTreeMap::new).extend(commitments);
+            spec.validate()?;
+
+            spec.to_json_file(&output_spec)?;
+            std::fs::write(&output_params, serde_json::to_string_pretty(&params)?)?;
+            println!("✅ Sealed {} constraint(s)", values.len());
+            println!("   Public spec: {}", output_spec.display());
+            println!("   Private parameters: {} (keep confidential)", output_params.display());
+            Ok(())
+        }
+        Command::Check { envelope, params } => {
+            let mut envelope = VerifiableComplianceEnvelope::from_file(&envelope)?;
+            let params: PrivateParams = serde_json::from_str(&std::fs::read_to_string(params)?)?;
+
+            let result = envelope.verify().and_then(|()| {
+                let expected = private_params::verify(&envelope.spec, &params)?;
+                // Placeholder proofs have no journal; real ones must commit the same parameters
+                match envelope.proof.output() {
+                    Some(output) if output.private_params != expected => Err(VceError::ProofVerificationFailed(
+                        "Journal commits different private parameters".to_string(),
+                    )),
+                    _ => Ok(expected),
+                }
+            });
+
+            match result {
+                Ok(verified) => {
+                    println!("✅ Envelope was proved under these private parameters:");
+                    for commitment in verified {
+                        println!("   {} ({})", commitment.name, hex::encode(&commitment.commitment));
+                    }
+                    Ok(())
+                }
+                Err(e) => {
+                    eprintln!("❌ Private parameter check failed: {e}");
+                    std::process::exit(1);
+                }
+            }
+        }
+    }
+}
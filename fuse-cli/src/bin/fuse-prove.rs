@@ -1,130 +1,402 @@
 //! CLI tool for generating Verifiable Compliance Envelopes
 
-use clap::{Parser, ValueEnum};
-use fuse_core::{ComplianceSpec, VerifiableComplianceEnvelope, Result, ProverType};
+use chrono::{DateTime, Utc};
+use clap::Parser;
+use fuse_core::{ComplianceSpec, VerifiableComplianceEnvelope, Result};
+use fuse_core::chain::ChainLink;
+use fuse_core::private_params::PrivateParams;
+use fuse_core::typed::TypedSystemData;
+use fuse_core::zkvm::{BatchData, ProofMode};
 use fuse_checkers::CheckerRegistry;
+use fuse_cli::prover::ProverArgs;
 use std::path::PathBuf;
 
-/// CLI argument representation of `ProverType`
-#[derive(Debug, Clone, Copy, ValueEnum)]
-enum ProverTypeArg {
-    /// Local CPU prover (default)
-    Local,
-    /// GPU-accelerated prover
-    Gpu,
-}
-
-impl From<ProverTypeArg> for ProverType {
-    fn from(arg: ProverTypeArg) -> Self {
-        match arg {
-            ProverTypeArg::Local => ProverType::Local,
-            ProverTypeArg::Gpu => ProverType::Gpu,
-        }
-    }
-}
-
 #[derive(Parser)]
 #[command(name = "fuse-prove")]
 #[command(about = "Generate a Verifiable Compliance Envelope from a spec and system data")]
 struct Args {
-    /// Path to the compliance specification file (JSON or YAML)
-    #[arg(short, long)]
-    spec: PathBuf,
+    /// Path to the compliance specification file (JSON or YAML); repeat to
+    /// prove several specs against the same system data in one proof
+    #[arg(short, long, required = true)]
+    spec: Vec<PathBuf>,
 
     /// Path to the system data file to verify
-    #[arg(short, long)]
-    system: PathBuf,
+    #[arg(short, long, required_unless_present_any = ["bundle", "evidence", "stream"])]
+    system: Option<PathBuf>,
+
+    /// Evidence bundle forming the system data: a directory, a .tar archive,
+    /// or a JSON manifest mapping names to file paths
+    #[arg(long, conflicts_with = "system")]
+    bundle: Option<PathBuf>,
 
-    /// Output path for the .vce file
+    /// Named JSON document to add to the evidence bundle, as `name=path` or
+    /// just `path` (named after the file stem); can be repeated
+    #[arg(long, value_name = "[NAME=]PATH", conflicts_with = "system")]
+    evidence: Vec<String>,
+
+    /// Output path for the .vce file; with several specs, one envelope per
+    /// spec is written with its index appended (`compliance-0.vce`, ...)
     #[arg(short, long, default_value = "compliance.vce")]
     output: PathBuf,
 
-    /// Prover type to use for proof generation
-    /// 
-    /// - local: CPU-based proving (default, always available)
-    /// - gpu: GPU-accelerated proving (requires GPU hardware and --features gpu)
-    #[arg(long, default_value = "local", value_enum)]
-    prover: ProverTypeArg,
+    #[command(flatten)]
+    proving: ProverArgs,
+
+    /// Path to the private parameters file for the spec's `private_constraints`
+    /// (as written by `fuse-params seal`); passed to the guest privately
+    #[arg(long)]
+    private_params: Option<PathBuf>,
+
+    /// Extra evidence file to commit by digest, as `name=path` or just `path`
+    /// (named after the file); can be repeated
+    #[arg(long = "attach", value_name = "[NAME=]PATH")]
+    attachments: Vec<String>,
+
+    /// Pass the system data to the guest pre-parsed in binary form (faster
+    /// on large logs); its canonical JSON is saved next to the envelope for
+    /// audits
+    #[arg(long, conflicts_with_all = ["bundle", "evidence"])]
+    binary_input: bool,
+
+    /// JSON Lines file of the checker's records (e.g. one SOC2 access log
+    /// per line), streamed to the guest in chunks for inputs too large for
+    /// guest memory
+    #[arg(long, conflicts_with_all = ["system", "bundle", "evidence", "binary_input"])]
+    stream: Option<PathBuf>,
+
+    /// Commit a cumulative log state so later periods can continue from
+    /// this envelope; the stream holds only this period's new records
+    #[arg(long, requires = "stream")]
+    incremental: bool,
+
+    /// Envelope of the previous period to continue from; its receipt is
+    /// verified in the guest and its counters carried forward
+    #[arg(long, requires = "incremental")]
+    previous: Option<PathBuf>,
+
+    /// Previous envelope of the spec's lineage to link to, for continuous
+    /// monitoring; the new envelope covers the period from where it ended
+    #[arg(long, value_name = "PREVIOUS_VCE")]
+    chain: Option<PathBuf>,
+
+    /// Start of the period the envelope covers (RFC 3339); without --chain
+    /// this starts a new chain
+    #[arg(long)]
+    period_start: Option<DateTime<Utc>>,
+
+    /// End of the period the envelope covers (RFC 3339); defaults to now
+    #[arg(long)]
+    period_end: Option<DateTime<Utc>>,
 }
 
 fn main() -> Result<()> {
+    fuse_cli::logging::init();
     let args = Args::parse();
 
     println!("🔍 Loading compliance specification...");
-    let spec = if args.spec.extension().and_then(|s| s.to_str()) == Some("yaml")
-        || args.spec.extension().and_then(|s| s.to_str()) == Some("yml")
-    {
-        ComplianceSpec::from_yaml_file(&args.spec)?
-    } else {
-        ComplianceSpec::from_json_file(&args.spec)?
+    let specs = args.spec.iter().map(|path| load_spec(path)).collect::<Result<Vec<_>>>()?;
+    for spec in &specs {
+        println!("   Claim: {}", spec.claim);
+        println!("   Jurisdiction: {}", spec.jurisdiction);
+        println!("   Version: {}", spec.version);
+    }
+    let batch = specs.len() > 1;
+    if batch && (args.binary_input || args.stream.is_some()) {
+        return Err(fuse_core::VceError::InvalidSpec(
+            "Several specs can only be proved over JSON system data or an evidence bundle".to_string(),
+        ));
+    }
+
+    if batch && (args.chain.is_some() || args.period_start.is_some()) {
+        return Err(fuse_core::VceError::InvalidSpec(
+            "Chained envelopes are issued one spec at a time".to_string(),
+        ));
+    }
+    let chain_link = chain_link(&args, &specs[0])?;
+
+    let previous_receipt = match &args.previous {
+        Some(path) => Some(load_previous(path, &specs[0])?),
+        None => None,
     };
 
-    println!("   Claim: {}", spec.claim);
-    println!("   Jurisdiction: {}", spec.jurisdiction);
-    println!("   Version: {}", spec.version);
+    let private_params_json = match &args.private_params {
+        Some(path) => std::fs::read_to_string(path)?,
+        None => String::new(),
+    };
+    let private_params: PrivateParams = if private_params_json.is_empty() {
+        PrivateParams::new()
+    } else {
+        serde_json::from_str(&private_params_json)?
+    };
+    // Host-side checkers see the private values; the envelope keeps only commitments
+    let checked_specs = specs
+        .iter()
+        .map(|spec| fuse_core::private_params::apply(spec, &private_params))
+        .collect::<Result<Vec<_>>>()?;
+    for spec in &specs {
+        if let Some(commitments) = &spec.private_constraints {
+            println!("   Private parameters: {} (verified against commitments)", commitments.len());
+        }
+    }
 
     println!("\n📊 Loading system data...");
-    let extension = args.system.extension().and_then(|s| s.to_str()).unwrap_or_default().to_lowercase();
-    let is_media = ["jpg", "jpeg", "png", "c2pa"].contains(&extension.as_str());
-
-    let system_data = if is_media && spec.claim.to_lowercase().contains("c2pa") {
-        println!("   Detected media file with C2PA claim. Extracting manifest...");
-        let c2pa_data = fuse_cli::c2pa::parse_c2pa_manifest(args.system.to_str().unwrap_or_default())
-            .map_err(|e| fuse_core::VceError::InputSerialization(e.to_string()))?;
-        let json = fuse_cli::c2pa::c2pa_data_to_json(&c2pa_data);
-        serde_json::to_string(&json).map_err(|e| fuse_core::VceError::InputSerialization(e.to_string()))?
+    let mut evidence_files = args.attachments
+        .iter()
+        .map(|arg| fuse_cli::evidence::attachment(arg))
+        .collect::<Result<Vec<_>>>()?;
+    let evidence_bundle = fuse_cli::evidence::bundle(args.bundle.as_deref(), &args.evidence)?;
+    let system_data_stream = args.stream.as_ref().map(std::fs::read).transpose()?;
+
+    // With a bundle or stream, host checkers see the document the guest's input stands for
+    let system_data = if let Some(records) = &system_data_stream {
+        let document = fuse_core::stream::document(&checked_specs[0], records)?;
+        println!("   Streaming {} bytes of records in {}-byte chunks", records.len(), fuse_core::stream::CHUNK_BYTES);
+        serde_json::to_string(&document)?
+    } else if let Some(system) = &args.system {
+        let extension = system.extension().and_then(|s| s.to_str()).unwrap_or_default().to_lowercase();
+        let is_media = ["jpg", "jpeg", "png", "c2pa"].contains(&extension.as_str());
+        if is_media && specs.iter().any(|spec| spec.claim.to_lowercase().contains("c2pa")) {
+            println!("   Detected media file with C2PA claim. Extracting manifest...");
+            let c2pa_data = fuse_cli::c2pa::parse_c2pa_manifest(system.to_str().unwrap_or_default())
+                .map_err(|e| fuse_core::VceError::InputSerialization(e.to_string()))?;
+            let json = fuse_cli::c2pa::c2pa_data_to_json(&c2pa_data);
+            // The proof is over the extracted manifest; commit the original file too
+            evidence_files.push(fuse_core::evidence::EvidenceFile {
+                name: system.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default(),
+                bytes: std::fs::read(system)?,
+            });
+            serde_json::to_string(&json).map_err(|e| fuse_core::VceError::InputSerialization(e.to_string()))?
+        } else {
+            std::fs::read_to_string(system)?
+        }
     } else {
-        std::fs::read_to_string(&args.system)?
+        for file in &evidence_bundle {
+            println!("   Bundle document: {} ({} bytes)", file.name, file.bytes.len());
+        }
+        serde_json::to_string(&fuse_core::evidence::bundle_document(&evidence_bundle)?)?
     };
     println!("   Loaded {} bytes of processed system data", system_data.len());
 
+    for file in &evidence_files {
+        println!("   Attached evidence: {} ({} bytes)", file.name, file.bytes.len());
+    }
+
     println!("\n⚙️  Running compliance checker...");
     let registry = CheckerRegistry::new();
-    let checker = registry.get_checker(&spec.claim)?;
-    
-    let result = checker.check(&spec, &system_data)?;
-    println!("   Result: {result}");
+    let mut host_results = Vec::with_capacity(checked_specs.len());
+    for checked_spec in &checked_specs {
+        let (result, score) = if let Some(composite) = &checked_spec.composite {
+            let nodes = registry.check_composite(checked_spec, composite, &system_data)?;
+            for node in &nodes {
+                println!("   {} [{}]: {}", node.path, node.label, node.result);
+            }
+            (nodes[0].result, None)
+        } else {
+            let checker = registry.checker_for(checked_spec)?;
+            (checker.check(checked_spec, &system_data)?, checker.score(checked_spec, &system_data)?)
+        };
+        if batch {
+            println!("   {}: {result}", checked_spec.claim);
+        } else if args.incremental {
+            // The guest continues from the previous period's counters
+            println!("   Result over this period's records: {result}");
+        } else {
+            println!("   Result: {result}");
+        }
+        if let Some(score) = &score {
+            println!("   Score: {score}");
+        }
+        host_results.push((result, score));
+    }
 
     println!("\n🔐 Generating zero-knowledge proof...");
-    let spec_hash = spec.hash();
     
     // Try to generate a real RISC Zero proof
     // If it fails (e.g., guest program not built), fall back to placeholder
-    let prover_type: ProverType = args.prover.into();
-    let proof = match fuse_core::zkvm::generate_proof(
-        &serde_json::to_string(&spec).unwrap_or_default(),
-        &system_data,
-        prover_type,
-    ) {
+    let spec_jsons = specs.iter().map(ComplianceSpec::to_proof_json).collect::<Result<Vec<_>>>()?;
+    let typed_system_data = if args.binary_input {
+        let data: serde_json::Value = serde_json::from_str(&system_data)?;
+        println!("   Passing typed system data in binary form");
+        Some(TypedSystemData::from_json(&data))
+    } else {
+        None
+    };
+    let spec_json = &spec_jsons[0];
+    let mode = if batch {
+        // With several specs the batch replaces the single spec
+        let system_data = if evidence_bundle.is_empty() { BatchData::Json(&system_data) } else { BatchData::Bundle(&evidence_bundle) };
+        ProofMode::Batch { spec_jsons: &spec_jsons, system_data }
+    } else if let Some(records) = system_data_stream.as_deref() {
+        if args.incremental {
+            ProofMode::Incremental { spec_json, records, previous_receipt: previous_receipt.as_deref() }
+        } else {
+            ProofMode::Stream { spec_json, records }
+        }
+    } else if let Some(system_data) = &typed_system_data {
+        ProofMode::Typed { spec_json, system_data }
+    } else if !evidence_bundle.is_empty() {
+        ProofMode::Bundle { spec_json, documents: &evidence_bundle }
+    } else {
+        ProofMode::Single { spec_json, system_data_json: &system_data, parse_full_json: false }
+    };
+    let mut inputs = fuse_core::zkvm::ProofInputs {
+        private_params_json: &private_params_json,
+        evidence_files: &evidence_files,
+        ..fuse_core::zkvm::ProofInputs::with_mode(mode)
+    };
+    let options = args.proving.options();
+    if let Some(cache) = &options.cache {
+        if cache.reuse_seed(&mut inputs, &options) {
+            println!("   Found these inputs in the proof cache at {}", cache.dir().display());
+        }
+    }
+    let proofs = match fuse_core::zkvm::generate_proof_with_inputs(&inputs, options) {
         Ok((receipt_bytes, zk_result, journal)) => {
             println!("   ✓ Real zkVM proof generated");
-            fuse_core::ComplianceProof::from_risc_zero_receipt(
-                spec_hash,
-                receipt_bytes,
-                zk_result,
-                journal,
-            )
+            if zk_result.log_state.is_incremental() {
+                let state = &zk_result.log_state;
+                println!("   Cumulative log: {} records over {} periods ({})", state.tally.records, state.periods, zk_result.result);
+            }
+            if specs.iter().any(|spec| spec.commit_fields) {
+                // The seed is needed to reveal committed fields later; keep it private
+                let seed_path = sibling_path(&args.output, ".seed");
+                std::fs::write(&seed_path, hex::encode(inputs.salt_seed))?;
+                println!("   ✓ Field commitment seed saved to {} (keep private)", seed_path.display());
+            }
+            if let Some(canonical_json) = typed_system_data.as_ref().map(TypedSystemData::canonical_json) {
+                // The committed system_data digest is over this form, not the original file
+                let canonical_path = sibling_path(&args.output, ".system.json");
+                std::fs::write(&canonical_path, canonical_json)?;
+                println!("   ✓ Canonical system data saved to {} (for fuse-audit-evidence)", canonical_path.display());
+            }
+            let mut proofs = Vec::with_capacity(specs.len());
+            for (index, spec) in specs.iter().enumerate() {
+                let proof = fuse_core::ComplianceProof::from_risc_zero_receipt(
+                    spec.hash(),
+                    receipt_bytes.clone(),
+                    zk_result.clone(),
+                    journal.clone(),
+                );
+                // Each envelope of a batch reads its own spec's entry
+                proofs.push(if batch { proof.with_batch_entry(index)? } else { proof });
+            }
+            proofs
         }
+        // A placeholder cannot carry the log state forward, and must not
+        // stand in for a proof that ran out of its limits
+        Err(e) if args.incremental || matches!(e, fuse_core::VceError::SessionLimitExceeded(_)) => return Err(e),
         Err(e) => {
             println!("   ⚠ Falling back to placeholder proof: {e}");
             println!("   (This is expected if guest program is not yet built)");
-            fuse_core::ComplianceProof::new(
-                spec_hash,
-                result,
-                system_data.as_bytes().to_vec(), // Journal contains public outputs
-            )
+            specs
+                .iter()
+                .zip(host_results)
+                .map(|(spec, (result, score))| {
+                    let mut proof = fuse_core::ComplianceProof::new(
+                        spec.hash(),
+                        result,
+                        system_data.as_bytes().to_vec(), // Journal contains public outputs
+                    );
+                    proof.score = score;
+                    proof
+                })
+                .collect()
         }
     };
 
     println!("\n📦 Creating Verifiable Compliance Envelope...");
-    let envelope = VerifiableComplianceEnvelope::new(spec, proof);
-    
-    println!("\n💾 Saving envelope to {}...", args.output.display());
-    envelope.to_file(&args.output)?;
+    for (index, (spec, proof)) in specs.into_iter().zip(proofs).enumerate() {
+        let mut envelope = VerifiableComplianceEnvelope::new(spec, proof);
+        if let Some(link) = &chain_link {
+            println!("   Chain: envelope {} covering {} to {}", link.sequence, link.period_start, link.period_end);
+            envelope = envelope.with_chain_link(link.clone());
+        }
+        let output = if batch { batch_output_path(&args.output, index) } else { args.output.clone() };
 
-    println!("\n✅ Success! Compliance envelope created at {}", args.output.display());
-    println!("   Verify with: fuse-verify {}", args.output.display());
+        println!("\n💾 Saving envelope to {}...", output.display());
+        envelope.to_file(&output)?;
+
+        println!("\n✅ Success! Compliance envelope created at {}", output.display());
+        println!("   Verify with: fuse-verify {}", output.display());
+    }
 
     Ok(())
 }
 
+fn load_spec(path: &std::path::Path) -> Result<ComplianceSpec> {
+    if path.extension().and_then(|s| s.to_str()) == Some("yaml")
+        || path.extension().and_then(|s| s.to_str()) == Some("yml")
+    {
+        ComplianceSpec::from_yaml_file(path)
+    } else {
+        ComplianceSpec::from_json_file(path)
+    }
+}
+
+/// Link to place the new envelope in its chain, if one was asked for
+fn chain_link(args: &Args, spec: &ComplianceSpec) -> Result<Option<ChainLink>> {
+    let period_end = args.period_end.unwrap_or_else(Utc::now);
+    let link = match (&args.chain, args.period_start) {
+        (Some(path), period_start) => {
+            let previous = VerifiableComplianceEnvelope::from_file(path)?;
+            if (&previous.spec.claim, &previous.spec.jurisdiction) != (&spec.claim, &spec.jurisdiction) {
+                return Err(fuse_core::VceError::ChainVerificationFailed(format!(
+                    "{} is for a different spec lineage",
+                    path.display()
+                )));
+            }
+            let link = ChainLink::next(&previous, period_end)?;
+            ChainLink { period_start: period_start.unwrap_or(link.period_start), ..link }
+        }
+        (None, Some(period_start)) => ChainLink::first(period_start, period_end),
+        (None, None) if args.period_end.is_some() => {
+            return Err(fuse_core::VceError::InvalidSpec(
+                "--period-end needs --period-start or --chain".to_string(),
+            ));
+        }
+        (None, None) => return Ok(None),
+    };
+    if link.period_end < link.period_start {
+        return Err(fuse_core::VceError::ChainVerificationFailed("The period ends before it starts".to_string()));
+    }
+    Ok(Some(link))
+}
+
+/// Receipt of the previous period's envelope, checked to continue `spec`
+fn load_previous(path: &std::path::Path, spec: &ComplianceSpec) -> Result<Vec<u8>> {
+    let mut previous = VerifiableComplianceEnvelope::from_file(path)?;
+    if previous.proof.is_placeholder() || previous.proof.batch_entry.is_some() {
+        return Err(fuse_core::VceError::ProofVerificationFailed(format!(
+            "{} is not an incremental proof that can be continued",
+            path.display()
+        )));
+    }
+    if previous.spec.hash() != spec.hash() {
+        return Err(fuse_core::VceError::InvalidSpec(format!(
+            "{} was proved for a different spec",
+            path.display()
+        )));
+    }
+    previous.verify()?;
+    println!("   Previous period: {} ({})", path.display(), previous.proof.result);
+    Ok(previous.proof.proof_data)
+}
+
+/// Envelope path for one spec of a batch: `compliance.vce` becomes
+/// `compliance-0.vce`, `compliance-1.vce`, ...
+fn batch_output_path(output: &std::path::Path, index: usize) -> PathBuf {
+    let stem = output.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_default();
+    let name = match output.extension() {
+        Some(extension) => format!("{stem}-{index}.{}", extension.to_string_lossy()),
+        None => format!("{stem}-{index}"),
+    };
+    output.with_file_name(name)
+}
+
+/// Path next to the envelope for a file that belongs with it, such as the
+/// salt seed for its field commitments
+fn sibling_path(output: &std::path::Path, suffix: &str) -> PathBuf {
+    let mut path = output.as_os_str().to_owned();
+    path.push(suffix);
+    PathBuf::from(path)
+}
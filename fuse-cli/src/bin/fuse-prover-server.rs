@@ -0,0 +1,279 @@
+//! HTTP proving service for `--prover remote`
+//!
+//! Proves what `fuse-prove --prover remote --remote-url ...` sends it with
+//! the local CPU or GPU prover, speaking the protocol described in
+//! `fuse_core::remote`. Clients verify every receipt against their own
+//! build of the checker's guest program, so the service needs no trust for
+//! integrity; it does see the system data and salt seeds, so run it where
+//! those may go.
+//!
+//! Each request is answered on its own thread, so a slow upload does not
+//! hold up other clients' status polls. A finished proof's receipt or
+//! error is kept until its client deletes the job or
+//! `--keep-finished-minutes` pass, so a client whose response was lost
+//! can fetch it again.
+
+use bincode::Options;
+use clap::Parser;
+use fuse_cli::prover::{ProverArgs, ProverTypeArg};
+use fuse_core::pool::{ProofHandle, ProofStatus, ProverPool};
+use fuse_core::progress::ProofPhase;
+use fuse_core::registry::{CheckerId, ImageRegistry};
+use fuse_core::remote::{JobState, JobStatus, ProveRequest, PROOFS_PATH};
+use fuse_core::{Result, VceError};
+use rand_core::{OsRng, RngCore};
+use std::collections::HashMap;
+use std::io::{Cursor, Read};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+#[derive(Parser)]
+#[command(name = "fuse-prover-server")]
+#[command(about = "Serve proofs to fuse-prove --prover remote")]
+struct Args {
+    /// Address to listen on
+    #[arg(long, default_value = "127.0.0.1:7878")]
+    listen: String,
+
+    /// Proofs to run at once; each uses every core and several GiB of memory
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    concurrent: u32,
+
+    /// Largest proof request to accept, in MiB
+    #[arg(long, default_value_t = 256)]
+    max_request_mb: u64,
+
+    /// Minutes to keep a finished proof's receipt or error after it
+    /// finished, unless its client deletes the job first
+    #[arg(long, default_value_t = 60, value_parser = clap::value_parser!(u64).range(1..))]
+    keep_finished_minutes: u64,
+
+    #[command(flatten)]
+    prover: ProverArgs,
+}
+
+/// What the guest execution reported so far
+#[derive(Debug, Default)]
+struct Seen {
+    segments: Option<usize>,
+    total_cycles: Option<u64>,
+    proved: Vec<usize>,
+}
+
+/// A proof submitted by a client
+struct Job {
+    handle: ProofHandle,
+    seen: Arc<Mutex<Seen>>,
+    /// Receipt, or why proving failed, once finished
+    result: Option<std::result::Result<Vec<u8>, String>>,
+    /// When the service first saw the proof finished
+    finished: Option<Instant>,
+}
+
+impl Job {
+    /// Collect the result if the proof has finished
+    fn poll(&mut self) {
+        if self.result.is_none() {
+            self.result = self.handle.try_result().map(|proof| proof.map(|(receipt, ..)| receipt).map_err(|e| e.to_string()));
+            if self.result.is_some() {
+                self.finished = Some(Instant::now());
+            }
+        }
+    }
+
+    fn status(&mut self, id: &str) -> JobStatus {
+        self.poll();
+        let (state, error) = match &self.result {
+            Some(Ok(_)) => (JobState::Done, None),
+            Some(Err(e)) => (JobState::Failed, Some(e.clone())),
+            None if self.handle.status() == ProofStatus::Queued => (JobState::Queued, None),
+            None => (JobState::Running, None),
+        };
+        let seen = self.seen.lock().unwrap_or_else(PoisonError::into_inner);
+        JobStatus { id: id.to_string(), state, segments: seen.segments, total_cycles: seen.total_cycles, proved: seen.proved.clone(), error }
+    }
+}
+
+struct Service {
+    args: Args,
+    registry: &'static ImageRegistry,
+    pool: ProverPool,
+    jobs: Mutex<HashMap<String, Job>>,
+}
+
+fn main() -> Result<()> {
+    fuse_cli::logging::init();
+    let args = Args::parse();
+    if matches!(args.prover.prover, ProverTypeArg::Remote) {
+        return Err(VceError::ProofGenerationFailed("fuse-prover-server proves locally; use --prover local or gpu".to_string()));
+    }
+
+    let registry = ImageRegistry::built();
+    if registry.iter().next().is_none() {
+        return Err(VceError::ProofGenerationFailed("No guest programs built. To build them, run: scripts/build-guests.sh".to_string()));
+    }
+    let pool = ProverPool::new(args.concurrent as usize)?;
+    let server = Server::http(&args.listen).map_err(|e| VceError::Io(std::io::Error::other(e)))?;
+    println!("🚀 Serving proofs on http://{}{PROOFS_PATH}", args.listen);
+    for (checker, image_id) in registry.iter() {
+        println!("   {} image ID: {}", checker.guest(), hex::encode(image_id.as_bytes()));
+    }
+    println!("   Prover: {:?}, {} at once", args.prover.prover, args.concurrent);
+
+    let service = Arc::new(Service { args, registry, pool, jobs: Mutex::new(HashMap::new()) });
+    for mut request in server.incoming_requests() {
+        let service = Arc::clone(&service);
+        std::thread::spawn(move || {
+            let response = service.handle(&mut request);
+            if let Err(e) = request.respond(response) {
+                tracing::warn!("Could not answer request: {e}");
+            }
+        });
+    }
+    Ok(())
+}
+
+impl Service {
+    /// The jobs, locked only while they are looked at, never during a
+    /// request body upload
+    fn jobs(&self) -> std::sync::MutexGuard<'_, HashMap<String, Job>> {
+        self.jobs.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn handle(&self, request: &mut Request) -> Response<Cursor<Vec<u8>>> {
+        self.drop_stale();
+        let path = request.url().split('?').next().unwrap_or_default().to_string();
+        let Some(rest) = path.strip_prefix(PROOFS_PATH) else {
+            return error(404, "Not found");
+        };
+        let parts: Vec<&str> = rest.split('/').filter(|part| !part.is_empty()).collect();
+        match (request.method(), parts.as_slice()) {
+            (Method::Post, []) => self.submit(request),
+            (Method::Get, [id]) => self.status(id),
+            (Method::Get, [id, "receipt"]) => self.receipt(id),
+            (Method::Delete, [id]) => match self.jobs().remove(*id) {
+                Some(job) => {
+                    job.handle.cancel();
+                    tracing::info!(id, "Deleted proof");
+                    Response::from_data(Vec::new()).with_status_code(204)
+                }
+                None => unknown(id),
+            },
+            _ => error(405, "Method not allowed"),
+        }
+    }
+
+    fn submit(&self, request: &mut Request) -> Response<Cursor<Vec<u8>>> {
+        let limit = self.args.max_request_mb.saturating_mul(1 << 20);
+        let mut body = Vec::new();
+        if let Err(e) = request.as_reader().take(limit + 1).read_to_end(&mut body) {
+            return error(400, &format!("Could not read request: {e}"));
+        }
+        if body.len() as u64 > limit {
+            return error(413, &format!("Request larger than {} MiB", self.args.max_request_mb));
+        }
+        // Same encoding as `bincode::serialize`, bounded so a forged length cannot exhaust memory
+        let decoded = bincode::DefaultOptions::new().with_fixint_encoding().allow_trailing_bytes().with_limit(limit).deserialize::<ProveRequest>(&body);
+        let proof = match decoded {
+            Ok(proof) => proof,
+            Err(e) => return error(400, &format!("Malformed proof request: {e}")),
+        };
+        let checker = CheckerId::for_inputs(&proof.inputs.as_inputs());
+        let Some(image_id) = self.registry.image_id(checker).map(|id| hex::encode(id.as_bytes())) else {
+            return error(409, &format!("This server has no {} guest program", checker.guest()));
+        };
+        if proof.image_id != image_id {
+            return error(409, &format!("This server proves {} image {image_id}, not {}", checker.guest(), proof.image_id));
+        }
+
+        let mut options = self.args.prover.options();
+        options.limits.segment_po2 = proof.segment_po2.or(options.limits.segment_po2);
+        options.limits.max_cycles = match (proof.max_cycles, options.limits.max_cycles) {
+            (Some(asked), Some(most)) => Some(asked.min(most)),
+            (asked, most) => asked.or(most),
+        };
+        let seen = Arc::new(Mutex::new(Seen::default()));
+        let observer = Arc::clone(&seen);
+        options.observer = Some(Arc::new(move |phase| {
+            let mut seen = observer.lock().unwrap_or_else(PoisonError::into_inner);
+            match phase {
+                ProofPhase::Executed { segments, total_cycles } => {
+                    seen.segments = Some(segments);
+                    seen.total_cycles = Some(total_cycles);
+                }
+                ProofPhase::SegmentProved { index, .. } => seen.proved.push(index),
+                _ => {}
+            }
+        }));
+
+        let mut id = [0u8; 16];
+        OsRng.fill_bytes(&mut id);
+        let id = hex::encode(id);
+        tracing::info!(id, "Queued proof");
+        let mut job = Job { handle: self.pool.submit(proof.inputs, options), seen, result: None, finished: None };
+        let status = job.status(&id);
+        self.jobs().insert(id, job);
+        json(202, &status)
+    }
+
+    /// Drop finished proofs whose clients have not fetched them within
+    /// `--keep-finished-minutes`, so abandoned jobs do not pile up
+    fn drop_stale(&self) {
+        let keep = Duration::from_secs(self.args.keep_finished_minutes.saturating_mul(60));
+        self.jobs().retain(|id, job| {
+            job.poll();
+            let stale = job.finished.is_some_and(|finished| finished.elapsed() > keep);
+            if stale {
+                tracing::info!(id, "Dropped proof its client did not fetch");
+            }
+            !stale
+        });
+    }
+
+    fn status(&self, id: &str) -> Response<Cursor<Vec<u8>>> {
+        match self.jobs().get_mut(id) {
+            Some(job) => json(200, &job.status(id)),
+            None => unknown(id),
+        }
+    }
+
+    /// The receipt stays until the client deletes the job, so a client
+    /// whose response was lost can ask again
+    fn receipt(&self, id: &str) -> Response<Cursor<Vec<u8>>> {
+        let receipt = {
+            let mut jobs = self.jobs();
+            let Some(job) = jobs.get_mut(id) else {
+                return unknown(id);
+            };
+            job.poll();
+            match &job.result {
+                Some(Ok(receipt)) => receipt.clone(),
+                _ => return error(409, "Proof is not ready"),
+            }
+        };
+        tracing::info!(id, "Sent receipt");
+        Response::from_data(receipt).with_header(header("application/octet-stream"))
+    }
+}
+
+fn json(status: u16, body: &impl serde::Serialize) -> Response<Cursor<Vec<u8>>> {
+    match serde_json::to_string(body) {
+        Ok(body) => Response::from_string(body).with_status_code(status).with_header(header("application/json")),
+        Err(e) => error(500, &e.to_string()),
+    }
+}
+
+fn error(status: u16, message: &str) -> Response<Cursor<Vec<u8>>> {
+    Response::from_string(serde_json::json!({ "error": message }).to_string())
+        .with_status_code(status)
+        .with_header(header("application/json"))
+}
+
+fn unknown(id: &str) -> Response<Cursor<Vec<u8>>> {
+    error(404, &format!("No proof {id}"))
+}
+
+fn header(content_type: &str) -> Header {
+    Header::from_bytes("Content-Type", content_type).expect("static header is valid")
+}
@@ -0,0 +1,140 @@
+//! CLI tool for revealing committed fields of a Verifiable Compliance Envelope
+//!
+//! When a spec sets `commit_fields`, the proof commits a salted Merkle root
+//! over every field of the system data. `fuse-reveal open` produces
+//! inclusion proofs that open chosen fields to an auditor; `fuse-reveal
+//! check` verifies them against the envelope without re-proving.
+
+use clap::{Parser, Subcommand};
+use fuse_core::merkle::{FieldReveal, FieldTree};
+use fuse_core::{Result, VceError, VerifiableComplianceEnvelope};
+use std::path::{Path, PathBuf};
+
+#[derive(Parser)]
+#[command(name = "fuse-reveal")]
+#[command(about = "Reveal committed fields of a Verifiable Compliance Envelope")]
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Open fields against the envelope's committed root
+    Open {
+        /// Path to the .vce file
+        #[arg(short, long)]
+        envelope: PathBuf,
+
+        /// Path to the system data the envelope was proved over
+        #[arg(short, long)]
+        system: PathBuf,
+
+        /// Path to the salt seed written by fuse-prove
+        #[arg(long)]
+        seed: PathBuf,
+
+        /// JSON Pointer of a field to reveal; objects and arrays reveal every field below them
+        #[arg(short, long = "path", required = true)]
+        paths: Vec<String>,
+
+        /// Output path for the reveal file
+        #[arg(short, long, default_value = "reveal.json")]
+        output: PathBuf,
+    },
+    /// Check a reveal file against an envelope
+    Check {
+        /// Path to the .vce file
+        #[arg(short, long)]
+        envelope: PathBuf,
+
+        /// Path to the reveal file
+        reveal: PathBuf,
+    },
+}
+
+fn main() -> Result<()> {
+    fuse_cli::logging::init();
+    match Args::parse().command {
+        Command::Open { envelope, system, seed, paths, output } => open(&envelope, &system, &seed, &paths, &output),
+        Command::Check { envelope, reveal } => {
+            if let Err(e) = check(&envelope, &reveal) {
+                eprintln!("❌ Reveal check failed: {e}");
+                std::process::exit(1);
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Load and verify an envelope, returning it with its committed root
+fn load_envelope(path: &Path) -> Result<(VerifiableComplianceEnvelope, Vec<u8>)> {
+    println!("📦 Loading Verifiable Compliance Envelope...");
+    let mut envelope = VerifiableComplianceEnvelope::from_file(path)?;
+    if envelope.proof.is_placeholder() {
+        return Err(VceError::ProofVerificationFailed(
+            "Placeholder proofs carry no field commitment".to_string(),
+        ));
+    }
+    envelope.verify()?;
+    println!("   ✓ Envelope verified");
+
+    let root = envelope.proof.output()
+        .map(|output| output.fields_root.clone())
+        .unwrap_or_default();
+    if root.is_empty() {
+        return Err(VceError::ProofVerificationFailed(
+            "Envelope does not commit to its fields (spec did not set commit_fields)".to_string(),
+        ));
+    }
+    Ok((envelope, root))
+}
+
+fn open(envelope: &Path, system: &Path, seed: &Path, paths: &[String], output: &Path) -> Result<()> {
+    let (envelope, root) = load_envelope(envelope)?;
+
+    let seed: [u8; 32] = hex::decode(std::fs::read_to_string(seed)?.trim())
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| VceError::InputSerialization("Salt seed must be 32 hex-encoded bytes".to_string()))?;
+    let system_data: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(system)?)?;
+
+    println!("\n🌳 Rebuilding field commitments...");
+    let tree = FieldTree::new(&system_data, &seed);
+    if tree.root().as_slice() != root.as_slice() {
+        return Err(VceError::ProofVerificationFailed(
+            "System data and seed do not reproduce the envelope's committed root".to_string(),
+        ));
+    }
+
+    let mut openings = Vec::new();
+    for path in paths {
+        openings.extend(tree.open(path)?);
+    }
+    for opening in &openings {
+        println!("   {} = {}", opening.path, opening.value);
+    }
+
+    let reveal = FieldReveal {
+        spec_hash: envelope.proof.spec_hash.clone(),
+        fields_root: hex::encode(&root),
+        openings,
+    };
+    std::fs::write(output, serde_json::to_string_pretty(&reveal)?)?;
+    println!("\n✅ Revealed {} field(s) to {}", reveal.openings.len(), output.display());
+    Ok(())
+}
+
+fn check(envelope: &Path, reveal: &Path) -> Result<()> {
+    let (envelope, root) = load_envelope(envelope)?;
+    let reveal: FieldReveal = serde_json::from_str(&std::fs::read_to_string(reveal)?)?;
+
+    reveal.verify(&envelope.proof.spec_hash, &root)?;
+
+    println!("\n🔓 Revealed fields:");
+    for opening in &reveal.openings {
+        println!("   {} = {}", opening.path, opening.value);
+    }
+    println!("✅ All {} field(s) open against the committed root", reveal.openings.len());
+    Ok(())
+}
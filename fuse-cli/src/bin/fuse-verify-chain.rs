@@ -0,0 +1,92 @@
+//! CLI tool for verifying a chain of Verifiable Compliance Envelopes
+//!
+//! For continuous monitoring each periodic envelope links to the digest of
+//! its predecessor in the same spec lineage. This verifies every envelope
+//! and the linkage between them, then reports gaps and overlaps in time
+//! coverage, envelopes without a zkVM proof, and when compliance broke.
+
+use clap::Parser;
+use fuse_core::{Result, VerifiableComplianceEnvelope};
+use std::path::PathBuf;
+
+#[derive(Parser)]
+#[command(name = "fuse-verify-chain")]
+#[command(about = "Verify a hash-chained history of Verifiable Compliance Envelopes")]
+struct Args {
+    /// The chain's .vce files, in any order
+    #[arg(required = true)]
+    envelopes: Vec<PathBuf>,
+}
+
+fn main() -> Result<()> {
+    fuse_cli::logging::init();
+    let args = Args::parse();
+
+    println!("📦 Loading {} envelopes...", args.envelopes.len());
+    let envelopes = args
+        .envelopes
+        .iter()
+        .map(|path| VerifiableComplianceEnvelope::from_file(path))
+        .collect::<Result<Vec<_>>>()?;
+
+    println!("\n🔗 Verifying envelopes and linkage...");
+    let report = match fuse_core::chain::verify_chain(&envelopes) {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("❌ {e}");
+            std::process::exit(1);
+        }
+    };
+    println!("   ✓ Envelopes {} verified and linked by digest", sequence_range(&report.sequences));
+
+    // Results as committed to the verified journals
+    let mut links: Vec<_> = envelopes.iter().filter_map(|envelope| envelope.chain.as_ref()).collect();
+    links.sort_by_key(|link| link.sequence);
+    for (link, result) in links.iter().zip(&report.results) {
+        if report.unproven.contains(&link.sequence) {
+            println!("   {}: {} to {}: unproven (placeholder proof)", link.sequence, link.period_start, link.period_end);
+        } else {
+            println!("   {}: {} to {}: {}", link.sequence, link.period_start, link.period_end, result);
+        }
+    }
+
+    if !report.gaps.is_empty() {
+        println!("\n⏳ Coverage Gaps:");
+        for gap in &report.gaps {
+            println!("   After envelope {}: {} to {}", gap.after, gap.from, gap.until);
+        }
+    }
+    if !report.overlaps.is_empty() {
+        println!("\n🔁 Overlapping Periods:");
+        for overlap in &report.overlaps {
+            println!("   Envelopes {} and {}: {} to {}", overlap.after, overlap.after + 1, overlap.from, overlap.until);
+        }
+    }
+    if !report.unproven.is_empty() {
+        println!("\n⚠️  Unproven Envelopes (placeholder proofs count as inconclusive):");
+        for sequence in &report.unproven {
+            println!("   Envelope {sequence}");
+        }
+    }
+    if !report.breaks.is_empty() {
+        println!("\n🚨 Compliance Breaks:");
+        for broken in &report.breaks {
+            println!("   Envelope {}: {} since {}", broken.sequence, broken.result, broken.since);
+        }
+    }
+
+    if report.is_continuous_compliance() {
+        println!("\n✅ Continuous compliance over the whole chain");
+        std::process::exit(0);
+    }
+    println!("\n❌ Compliance was not continuous");
+    std::process::exit(1);
+}
+
+fn sequence_range(sequences: &[u64]) -> String {
+    match (sequences.first(), sequences.last()) {
+        (Some(first), Some(last)) if first != last => format!("{first}-{last}"),
+        (Some(first), _) => first.to_string(),
+        _ => String::new(),
+    }
+}
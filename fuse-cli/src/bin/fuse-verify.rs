@@ -1,7 +1,7 @@
 //! CLI tool for verifying Verifiable Compliance Envelopes
 
 use clap::Parser;
-use fuse_core::{VerifiableComplianceEnvelope, Result};
+use fuse_core::{ComplianceResult, VerifiableComplianceEnvelope, Result};
 use std::path::PathBuf;
 
 #[derive(Parser)]
@@ -17,6 +17,7 @@ struct Args {
 }
 
 fn main() -> Result<()> {
+    fuse_cli::logging::init();
     let args = Args::parse();
 
     println!("📦 Loading Verifiable Compliance Envelope...");
@@ -31,8 +32,20 @@ fn main() -> Result<()> {
         println!("   Expiry: {}", envelope.spec.expiry);
         println!("\n🔐 Proof:");
         println!("   Spec Hash: {}", envelope.proof.spec_hash);
+        if let Some(index) = envelope.proof.batch_entry {
+            println!("   Batch Entry: {index}");
+        }
         println!("   Result: {}", envelope.proof.result);
         println!("   Timestamp: {}", envelope.proof.timestamp);
+        if let Some(link) = &envelope.chain {
+            println!("   Chain Sequence: {} ({} to {})", link.sequence, link.period_start, link.period_end);
+            if let Some(previous) = &link.previous_digest {
+                println!("   Previous Envelope: {previous}");
+            }
+        }
+        if let Some(threshold) = &envelope.spec.pass_threshold {
+            println!("   Pass Threshold: {threshold}");
+        }
     }
 
     println!("\n🔍 Verifying envelope...");
@@ -47,12 +60,61 @@ fn main() -> Result<()> {
     match envelope.verify() {
         Ok(()) => {
             println!("✅ Envelope is valid!");
+            if let Some(checker) = envelope.proof.checker {
+                println!("   Guest Program: {}", checker.guest());
+            }
+
+            if let Some(score) = &envelope.proof.score {
+                println!("   Score: {score}");
+            }
+
+            // Composite specs commit the result of every sub-claim
+            if let Some(output) = envelope.proof.output() {
+                if !output.node_results.is_empty() {
+                    println!("\n🧩 Composite Results (from Proof Journal):");
+                    for node in &output.node_results {
+                        let depth = node.path.matches('/').count().saturating_sub(1);
+                        println!("   {}{} [{}]: {}", "  ".repeat(depth), node.path, node.label, node.result);
+                    }
+                }
+
+                // Incremental proofs commit the cumulative log and the period they continue
+                if output.log_state.is_incremental() {
+                    let state = &output.log_state;
+                    println!("\n📈 Cumulative Log (from Proof Journal):");
+                    println!("   Periods: {}", state.periods);
+                    println!("   Records: {} ({} conforming)", state.tally.records, state.tally.conforming);
+                    println!("   Log root: {}", hex::encode(&state.root));
+                    if let Some(previous) = output.children.first() {
+                        println!("   Previous period: {} (journal {})", previous.result, hex::encode(&previous.journal_digest));
+                    }
+                } else if !output.children.is_empty() {
+                    // Aggregates commit the journal digest and result of every child
+                    println!("\n🗂  Aggregated Children (from Proof Journal):");
+                    for (index, child) in output.children.iter().enumerate() {
+                        println!("   {index}: {} (journal {})", child.result, hex::encode(&child.journal_digest));
+                    }
+                }
 
-            // If it's a C2PA claim, let's look at the journal for selective disclosure
-            if envelope.spec.claim.to_lowercase().contains("c2pa") {
+                // Digests of the exact evidence proven over, for fuse-audit-evidence
+                let evidence = envelope.proof.evidence();
+                if !evidence.is_empty() {
+                    println!("\n🧾 Evidence Commitments (from Proof Journal):");
+                    for commitment in &evidence {
+                        println!("   {}: {}", commitment.name, hex::encode(&commitment.digest));
+                    }
+                }
+            }
+
+            // Show the journal's selective disclosure for C2PA claims and any
+            // spec that requests disclosed fields
+            if envelope.spec.claim.to_lowercase().contains("c2pa")
+                || envelope.spec.disclosed_fields.is_some()
+                || envelope.spec.disclosure_predicates.is_some()
+            {
                 println!("\n📂 Selective Disclosure (from Proof Journal):");
                 
-                if let Some(output) = &envelope.proof.journal_output {
+                if let Some(output) = envelope.proof.output() {
                     println!("   Compliance Status: {:?}", output.result);
                     
                     if !output.claim_hash.is_empty() {
@@ -60,13 +122,25 @@ fn main() -> Result<()> {
                     }
                     
                     if !output.redacted_json.is_empty() {
-                        println!("   Redacted Manifest Data:");
+                        println!("   Disclosed Data:");
                         // Parse the JSON string back to Value for pretty printing
                         match serde_json::from_str::<serde_json::Value>(&output.redacted_json) {
                             Ok(json) => println!("{}", serde_json::to_string_pretty(&json).unwrap_or_default()),
                             Err(_) => println!("   {}", output.redacted_json),
                         }
                     }
+
+                    for path in &output.missing_fields {
+                        println!("   ⚠️  Disclosed field not found: {path}");
+                    }
+
+                    for predicate in &output.predicates {
+                        if predicate.error_code == fuse_core::ErrorCode::None {
+                            println!("   Predicate {}: {}", predicate.name, predicate.holds);
+                        } else {
+                            println!("   Predicate {}: inconclusive ({})", predicate.name, predicate.error_code);
+                        }
+                    }
                 } else {
                     println!("   (No detailed journal output found)");
                 }
@@ -77,10 +151,17 @@ fn main() -> Result<()> {
                     println!("✅ Compliance check: PASS");
                     std::process::exit(0);
                 }
-                Ok(false) => {
-                    println!("❌ Compliance check: FAIL");
-                    std::process::exit(1);
-                }
+                // Inconclusive only on the verified journal's word, not the file's
+                Ok(false) => match envelope.proof.output().filter(|output| output.result == ComplianceResult::Error) {
+                    Some(output) => {
+                        println!("⚠️  Compliance check: ERROR (inconclusive: {})", output.error_code);
+                        std::process::exit(2);
+                    }
+                    None => {
+                        println!("❌ Compliance check: FAIL");
+                        std::process::exit(1);
+                    }
+                },
                 Err(e) => {
                     eprintln!("❌ Error checking compliance: {e}");
                     std::process::exit(1);
@@ -0,0 +1,37 @@
+//! Evidence file arguments shared by fuse-prove and fuse-audit-evidence
+
+use fuse_core::evidence::{self, EvidenceFile};
+use fuse_core::Result;
+use std::path::{Path, PathBuf};
+
+/// Read a `[NAME=]PATH` attachment; the name defaults to the file name
+pub fn attachment(arg: &str) -> Result<EvidenceFile> {
+    let (name, path) = split_named(arg, |path| path.file_name());
+    Ok(EvidenceFile { name, bytes: std::fs::read(path)? })
+}
+
+/// Load an evidence bundle from `--bundle` and repeated `--evidence
+/// [NAME=]PATH` arguments; the name defaults to the file stem
+pub fn bundle(bundle_path: Option<&Path>, evidence_args: &[String]) -> Result<Vec<EvidenceFile>> {
+    let mut files = match bundle_path {
+        Some(path) => evidence::load_bundle(path)?,
+        None => Vec::new(),
+    };
+    for arg in evidence_args {
+        let (name, path) = split_named(arg, |path| path.file_stem());
+        files.push(EvidenceFile { name, bytes: std::fs::read(path)? });
+    }
+    evidence::validate_files(&files)?;
+    Ok(files)
+}
+
+fn split_named(arg: &str, default_name: impl Fn(&Path) -> Option<&std::ffi::OsStr>) -> (String, PathBuf) {
+    match arg.split_once('=') {
+        Some((name, path)) => (name.to_string(), PathBuf::from(path)),
+        None => {
+            let path = PathBuf::from(arg);
+            let name = default_name(&path).map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+            (name, path)
+        }
+    }
+}
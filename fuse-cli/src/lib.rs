@@ -1,4 +1,7 @@
 //! FUSE CLI library - shared functionality for CLI tools
 
 pub mod c2pa;
+pub mod evidence;
+pub mod logging;
+pub mod prover;
 
@@ -0,0 +1,21 @@
+//! Console output for the library's `tracing` events
+//!
+//! fuse-core reports proving phases, cache use and warnings as `tracing`
+//! events rather than printing them. The tools show them on stdout next to
+//! their own output; `RUST_LOG` overrides what is shown (e.g.
+//! `RUST_LOG=fuse_core=debug,risc0_zkvm=info`).
+
+use tracing_subscriber::EnvFilter;
+
+/// Show `fuse_core` events at info level and above, unless `RUST_LOG` says
+/// otherwise
+pub fn init() {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("fuse_core=info"));
+    // Fails only when a subscriber is already set, which then keeps working
+    let _ = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stdout)
+        .with_target(false)
+        .without_time()
+        .try_init();
+}
@@ -0,0 +1,101 @@
+//! Prover arguments shared by the proving tools
+
+use clap::{Args, ValueEnum};
+use fuse_core::ProverType;
+use fuse_core::cache::ProofCache;
+use fuse_core::zkvm::{ProofOptions, SessionLimits};
+use std::path::PathBuf;
+
+/// How to prove, as given on the command line
+#[derive(Debug, Clone, Args)]
+pub struct ProverArgs {
+    /// Prover type to use for proof generation
+    ///
+    /// - local: CPU-based proving (default, always available)
+    /// - gpu: GPU-accelerated proving (requires GPU hardware and --features gpu)
+    /// - remote: a proving service such as fuse-prover-server at --remote-url
+    #[arg(long, default_value = "local", value_enum)]
+    pub prover: ProverTypeArg,
+
+    /// Base URL of the proving service for --prover remote; its receipts
+    /// are verified locally before they are accepted
+    #[arg(long, required_if_eq("prover", "remote"))]
+    pub remote_url: Option<String>,
+
+    /// Largest receipt to accept from the proving service, in MiB
+    #[arg(long, default_value_t = fuse_core::zkvm::DEFAULT_MAX_RECEIPT_BYTES >> 20, value_parser = clap::value_parser!(u64).range(1..))]
+    pub remote_max_receipt_mb: u64,
+
+    /// Worker threads proving segments in parallel, each needing several GiB
+    /// of memory; the segment receipts are joined into one receipt
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    pub workers: u32,
+
+    /// Segment size as a power of two of cycles; smaller segments spread a
+    /// proof over more workers
+    #[arg(long, value_parser = clap::value_parser!(u32).range(13..=21))]
+    pub segment_po2: Option<u32>,
+
+    /// Stop proving once the guest has run this many total cycles, so a
+    /// malformed or huge input cannot run indefinitely
+    #[arg(long)]
+    pub max_cycles: Option<u64>,
+
+    /// Directory to write execution segments under while proving (a system
+    /// temporary directory by default)
+    #[arg(long)]
+    pub segment_dir: Option<PathBuf>,
+
+    /// Reuse the proof of identical inputs from the proof cache, and cache
+    /// new proofs (see fuse-cache)
+    #[arg(long)]
+    pub cache: bool,
+
+    /// Proof cache directory; implies --cache ($FUSE_CACHE_DIR or
+    /// ~/.cache/fuse/proofs by default)
+    #[arg(long)]
+    pub cache_dir: Option<PathBuf>,
+}
+
+impl ProverArgs {
+    #[must_use]
+    pub fn prover_type(&self) -> ProverType {
+        match self.prover {
+            ProverTypeArg::Local => ProverType::Local,
+            ProverTypeArg::Gpu => ProverType::Gpu,
+            ProverTypeArg::Remote => ProverType::Remote {
+                url: self.remote_url.clone().unwrap_or_default(),
+                max_receipt_bytes: self.remote_max_receipt_mb.saturating_mul(1 << 20),
+            },
+        }
+    }
+
+    #[must_use]
+    pub fn options(&self) -> ProofOptions {
+        ProofOptions {
+            workers: self.workers as usize,
+            limits: SessionLimits {
+                max_cycles: self.max_cycles,
+                segment_po2: self.segment_po2,
+                segment_dir: self.segment_dir.clone(),
+            },
+            cache: match &self.cache_dir {
+                Some(dir) => Some(ProofCache::new(dir)),
+                None if self.cache => Some(ProofCache::new(ProofCache::default_dir())),
+                None => None,
+            },
+            ..ProofOptions::new(self.prover_type())
+        }
+    }
+}
+
+/// CLI argument representation of `ProverType`
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ProverTypeArg {
+    /// Local CPU prover (default)
+    Local,
+    /// GPU-accelerated prover
+    Gpu,
+    /// Remote proving service
+    Remote,
+}
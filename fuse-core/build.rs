@@ -1,34 +1,40 @@
 //! Build script for fuse-core
 //!
-//! This script checks if the guest program ELF binary exists and
-//! sets up the environment for including it in the host program.
+//! This script checks which guest program ELF binaries exist (the general
+//! guest and one per checker family) and sets up the environment for
+//! including them in the host program.
+
+/// Checker name (as in `registry::CheckerId::name`) and guest binary
+const GUESTS: [(&str, &str); 6] = [
+    ("general", "fuse-guest"),
+    ("soc2", "fuse-guest-soc2"),
+    ("gdpr", "fuse-guest-gdpr"),
+    ("supply-chain", "fuse-guest-supply-chain"),
+    ("ml-model", "fuse-guest-ml-model"),
+    ("c2pa", "fuse-guest-c2pa"),
+];
 
 fn main() {
     // Tell cargo to accept this cfg
-    println!("cargo:rustc-check-cfg=cfg(guest_program_built)");
-    // Check if guest program ELF exists
-    // Try workspace target directory first (default for cargo workspaces)
-    let guest_elf_paths = [
-        "../target/riscv32im-risc0-zkvm-elf/release/fuse-guest",  // Workspace target (primary)
-        "../fuse-guest/target/riscv32im-risc0-zkvm-elf/release/fuse-guest",  // Package target (fallback)
-    ];
-    
-    let mut found = false;
-    for guest_elf_path in &guest_elf_paths {
-        let path = std::path::Path::new(guest_elf_path);
-        if path.exists() {
-            println!("cargo:rustc-cfg=guest_program_built");
+    let names: Vec<String> = GUESTS.iter().map(|(name, _)| format!("\"{name}\"")).collect();
+    println!("cargo:rustc-check-cfg=cfg(guest_built, values({}))", names.join(", "));
+
+    let mut missing = Vec::new();
+    for (name, guest) in GUESTS {
+        // The ELF is included from the workspace target directory
+        let guest_elf_path = format!("../target/riscv32im-risc0-zkvm-elf/release/{guest}");
+        if std::path::Path::new(&guest_elf_path).exists() {
+            println!("cargo:rustc-cfg=guest_built=\"{name}\"");
             println!("cargo:rerun-if-changed={guest_elf_path}");
             eprintln!("[build.rs] ✅ Found guest ELF at: {guest_elf_path}");
-            found = true;
-            break;
+        } else {
+            eprintln!("[build.rs] ❌ Not found: {guest_elf_path}");
+            missing.push(guest);
         }
-        eprintln!("[build.rs] ❌ Not found: {guest_elf_path}");
     }
-    
-    if !found {
-        eprintln!("[build.rs] ⚠️  Guest program not built. Run: cargo build -p fuse-guest --release --target riscv32im-risc0-zkvm-elf");
-        println!("cargo:warning=Guest program not built. Run: cargo build -p fuse-guest --release --target riscv32im-risc0-zkvm-elf");
+
+    if !missing.is_empty() {
+        eprintln!("[build.rs] ⚠️  Guest program not built: {}. Run: scripts/build-guests.sh", missing.join(", "));
+        println!("cargo:warning=Guest program not built: {}. Run: scripts/build-guests.sh", missing.join(", "));
     }
 }
-
@@ -0,0 +1,64 @@
+//! Proof aggregation
+//!
+//! One envelope can cover many child envelopes, e.g. one per subsidiary
+//! rolled up into a group-level attestation. The guest verifies each
+//! child receipt through RISC Zero composition and commits each child's
+//! image ID, journal digest and result. The aggregate is scored as the
+//! fraction of children that pass, against the aggregate spec's
+//! `pass_threshold` (all of them by default).
+//!
+//! Children may be proved by different checkers' guest programs (see
+//! [`crate::registry`]); verifiers check every committed image ID is a
+//! registered one. Children that are aggregates themselves work the same
+//! way.
+//!
+//! Mirrors the guest program's `aggregate` module; types and field order must match.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use crate::proof::ComplianceResult;
+
+/// Child proofs to aggregate, passed to the guest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Aggregation {
+    pub children: Vec<AggregatedChild>,
+}
+
+/// A child proof's journal and the image ID of the guest that proved it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AggregatedChild {
+    pub image_id: [u32; 8],
+    pub journal: Vec<u8>,
+}
+
+/// A child proof, committed to the journal
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChildCommitment {
+    pub image_id: Vec<u32>,
+    pub journal_digest: Vec<u8>,
+    pub result: ComplianceResult,
+}
+
+impl ChildCommitment {
+    /// Whether this commits the child proof with the given journal bytes
+    #[must_use]
+    pub fn matches(&self, journal: &[u8]) -> bool {
+        self.journal_digest == Sha256::digest(journal).as_slice()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_child_matches_its_journal_only() {
+        let child = ChildCommitment {
+            image_id: vec![0; 8],
+            journal_digest: Sha256::digest(b"child journal").to_vec(),
+            result: ComplianceResult::Pass,
+        };
+        assert!(child.matches(b"child journal"));
+        assert!(!child.matches(b"other journal"));
+    }
+}
@@ -0,0 +1,338 @@
+//! Content-addressed proof cache
+//!
+//! Benchmark runs, CI and repeated `fuse-prove` invocations re-prove the
+//! same inputs, at minutes per proof. A [`ProofCache`] keeps receipts on
+//! disk keyed by everything that determines them: the guest image ID, the
+//! guest inputs byte for byte (the raw spec JSON is committed to the
+//! journal, so an equivalent but differently formatted spec is a different
+//! proof), the salt seed and the segment size the receipt was joined from.
+//!
+//! A fresh proof has a random salt seed, so identical inputs would never
+//! hit. The cache therefore remembers the seed each set of inputs was
+//! proved with, and [`ProofCache::reuse_seed`] adopts it before proving.
+//! Seeds are private; keep the cache directory as private as the `.seed`
+//! files `fuse-prove` writes.
+//!
+//! Every cached receipt is verified again before it is used, and its
+//! journal must commit to the very inputs asked for, so a corrupted,
+//! tampered or swapped cache entry is dropped and re-proved rather than
+//! turned into an envelope. Dev-mode receipts are never cached.
+
+use chrono::{DateTime, Utc};
+use risc0_zkvm::sha::Digest as ImageId;
+use risc0_zkvm::Receipt;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use crate::error::{Result, VceError};
+use crate::evidence::{self, EvidenceFile};
+use crate::proof::{ComplianceResult, JournalOutput};
+use crate::registry::CheckerId;
+use crate::zkvm::{self, ProofInputs, ProofMode, ProofOptions};
+
+const CACHE_DOMAIN: &[u8] = b"fuse.cache.v1";
+
+/// On-disk store of receipts keyed by their inputs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofCache {
+    dir: PathBuf,
+}
+
+/// Where a set of inputs is cached
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheKey {
+    /// Hex digest of the inputs and options, without the salt seed
+    pub inputs: String,
+    /// Hex digest identifying the entry: the inputs and the salt seed
+    pub entry: String,
+    image_id: ImageId,
+}
+
+/// A cached proof's metadata, stored next to its receipt
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheEntry {
+    pub key: String,
+    pub inputs: String,
+    /// Hex image ID of the guest program that proved it
+    pub image_id: String,
+    pub result: ComplianceResult,
+    pub created_at: DateTime<Utc>,
+    pub receipt_size: u64,
+}
+
+/// Everything the receipt depends on besides the salt seed
+#[derive(Serialize)]
+struct KeyMaterial<'a> {
+    image_id: &'a [u8],
+    mode: ProofMode<'a>,
+    private_params_json: &'a str,
+    evidence_files: &'a [EvidenceFile],
+    // The segment size changes how the receipt was joined; worker count
+    // and CPU or GPU do not
+    segment_po2: Option<u32>,
+}
+
+impl CacheKey {
+    /// Key for proving `inputs` with `options` on the guest program with
+    /// the given image ID
+    pub fn new(inputs: &ProofInputs<'_>, options: &ProofOptions, image_id: ImageId) -> Result<Self> {
+        let material = KeyMaterial {
+            image_id: image_id.as_bytes(),
+            mode: inputs.mode,
+            private_params_json: inputs.private_params_json,
+            evidence_files: inputs.evidence_files,
+            segment_po2: options.limits.segment_po2,
+        };
+        let mut hasher = Sha256::new();
+        hasher.update(CACHE_DOMAIN);
+        bincode::serialize_into(&mut hasher, &material)
+            .map_err(|e| VceError::InputSerialization(format!("Failed to hash proof inputs for the cache: {e}")))?;
+        let inputs_digest = hasher.finalize();
+
+        let mut hasher = Sha256::new();
+        hasher.update(CACHE_DOMAIN);
+        hasher.update(inputs_digest);
+        hasher.update(inputs.salt_seed);
+        Ok(Self { inputs: hex::encode(inputs_digest), entry: hex::encode(hasher.finalize()), image_id })
+    }
+}
+
+impl ProofCache {
+    /// Cache stored under `dir`, created on first use
+    #[must_use]
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// `$FUSE_CACHE_DIR`, else `fuse/proofs` under the user's cache
+    /// directory (`$XDG_CACHE_HOME` or `~/.cache`)
+    #[must_use]
+    pub fn default_dir() -> PathBuf {
+        if let Some(dir) = std::env::var_os("FUSE_CACHE_DIR") {
+            return PathBuf::from(dir);
+        }
+        let cache_home = std::env::var_os("XDG_CACHE_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+            .unwrap_or_else(std::env::temp_dir);
+        cache_home.join("fuse").join("proofs")
+    }
+
+    #[must_use]
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Adopt the salt seed identical inputs were proved with before, so
+    /// proving them hits the cache; returns whether there was one
+    ///
+    /// Any problem reading the seed counts as a miss.
+    pub fn reuse_seed(&self, inputs: &mut ProofInputs<'_>, options: &ProofOptions) -> bool {
+        let Ok(image_id) = zkvm::image_id(CheckerId::for_inputs(inputs)) else { return false };
+        let Ok(key) = CacheKey::new(inputs, options, image_id) else { return false };
+        let seed = std::fs::read_to_string(self.seed_path(&key.inputs))
+            .ok()
+            .and_then(|seed| hex::decode(seed.trim()).ok())
+            .and_then(|seed| <[u8; 32]>::try_from(seed).ok());
+        match seed {
+            Some(seed) => {
+                inputs.salt_seed = seed;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// The cached proof of `inputs` under `key`, verified again, as returned
+    /// by [`zkvm::generate_proof_with_inputs`]
+    ///
+    /// An entry that fails verification, or whose journal does not commit
+    /// to `inputs`, is removed and reported as a miss.
+    pub(crate) fn get(&self, key: &CacheKey, inputs: &ProofInputs<'_>) -> Option<(Vec<u8>, JournalOutput, Vec<u8>)> {
+        let entry = self.read_entry(&self.entry_path(&key.entry)).ok()?;
+        let proof = self.verify(&entry, key.image_id)
+            .and_then(|proof| evidence::check_journal(&proof.1, inputs).map(|()| proof));
+        match proof {
+            Ok(proof) => Some(proof),
+            Err(e) => {
+                tracing::warn!(key = short(&entry.key), "Discarding cached proof: {e}");
+                let _ = self.remove(&entry);
+                None
+            }
+        }
+    }
+
+    /// Store a proof and the salt seed its inputs were proved with
+    pub(crate) fn put(&self, key: &CacheKey, salt_seed: &[u8; 32], receipt_bytes: &[u8], result: ComplianceResult) -> Result<()> {
+        std::fs::create_dir_all(self.dir.join("seeds"))?;
+        let entry = CacheEntry {
+            key: key.entry.clone(),
+            inputs: key.inputs.clone(),
+            image_id: hex::encode(key.image_id.as_bytes()),
+            result,
+            created_at: Utc::now(),
+            receipt_size: receipt_bytes.len() as u64,
+        };
+        write_atomically(&self.seed_path(&key.inputs), hex::encode(salt_seed).as_bytes())?;
+        write_atomically(&self.receipt_path(&key.entry), receipt_bytes)?;
+        // The metadata marks the entry complete, so it goes last
+        write_atomically(&self.entry_path(&key.entry), &serde_json::to_vec_pretty(&entry)?)
+    }
+
+    /// Every complete entry, oldest first
+    ///
+    /// Entries whose metadata cannot be read are skipped with a warning.
+    pub fn entries(&self) -> Result<Vec<CacheEntry>> {
+        let listing = match std::fs::read_dir(&self.dir) {
+            Ok(listing) => listing,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        let mut entries = Vec::new();
+        for file in listing {
+            let path = file?.path();
+            if path.extension().is_some_and(|extension| extension == "json") {
+                match self.read_entry(&path) {
+                    Ok(entry) => entries.push(entry),
+                    Err(e) => tracing::warn!(path = %path.display(), "Skipping unreadable cache entry: {e}"),
+                }
+            }
+        }
+        entries.sort_by_key(|entry| entry.created_at);
+        Ok(entries)
+    }
+
+    /// Load an entry's receipt and verify it against the guest program
+    /// with the given image ID
+    pub fn verify(&self, entry: &CacheEntry, image_id: ImageId) -> Result<(Vec<u8>, JournalOutput, Vec<u8>)> {
+        if entry.image_id != hex::encode(image_id.as_bytes()) {
+            return Err(VceError::ProofVerificationFailed("Cached proof is from a different guest program".to_string()));
+        }
+        let receipt_bytes = std::fs::read(self.receipt_path(&entry.key))?;
+        let receipt: Receipt = bincode::deserialize(&receipt_bytes)
+            .map_err(|e| VceError::ReceiptDeserialization(format!("Failed to deserialize cached receipt: {e}")))?;
+        receipt.verify(image_id)
+            .map_err(|e| VceError::ProofVerificationFailed(format!("Cached receipt does not verify: {e}")))?;
+        let output: JournalOutput = receipt.journal.decode()
+            .map_err(|e| VceError::RiscZero(format!("Failed to decode cached journal: {e}")))?;
+        if output.result != entry.result {
+            return Err(VceError::ProofVerificationFailed("Cached receipt does not match its metadata".to_string()));
+        }
+        Ok((receipt_bytes, output, receipt.journal.bytes))
+    }
+
+    /// Delete an entry, and its inputs' salt seed once no entry uses it
+    pub fn remove(&self, entry: &CacheEntry) -> Result<()> {
+        remove_if_present(&self.entry_path(&entry.key))?;
+        remove_if_present(&self.receipt_path(&entry.key))?;
+        if !self.entries()?.iter().any(|other| other.inputs == entry.inputs) {
+            remove_if_present(&self.seed_path(&entry.inputs))?;
+        }
+        Ok(())
+    }
+
+    fn read_entry(&self, path: &Path) -> Result<CacheEntry> {
+        Ok(serde_json::from_slice(&std::fs::read(path)?)?)
+    }
+
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{key}.json"))
+    }
+
+    fn receipt_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{key}.receipt"))
+    }
+
+    fn seed_path(&self, inputs: &str) -> PathBuf {
+        self.dir.join("seeds").join(inputs)
+    }
+}
+
+/// First characters of a key, for messages
+#[must_use]
+pub fn short(key: &str) -> &str {
+    &key[..key.len().min(12)]
+}
+
+/// Write through a temporary file, so readers never see part of a file
+fn write_atomically(path: &Path, bytes: &[u8]) -> Result<()> {
+    let partial = path.with_extension(format!("partial-{:016x}", rand::random::<u64>()));
+    std::fs::write(&partial, bytes)?;
+    std::fs::rename(&partial, path)?;
+    Ok(())
+}
+
+fn remove_if_present(path: &Path) -> Result<()> {
+    match std::fs::remove_file(path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::zkvm::{ProverType, SessionLimits};
+
+    fn key(inputs: &ProofInputs<'_>, options: &ProofOptions) -> CacheKey {
+        CacheKey::new(inputs, options, ImageId::ZERO).unwrap()
+    }
+
+    #[test]
+    fn test_key_covers_inputs_seed_and_options() {
+        let serial = ProofOptions::new(ProverType::Local);
+        let inputs = ProofInputs { salt_seed: [1; 32], ..ProofInputs::new(r#"{"claim": "a"}"#, "{}") };
+        let base = key(&inputs, &serial);
+        assert_eq!(base, key(&inputs.clone(), &serial));
+        // CPU and GPU, serial and parallel receipts are interchangeable
+        assert_eq!(base, key(&inputs, &ProofOptions::new(ProverType::Gpu)));
+
+        let reseeded = key(&ProofInputs { salt_seed: [2; 32], ..inputs.clone() }, &serial);
+        assert_eq!(reseeded.inputs, base.inputs);
+        assert_ne!(reseeded.entry, base.entry);
+
+        // The spec is committed byte for byte
+        let reformatted = ProofMode::Single { spec_json: r#"{"claim":"a"}"#, system_data_json: "{}", parse_full_json: false };
+        let reformatted = key(&ProofInputs { mode: reformatted, ..inputs.clone() }, &serial);
+        assert_ne!(reformatted.inputs, base.inputs);
+        assert_eq!(base, key(&inputs, &ProofOptions { workers: 4, ..serial.clone() }));
+        let resegmented = key(&inputs, &ProofOptions { limits: SessionLimits { segment_po2: Some(18), ..Default::default() }, ..serial.clone() });
+        assert_ne!(resegmented.inputs, base.inputs);
+        let other_guest = CacheKey::new(&inputs, &serial, ImageId::from([1u32; 8])).unwrap();
+        assert_ne!(other_guest.inputs, base.inputs);
+    }
+
+    #[test]
+    fn test_corrupted_entry_is_a_miss_and_removed() {
+        let dir = std::env::temp_dir().join(format!("fuse-cache-test-{:016x}", rand::random::<u64>()));
+        let cache = ProofCache::new(&dir);
+        let inputs = ProofInputs { salt_seed: [7; 32], ..ProofInputs::new("{}", "{}") };
+        let key = key(&inputs, &ProofOptions::new(ProverType::Local));
+
+        assert!(cache.entries().unwrap().is_empty());
+        cache.put(&key, &inputs.salt_seed, b"not a receipt", ComplianceResult::Pass).unwrap();
+        let entries = cache.entries().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].key, key.entry);
+
+        assert!(cache.get(&key, &inputs).is_none());
+        assert!(cache.entries().unwrap().is_empty());
+        assert!(!cache.seed_path(&key.inputs).exists());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_unreadable_entry_is_skipped() {
+        let dir = std::env::temp_dir().join(format!("fuse-cache-test-{:016x}", rand::random::<u64>()));
+        let cache = ProofCache::new(&dir);
+        let inputs = ProofInputs { salt_seed: [7; 32], ..ProofInputs::new("{}", "{}") };
+        let key = key(&inputs, &ProofOptions::new(ProverType::Local));
+        cache.put(&key, &inputs.salt_seed, b"receipt", ComplianceResult::Pass).unwrap();
+        std::fs::write(dir.join("truncated.json"), b"{\"key\": ").unwrap();
+
+        let entries = cache.entries().unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].key, key.entry);
+    }
+}
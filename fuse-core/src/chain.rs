@@ -0,0 +1,308 @@
+//! Hash-chained envelopes for continuous compliance
+//!
+//! For continuous monitoring an issuer proves the same spec lineage (one
+//! claim in one jurisdiction, across spec versions) every period. Each
+//! envelope's [`ChainLink`] carries its sequence number, the time period it
+//! covers, and the digest of its predecessor, so auditors get a
+//! tamper-evident history instead of isolated snapshots: changing or
+//! dropping an earlier envelope breaks every later link.
+//!
+//! [`verify_chain`] verifies every envelope, checks the linkage and reports
+//! gaps and overlaps in time coverage, envelopes without a zkVM proof, and
+//! the points where compliance broke.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::envelope::VerifiableComplianceEnvelope;
+use crate::error::{Result, VceError};
+use crate::proof::ComplianceResult;
+
+/// An envelope's place in its lineage
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainLink {
+    /// Position in the lineage, from 0
+    pub sequence: u64,
+    /// Hex digest of the previous envelope (see
+    /// [`VerifiableComplianceEnvelope::digest`]); `None` for sequence 0
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_digest: Option<String>,
+    /// Start of the period the envelope attests to
+    pub period_start: DateTime<Utc>,
+    /// End of the period the envelope attests to
+    pub period_end: DateTime<Utc>,
+}
+
+impl ChainLink {
+    /// Link for the first envelope of a lineage
+    #[must_use]
+    pub fn first(period_start: DateTime<Utc>, period_end: DateTime<Utc>) -> Self {
+        Self { sequence: 0, previous_digest: None, period_start, period_end }
+    }
+
+    /// Link for the envelope following `previous`, covering up to
+    /// `period_end` from where `previous` left off
+    pub fn next(previous: &VerifiableComplianceEnvelope, period_end: DateTime<Utc>) -> Result<Self> {
+        let link = previous.chain.as_ref().ok_or_else(|| {
+            VceError::ChainVerificationFailed("Previous envelope is not part of a chain".to_string())
+        })?;
+        Ok(Self {
+            sequence: link.sequence + 1,
+            previous_digest: Some(previous.digest()?),
+            period_start: link.period_end,
+            period_end,
+        })
+    }
+}
+
+/// Time between two consecutive envelopes that neither covers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoverageGap {
+    /// Sequence number of the envelope before the gap
+    pub after: u64,
+    pub from: DateTime<Utc>,
+    pub until: DateTime<Utc>,
+}
+
+/// Time covered by two consecutive envelopes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoverageOverlap {
+    /// Sequence number of the earlier envelope
+    pub after: u64,
+    pub from: DateTime<Utc>,
+    pub until: DateTime<Utc>,
+}
+
+/// Envelope whose result is not a pass, following a pass or starting the chain
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComplianceBreak {
+    pub sequence: u64,
+    /// Start of the first period that did not pass
+    pub since: DateTime<Utc>,
+    pub result: ComplianceResult,
+}
+
+/// Outcome of checking a chain whose links are intact
+#[derive(Debug, Clone, Default)]
+pub struct ChainReport {
+    /// Sequence numbers checked, in order
+    pub sequences: Vec<u64>,
+    /// Verified result of each envelope, in the same order; `Error` for an
+    /// unproven envelope, whatever its file claims
+    pub results: Vec<ComplianceResult>,
+    pub gaps: Vec<CoverageGap>,
+    pub overlaps: Vec<CoverageOverlap>,
+    /// Sequence numbers of envelopes carrying a placeholder instead of a
+    /// zkVM proof
+    pub unproven: Vec<u64>,
+    pub breaks: Vec<ComplianceBreak>,
+}
+
+impl ChainReport {
+    /// Whether the chain covers its whole time span and proved a pass
+    /// throughout
+    #[must_use]
+    pub fn is_continuous_compliance(&self) -> bool {
+        self.gaps.is_empty() && self.unproven.is_empty() && self.breaks.is_empty()
+    }
+}
+
+/// Check that the envelopes form one unbroken chain, in any input order
+///
+/// The chain may start after sequence 0 when earlier envelopes are not at
+/// hand, but every envelope given must link to the one before it. Every
+/// envelope must verify, and breaks are found from the results committed
+/// to the verified journals, not the results the files claim. A
+/// placeholder proof has no journal, so its envelope is reported as
+/// unproven and counts as inconclusive.
+pub fn verify_chain(envelopes: &[VerifiableComplianceEnvelope]) -> Result<ChainReport> {
+    check_chain(envelopes, verified_result)
+}
+
+/// [`verify_chain`] with `verify` giving each envelope's proven result,
+/// or `None` if it is unproven
+fn check_chain(
+    envelopes: &[VerifiableComplianceEnvelope],
+    verify: impl Fn(&VerifiableComplianceEnvelope) -> Result<Option<ComplianceResult>>,
+) -> Result<ChainReport> {
+    let mut links = envelopes
+        .iter()
+        .map(|envelope| {
+            let link = envelope.chain.as_ref().ok_or_else(|| {
+                VceError::ChainVerificationFailed(format!("Envelope for '{}' has no chain link", envelope.spec.claim))
+            })?;
+            Ok((link, envelope))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    links.sort_by_key(|(link, _)| link.sequence);
+
+    let mut report = ChainReport::default();
+    let mut previous: Option<(&ChainLink, &VerifiableComplianceEnvelope, ComplianceResult)> = None;
+    for (link, envelope) in links {
+        if link.period_end < link.period_start {
+            return Err(VceError::ChainVerificationFailed(format!("Envelope {} ends before it starts", link.sequence)));
+        }
+        if link.sequence == 0 && link.previous_digest.is_some() {
+            return Err(VceError::ChainVerificationFailed("Envelope 0 links to a predecessor".to_string()));
+        }
+        let proven = verify(envelope).map_err(|e| {
+            VceError::ChainVerificationFailed(format!("Envelope {} does not verify: {e}", link.sequence))
+        })?;
+        if proven.is_none() {
+            report.unproven.push(link.sequence);
+        }
+        let result = proven.unwrap_or(ComplianceResult::Error);
+        let passed = result == ComplianceResult::Pass;
+        match previous {
+            Some((previous_link, previous_envelope, previous_result)) => {
+                if link.sequence != previous_link.sequence + 1 {
+                    return Err(VceError::ChainVerificationFailed(format!(
+                        "Envelope {} follows envelope {}; the envelopes in between are missing",
+                        link.sequence, previous_link.sequence
+                    )));
+                }
+                if (&envelope.spec.claim, &envelope.spec.jurisdiction) != (&previous_envelope.spec.claim, &previous_envelope.spec.jurisdiction) {
+                    return Err(VceError::ChainVerificationFailed(format!(
+                        "Envelope {} is for a different spec lineage",
+                        link.sequence
+                    )));
+                }
+                if link.previous_digest.as_deref() != Some(previous_envelope.digest()?.as_str()) {
+                    return Err(VceError::ChainVerificationFailed(format!(
+                        "Envelope {} does not link to envelope {}; the history was altered",
+                        link.sequence, previous_link.sequence
+                    )));
+                }
+                if link.period_start > previous_link.period_end {
+                    report.gaps.push(CoverageGap { after: previous_link.sequence, from: previous_link.period_end, until: link.period_start });
+                } else if link.period_start < previous_link.period_end {
+                    let until = link.period_end.min(previous_link.period_end);
+                    report.overlaps.push(CoverageOverlap { after: previous_link.sequence, from: link.period_start, until });
+                }
+                if !passed && previous_result == ComplianceResult::Pass {
+                    report.breaks.push(ComplianceBreak { sequence: link.sequence, since: link.period_start, result });
+                }
+            }
+            None if !passed => {
+                report.breaks.push(ComplianceBreak { sequence: link.sequence, since: link.period_start, result });
+            }
+            None => {}
+        }
+        report.sequences.push(link.sequence);
+        report.results.push(result);
+        previous = Some((link, envelope, result));
+    }
+    Ok(report)
+}
+
+/// Verify a copy of the envelope, since the chain digests are over the
+/// envelopes as issued, and read its result from the verified journal
+///
+/// Placeholder proofs verify only for compatibility and have no journal;
+/// the result their file claims proves nothing, so they give `None`.
+fn verified_result(envelope: &VerifiableComplianceEnvelope) -> Result<Option<ComplianceResult>> {
+    let mut envelope = envelope.clone();
+    envelope.verify()?;
+    if envelope.proof.is_placeholder() {
+        return Ok(None);
+    }
+    Ok(envelope.proof.output().map(|output| output.result))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proof::ComplianceProof;
+    use crate::spec::ComplianceSpec;
+    use chrono::TimeZone;
+    use std::collections::BTreeMap;
+
+    fn day(day: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 1, day, 0, 0, 0).unwrap()
+    }
+
+    fn envelope(result: ComplianceResult) -> VerifiableComplianceEnvelope {
+        let spec = ComplianceSpec::new(
+            "SOC2 control X".to_string(),
+            "abc123".to_string(),
+            BTreeMap::new(),
+            "US".to_string(),
+            "1.0".to_string(),
+            Utc::now() + chrono::Duration::days(365),
+        );
+        let proof = ComplianceProof::new(spec.hash(), result, vec![]);
+        VerifiableComplianceEnvelope::new(spec, proof)
+    }
+
+    fn chain(results: &[(ComplianceResult, u32, u32)]) -> Vec<VerifiableComplianceEnvelope> {
+        let mut envelopes: Vec<VerifiableComplianceEnvelope> = Vec::new();
+        for &(result, start, end) in results {
+            let link = match envelopes.last() {
+                Some(previous) => ChainLink { period_start: day(start), ..ChainLink::next(previous, day(end)).unwrap() },
+                None => ChainLink::first(day(start), day(end)),
+            };
+            envelopes.push(envelope(result).with_chain_link(link));
+        }
+        envelopes
+    }
+
+    /// Check the chain as if every envelope carried a zkVM proof of the
+    /// result its file claims
+    fn check_as_proven(envelopes: &[VerifiableComplianceEnvelope]) -> Result<ChainReport> {
+        check_chain(envelopes, |envelope| Ok(Some(envelope.proof.result)))
+    }
+
+    #[test]
+    fn test_chain_reports_gaps_overlaps_and_breaks() {
+        use ComplianceResult::{Fail, Pass};
+        let mut envelopes = chain(&[(Pass, 1, 8), (Pass, 8, 15), (Fail, 17, 22), (Pass, 20, 29)]);
+        envelopes.reverse();
+        let report = check_as_proven(&envelopes).unwrap();
+        assert_eq!(report.sequences, vec![0, 1, 2, 3]);
+        assert_eq!(report.gaps, vec![CoverageGap { after: 1, from: day(15), until: day(17) }]);
+        assert_eq!(report.overlaps, vec![CoverageOverlap { after: 2, from: day(20), until: day(22) }]);
+        assert_eq!(report.breaks, vec![ComplianceBreak { sequence: 2, since: day(17), result: Fail }]);
+        assert!(!report.is_continuous_compliance());
+
+        let report = check_as_proven(&chain(&[(Pass, 1, 8), (Pass, 8, 15)])).unwrap();
+        assert!(report.is_continuous_compliance());
+    }
+
+    #[test]
+    fn test_altered_or_missing_envelope_breaks_chain() {
+        use ComplianceResult::{Fail, Pass};
+        let mut envelopes = chain(&[(Pass, 1, 8), (Fail, 8, 15), (Pass, 15, 22)]);
+        assert!(!check_as_proven(&envelopes).unwrap().is_continuous_compliance());
+
+        let mut altered = envelopes.clone();
+        altered[1].proof.result = Pass;
+        assert!(check_as_proven(&altered).is_err());
+
+        envelopes.remove(1);
+        assert!(check_as_proven(&envelopes).is_err());
+    }
+
+    #[test]
+    fn test_placeholder_claiming_a_pass_is_unproven() {
+        use ComplianceResult::{Error, Pass};
+        // Nothing links to the last envelope, so only its proof could back its claim
+        let envelopes = chain(&[(Pass, 1, 8), (Pass, 8, 15)]);
+        assert!(envelopes.iter().all(|envelope| envelope.proof.is_placeholder()));
+        let report = verify_chain(&envelopes).unwrap();
+        assert_eq!(report.unproven, vec![0, 1]);
+        assert_eq!(report.results, vec![Error, Error]);
+        assert_eq!(report.breaks, vec![ComplianceBreak { sequence: 0, since: day(1), result: Error }]);
+        assert!(!report.is_continuous_compliance());
+    }
+
+    #[test]
+    fn test_envelope_that_does_not_verify_breaks_chain() {
+        use ComplianceResult::Pass;
+        let mut envelopes = chain(&[(Pass, 1, 8), (Pass, 8, 15)]);
+        assert!(verify_chain(&envelopes).is_ok());
+
+        // The last envelope is not linked to, so only verification catches it
+        envelopes[1].proof.spec_hash = "0".repeat(64);
+        let error = verify_chain(&envelopes).unwrap_err();
+        assert!(error.to_string().contains("Envelope 1 does not verify"), "{error}");
+    }
+}
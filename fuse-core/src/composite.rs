@@ -0,0 +1,220 @@
+//! Composite specifications
+//!
+//! A composite spec combines several sub-claims in a tree joined by
+//! `all`, `any` or `threshold` (k-of-n), so a single envelope can attest to
+//! a whole control set. The guest program evaluates the tree and commits the
+//! result of every node; the types here mirror what it commits.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use crate::proof::{ComplianceResult, ErrorCode};
+use crate::score::Score;
+
+/// Maximum nesting depth accepted by the guest program
+pub const MAX_DEPTH: usize = 16;
+
+/// A node in a composite claim tree
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClaimNode {
+    /// A single sub-claim checked by one checker
+    Check(SubClaim),
+    /// Passes when every child passes
+    All(Vec<ClaimNode>),
+    /// Passes when at least one child passes
+    Any(Vec<ClaimNode>),
+    /// Passes when at least `k` children pass
+    Threshold { k: usize, of: Vec<ClaimNode> },
+}
+
+/// A leaf of a composite claim tree
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubClaim {
+    /// Identifier reported in the per-node results; unique within the tree
+    pub id: String,
+
+    /// Claim routed to a checker, as in `ComplianceSpec::claim`
+    pub claim: String,
+
+    /// Constraints for this sub-claim's checker
+    #[serde(default)]
+    pub constraints: BTreeMap<String, String>,
+
+    /// Pass threshold for this sub-claim, if its checker is graded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pass_threshold: Option<String>,
+
+    /// Rule expression deciding this sub-claim instead of a built-in checker
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rule: Option<String>,
+
+    /// Top-level key of the system data holding this sub-claim's evidence.
+    /// Without it the checker sees the whole document.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_key: Option<String>,
+}
+
+/// Result of one node in a composite tree, committed to the journal
+///
+/// Mirrors the guest program's `NodeResult`; field order must match.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NodeResult {
+    /// Location in the tree: "/" for the root, "/0/2" for the third child of the first child
+    pub path: String,
+    /// Leaf id, or the combinator ("all", "any", "2-of-3")
+    pub label: String,
+    pub result: ComplianceResult,
+    #[serde(default)]
+    pub error_code: ErrorCode,
+    #[serde(default)]
+    pub score: Score,
+}
+
+impl ClaimNode {
+    /// Children and the number of them that must pass, for combinator nodes
+    #[must_use]
+    pub fn children(&self) -> Option<(&[ClaimNode], usize)> {
+        match self {
+            ClaimNode::Check(_) => None,
+            ClaimNode::All(children) => Some((children, children.len())),
+            ClaimNode::Any(children) => Some((children, 1)),
+            ClaimNode::Threshold { k, of } => Some((of, *k)),
+        }
+    }
+
+    /// Label reported for this node in the per-node results
+    #[must_use]
+    pub fn label(&self) -> String {
+        match self {
+            ClaimNode::Check(leaf) => leaf.id.clone(),
+            ClaimNode::All(_) => "all".to_string(),
+            ClaimNode::Any(_) => "any".to_string(),
+            ClaimNode::Threshold { k, of } => format!("{k}-of-{}", of.len()),
+        }
+    }
+
+    /// Check the tree is well formed: non-empty combinators, satisfiable
+    /// thresholds, unique leaf ids and bounded depth
+    pub fn validate(&self) -> crate::Result<()> {
+        let mut ids = HashSet::new();
+        self.validate_node(0, &mut ids)
+    }
+
+    fn validate_node<'a>(&'a self, depth: usize, ids: &mut HashSet<&'a str>) -> crate::Result<()> {
+        if depth > MAX_DEPTH {
+            return Err(invalid(format!("Composite tree is nested deeper than {MAX_DEPTH} levels")));
+        }
+        match self {
+            ClaimNode::Check(leaf) => {
+                if leaf.id.is_empty() || leaf.claim.is_empty() {
+                    return Err(invalid("Composite sub-claims need an id and a claim".to_string()));
+                }
+                if !ids.insert(leaf.id.as_str()) {
+                    return Err(invalid(format!("Duplicate composite sub-claim id '{}'", leaf.id)));
+                }
+                if let Some(rule) = &leaf.rule {
+                    crate::spec::validate_rule(rule)?;
+                }
+                if let Some(threshold) = &leaf.pass_threshold {
+                    if crate::score::parse_threshold(threshold).is_none() {
+                        return Err(invalid(format!(
+                            "Sub-claim '{}' has an invalid pass threshold '{threshold}'",
+                            leaf.id
+                        )));
+                    }
+                }
+                Ok(())
+            }
+            _ => {
+                let (children, required) = self.children().unwrap_or_default();
+                if children.is_empty() {
+                    return Err(invalid(format!("Composite '{}' node has no children", self.label())));
+                }
+                if required == 0 || required > children.len() {
+                    return Err(invalid(format!(
+                        "Composite threshold must be between 1 and {}, got {required}",
+                        children.len()
+                    )));
+                }
+                children.iter().try_for_each(|child| child.validate_node(depth + 1, ids))
+            }
+        }
+    }
+}
+
+/// Combine child results for a node that needs `required` passes
+///
+/// Uses three-valued logic: enough passes is a Pass, too few possible
+/// passes (even if every errored child had passed) is a Fail, and anything
+/// in between is inconclusive.
+#[must_use]
+pub fn combine(results: &[ComplianceResult], required: usize) -> ComplianceResult {
+    let passed = results.iter().filter(|r| **r == ComplianceResult::Pass).count();
+    let errored = results.iter().filter(|r| **r == ComplianceResult::Error).count();
+    if passed >= required {
+        ComplianceResult::Pass
+    } else if passed + errored < required {
+        ComplianceResult::Fail
+    } else {
+        ComplianceResult::Error
+    }
+}
+
+fn invalid(message: String) -> crate::VceError {
+    crate::VceError::InvalidSpec(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ComplianceResult::{Error, Fail, Pass};
+
+    fn leaf(id: &str) -> ClaimNode {
+        ClaimNode::Check(SubClaim {
+            id: id.to_string(),
+            claim: "GDPR data residency".to_string(),
+            constraints: BTreeMap::new(),
+            pass_threshold: None,
+            rule: None,
+            data_key: None,
+        })
+    }
+
+    #[test]
+    fn test_combine_three_valued() {
+        assert_eq!(combine(&[Pass, Pass], 2), Pass);
+        assert_eq!(combine(&[Pass, Fail], 2), Fail);
+        assert_eq!(combine(&[Pass, Error], 2), Error);
+        assert_eq!(combine(&[Fail, Error], 1), Error);
+        assert_eq!(combine(&[Pass, Error], 1), Pass);
+        assert_eq!(combine(&[Pass, Fail, Error], 2), Error);
+        assert_eq!(combine(&[Fail, Fail, Error], 2), Fail);
+    }
+
+    #[test]
+    fn test_validate_tree() {
+        let tree = ClaimNode::All(vec![
+            leaf("a"),
+            ClaimNode::Threshold { k: 1, of: vec![leaf("b"), leaf("c")] },
+        ]);
+        assert!(tree.validate().is_ok());
+
+        assert!(ClaimNode::All(vec![leaf("a"), leaf("a")]).validate().is_err());
+        assert!(ClaimNode::Any(vec![]).validate().is_err());
+        assert!(ClaimNode::Threshold { k: 3, of: vec![leaf("a"), leaf("b")] }.validate().is_err());
+        assert!(ClaimNode::Threshold { k: 0, of: vec![leaf("a")] }.validate().is_err());
+    }
+
+    #[test]
+    fn test_claim_node_json_shape() {
+        let json = r#"{"any": [
+            {"check": {"id": "eu", "claim": "GDPR data residency", "constraints": {"data_region": "EU"}}},
+            {"threshold": {"k": 1, "of": [{"check": {"id": "soc2", "claim": "SOC2 control X verified"}}]}}
+        ]}"#;
+        let node: ClaimNode = serde_json::from_str(json).unwrap();
+        assert_eq!(node.label(), "any");
+        let (children, required) = node.children().unwrap();
+        assert_eq!((children.len(), required), (2, 1));
+        assert_eq!(children[1].label(), "1-of-1");
+    }
+}
@@ -0,0 +1,237 @@
+//! Selective disclosure paths
+//!
+//! Mirrors the guest program's disclosure filter. Entries in
+//! `disclosed_fields` starting with `/` are JSON Pointers (RFC 6901) into
+//! the system data, with `*` additionally matching every element of an
+//! array. Bare names are top-level keys of the checker's legacy disclosure
+//! root (the `claim` object for C2PA). Paths that do not resolve are
+//! reported in `JournalOutput::missing_fields`.
+//!
+//! Disclosure predicates reveal derived facts instead of values, such as
+//! "`capture_time` is before 2025-06-01": each is a rule expression over
+//! the system data, and only its boolean outcome is committed.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashSet;
+use crate::proof::ErrorCode;
+
+/// A named fact about the system data whose outcome is disclosed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DisclosurePredicate {
+    /// Name reported in the journal
+    pub name: String,
+    /// Rule expression, e.g. `date(claim.capture_time) < date("2025-06-01")`
+    pub expr: String,
+}
+
+/// Outcome of one disclosure predicate, committed to the journal
+///
+/// Mirrors the guest program's `PredicateResult`; field order must match.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PredicateResult {
+    pub name: String,
+    /// False whenever `error_code` is set
+    pub holds: bool,
+    #[serde(default)]
+    pub error_code: ErrorCode,
+}
+
+/// Check predicate names are unique and every expression parses
+pub fn validate_predicates(predicates: &[DisclosurePredicate]) -> crate::Result<()> {
+    let mut names = HashSet::new();
+    for predicate in predicates {
+        if predicate.name.is_empty() || !names.insert(predicate.name.as_str()) {
+            return Err(crate::VceError::InvalidSpec(format!(
+                "Disclosure predicate names must be unique and non-empty, got '{}'",
+                predicate.name
+            )));
+        }
+        crate::spec::validate_rule(&predicate.expr)?;
+    }
+    Ok(())
+}
+
+/// Filtered document plus the paths that could not be resolved
+#[derive(Debug, Clone, PartialEq)]
+pub struct Disclosure {
+    pub redacted: Value,
+    pub missing: Vec<String>,
+}
+
+/// Apply `disclosed_fields` to the system data
+#[must_use]
+pub fn disclose(fields: &[String], system_data: &Value, legacy_root: &Value) -> Disclosure {
+    let mut redacted = Value::Object(Map::new());
+    let mut missing = Vec::new();
+
+    for field in fields {
+        let found = match parse_pointer(field) {
+            Some(tokens) => {
+                let tokens: Vec<&str> = tokens.iter().map(String::as_str).collect();
+                resolves(system_data, &tokens) && {
+                    copy(system_data, &tokens, &mut redacted);
+                    true
+                }
+            }
+            // Legacy top-level name
+            None => match legacy_root.get(field) {
+                Some(value) => {
+                    if let Value::Object(map) = &mut redacted {
+                        map.insert(field.clone(), value.clone());
+                    }
+                    true
+                }
+                None => false,
+            },
+        };
+        if !found {
+            missing.push(field.clone());
+        }
+    }
+
+    Disclosure { redacted, missing }
+}
+
+/// Check a disclosure path is a bare name or a well-formed JSON Pointer
+pub fn validate_path(path: &str) -> crate::Result<()> {
+    if path.starts_with('/') {
+        let mut chars = path.chars();
+        while let Some(c) = chars.next() {
+            if c == '~' && !matches!(chars.next(), Some('0' | '1')) {
+                return Err(crate::VceError::InvalidSpec(format!(
+                    "Disclosure path '{path}' has an invalid '~' escape"
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Split a JSON Pointer into unescaped reference tokens.
+/// Returns `None` for bare names, which do not start with `/`.
+fn parse_pointer(path: &str) -> Option<Vec<String>> {
+    let rest = path.strip_prefix('/')?;
+    Some(rest.split('/').map(|token| token.replace("~1", "/").replace("~0", "~")).collect())
+}
+
+/// Array index per RFC 6901: decimal digits without leading zeros
+fn array_index(token: &str) -> Option<usize> {
+    if token.is_empty() || (token.len() > 1 && token.starts_with('0')) || !token.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    token.parse().ok()
+}
+
+/// Whether every value selected by the path exists
+fn resolves(source: &Value, tokens: &[&str]) -> bool {
+    let Some((token, rest)) = tokens.split_first() else {
+        return true;
+    };
+    match source {
+        Value::Object(map) => map.get(*token).is_some_and(|child| resolves(child, rest)),
+        Value::Array(items) if *token == "*" => items.iter().all(|child| resolves(child, rest)),
+        Value::Array(items) => array_index(token)
+            .and_then(|index| items.get(index))
+            .is_some_and(|child| resolves(child, rest)),
+        _ => false,
+    }
+}
+
+/// Copy the selected values into the redacted document, keeping their
+/// location. Undisclosed array elements before a disclosed index are null.
+/// The path must already resolve.
+fn copy(source: &Value, tokens: &[&str], out: &mut Value) {
+    let Some((token, rest)) = tokens.split_first() else {
+        *out = source.clone();
+        return;
+    };
+    match source {
+        Value::Object(map) => {
+            if !out.is_object() {
+                *out = Value::Object(Map::new());
+            }
+            if let (Some(child), Value::Object(out_map)) = (map.get(*token), out) {
+                copy(child, rest, out_map.entry((*token).to_string()).or_insert(Value::Null));
+            }
+        }
+        Value::Array(items) => {
+            if !out.is_array() {
+                *out = Value::Array(Vec::new());
+            }
+            let Value::Array(out_items) = out else { return };
+            let selected: Vec<usize> = if *token == "*" {
+                (0..items.len()).collect()
+            } else {
+                array_index(token).into_iter().collect()
+            };
+            for index in selected {
+                if out_items.len() <= index {
+                    out_items.resize(index + 1, Value::Null);
+                }
+                copy(&items[index], rest, &mut out_items[index]);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn fields(paths: &[&str]) -> Vec<String> {
+        paths.iter().map(|p| (*p).to_string()).collect()
+    }
+
+    #[test]
+    fn test_nested_pointers() {
+        let data = json!({
+            "claim": {"claim_generator": "cam", "signature_info": {"issuer": "CA", "serial": "42"}},
+            "storage_locations": [{"region": "EU", "host": "a"}, {"region": "US", "host": "b"}]
+        });
+        let disclosure = disclose(
+            &fields(&["/claim/signature_info/issuer", "/storage_locations/*/region"]),
+            &data,
+            &data,
+        );
+        assert_eq!(disclosure.redacted, json!({
+            "claim": {"signature_info": {"issuer": "CA"}},
+            "storage_locations": [{"region": "EU"}, {"region": "US"}]
+        }));
+        assert!(disclosure.missing.is_empty());
+    }
+
+    #[test]
+    fn test_legacy_names_and_missing_paths() {
+        let data = json!({"claim": {"claim_generator": "cam", "a/b": 1}, "items": [1, 2]});
+        let disclosure = disclose(
+            &fields(&["claim_generator", "absent", "/claim/a~1b", "/claim/nope/deeper", "/items/1", "/items/01"]),
+            &data,
+            &data["claim"],
+        );
+        assert_eq!(disclosure.redacted, json!({
+            "claim_generator": "cam",
+            "claim": {"a/b": 1},
+            "items": [null, 2]
+        }));
+        assert_eq!(disclosure.missing, fields(&["absent", "/claim/nope/deeper", "/items/01"]));
+    }
+
+    #[test]
+    fn test_validate_predicates() {
+        let predicate = |name: &str, expr: &str| DisclosurePredicate { name: name.to_string(), expr: expr.to_string() };
+        assert!(validate_predicates(&[predicate("trusted", r#"claim.issuer in ["CA"]"#)]).is_ok());
+        assert!(validate_predicates(&[predicate("a", "true"), predicate("a", "false")]).is_err());
+        assert!(validate_predicates(&[predicate("bad", r#"claim.issuer matches "(""#)]).is_err());
+    }
+
+    #[test]
+    fn test_validate_path() {
+        assert!(validate_path("issuer").is_ok());
+        assert!(validate_path("/a~0b/c~1d").is_ok());
+        assert!(validate_path("/a~2").is_err());
+        assert!(validate_path("/a~").is_err());
+    }
+}
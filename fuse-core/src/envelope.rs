@@ -1,8 +1,11 @@
 //! Verifiable Compliance Envelope (VCE) structure
 
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use crate::chain::ChainLink;
 use crate::spec::ComplianceSpec;
 use crate::proof::ComplianceProof;
+use crate::registry::CheckerId;
 use crate::error::{VceError, Result};
 
 /// Verifiable Compliance Envelope - the complete artifact
@@ -17,6 +20,11 @@ pub struct VerifiableComplianceEnvelope {
     /// Optional signature (for future use)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub signature: Option<String>,
+
+    /// Place in a hash-chained lineage of periodic envelopes, linking to the
+    /// previous one (see [`crate::chain`])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chain: Option<ChainLink>,
 }
 
 impl VerifiableComplianceEnvelope {
@@ -27,9 +35,23 @@ impl VerifiableComplianceEnvelope {
             spec,
             proof,
             signature: None,
+            chain: None,
         }
     }
 
+    /// Place the envelope in a chain of periodic envelopes
+    #[must_use]
+    pub fn with_chain_link(mut self, link: ChainLink) -> Self {
+        self.chain = Some(link);
+        self
+    }
+
+    /// Hex SHA-256 of the envelope's JSON, which the next envelope of a
+    /// chain links to; independent of how the file is formatted
+    pub fn digest(&self) -> Result<String> {
+        Ok(hex::encode(Sha256::digest(serde_json::to_vec(self)?)))
+    }
+
     /// Verify the entire envelope
     pub fn verify(&mut self) -> Result<()> {
         // Validate the specification
@@ -47,6 +69,40 @@ impl VerifiableComplianceEnvelope {
         // Verify the proof itself
         self.proof.verify()?;
 
+        // The journal must be for this spec, not another wrapped under it
+        // (nor, for a batch entry, another spec in the batch); verifying a
+        // real proof always decodes it. Proofs are made over the spec's
+        // `to_proof_json` form, so that is what the commitment is checked
+        // against.
+        if self.proof.journal_output.is_some() {
+            let spec_json = self.spec.to_proof_json()?;
+            let committed = self.proof.evidence()
+                .into_iter()
+                .find(|commitment| commitment.name == crate::evidence::SPEC);
+            if !committed.is_some_and(|commitment| commitment.matches(spec_json.as_bytes())) {
+                return Err(VceError::ProofVerificationFailed(
+                    "Proof was not generated for this specification in its to_proof_json form".to_string(),
+                ));
+            }
+        }
+
+        // The receipt must come from the guest program of the checker the
+        // spec names; batches and aggregates run in the general one
+        if let Some(checker) = self.proof.checker {
+            let aggregate = self.proof.journal_output.as_ref()
+                .is_some_and(|output| !output.children.is_empty() && !output.log_state.is_incremental());
+            let expected = if self.proof.batch_entry.is_some() || aggregate {
+                CheckerId::General
+            } else {
+                CheckerId::for_spec(&self.spec)
+            };
+            if checker != expected {
+                return Err(VceError::ProofVerificationFailed(format!(
+                    "Proof was generated by the {checker} guest program, but the specification's claim is checked by the {expected} one"
+                )));
+            }
+        }
+
         Ok(())
     }
 
@@ -98,5 +154,85 @@ mod tests {
         let mut envelope = VerifiableComplianceEnvelope::new(spec, proof);
         assert!(envelope.verify().is_ok());
     }
+
+    #[test]
+    fn test_batch_entry_must_commit_envelope_spec() {
+        let spec = |claim: &str| ComplianceSpec::new(
+            claim.to_string(),
+            "abc123".to_string(),
+            BTreeMap::new(),
+            "EU".to_string(),
+            "1.0".to_string(),
+            chrono::Utc::now() + chrono::Duration::days(365),
+        );
+        let specs = [spec("GDPR data residency"), spec("ML model usage constraint")];
+        let entry = |spec: &ComplianceSpec, result: &str| serde_json::json!({
+            "result": result,
+            "claim_hash": [],
+            "redacted_json": "",
+            "evidence": [{
+                "name": crate::evidence::SPEC,
+                "digest": crate::evidence::digest(crate::evidence::SPEC, &serde_json::to_vec(spec).unwrap()).to_vec(),
+            }],
+        });
+        let journal: crate::proof::JournalOutput = serde_json::from_value(serde_json::json!({
+            "result": "Fail",
+            "claim_hash": [],
+            "redacted_json": "",
+            "batch": [entry(&specs[0], "Pass"), entry(&specs[1], "Fail")],
+        })).unwrap();
+
+        let envelope = |spec: &ComplianceSpec, index: usize| {
+            let mut proof = ComplianceProof::new(spec.hash(), ComplianceResult::Error, vec![]);
+            proof.journal_output = Some(journal.clone());
+            VerifiableComplianceEnvelope::new(spec.clone(), proof.with_batch_entry(index).unwrap())
+        };
+        let mut gdpr = envelope(&specs[0], 0);
+        assert!(gdpr.verify().is_ok());
+        assert_eq!(gdpr.proof.result, ComplianceResult::Pass);
+        assert!(envelope(&specs[1], 0).verify().is_err());
+        assert!(envelope(&specs[1], 1).verify().is_ok());
+    }
+
+    #[test]
+    fn test_spec_commitment_must_be_over_proof_json() {
+        let spec = ComplianceSpec::new(
+            "GDPR data residency".to_string(),
+            "abc123".to_string(),
+            BTreeMap::from([("data_region".to_string(), "EU".to_string())]),
+            "EU".to_string(),
+            "1.0".to_string(),
+            chrono::Utc::now() + chrono::Duration::days(365),
+        );
+        let proved_over = |spec_json: &[u8]| {
+            let journal: crate::proof::JournalOutput = serde_json::from_value(serde_json::json!({
+                "result": "Pass",
+                "claim_hash": [],
+                "redacted_json": "",
+                "evidence": [{
+                    "name": crate::evidence::SPEC,
+                    "digest": crate::evidence::digest(crate::evidence::SPEC, spec_json).to_vec(),
+                }],
+            })).unwrap();
+            let mut proof = ComplianceProof::new(spec.hash(), ComplianceResult::Pass, vec![]);
+            proof.journal_output = Some(journal);
+            VerifiableComplianceEnvelope::new(spec.clone(), proof).verify()
+        };
+
+        assert!(proved_over(spec.to_proof_json().unwrap().as_bytes()).is_ok());
+
+        // The same spec, formatted or ordered differently, was not what the
+        // envelope's spec commits to
+        let pretty = serde_json::to_string_pretty(&spec).unwrap();
+        let mut reordered: Vec<(String, serde_json::Value)> =
+            serde_json::from_str::<serde_json::Map<_, _>>(&spec.to_proof_json().unwrap()).unwrap().into_iter().collect();
+        reordered.reverse();
+        let reordered = serde_json::to_string(&serde_json::Map::from_iter(reordered)).unwrap();
+        assert_eq!(serde_json::from_str::<ComplianceSpec>(&reordered).unwrap().hash(), spec.hash());
+        for other_form in [pretty, reordered] {
+            let e = proved_over(other_form.as_bytes()).unwrap_err();
+            assert!(e.to_string().contains("to_proof_json"), "{e}");
+        }
+    }
 }
 
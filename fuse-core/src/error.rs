@@ -9,6 +9,11 @@ pub enum VceError {
     #[error("Invalid specification: {0}")]
     InvalidSpec(String),
 
+    /// A checker could not decide; the code is the one the guest program
+    /// commits for the same input
+    #[error("Check inconclusive: {1}")]
+    Inconclusive(crate::proof::ErrorCode, String),
+
     #[error("Invalid envelope format: {0}")]
     InvalidEnvelope(String),
 
@@ -41,5 +46,14 @@ pub enum VceError {
 
     #[error("Receipt deserialization error: {0}")]
     ReceiptDeserialization(String),
+
+    #[error("Chain verification failed: {0}")]
+    ChainVerificationFailed(String),
+
+    #[error("Session limit exceeded: {0}")]
+    SessionLimitExceeded(String),
+
+    #[error("Proof generation cancelled")]
+    Cancelled,
 }
 
@@ -0,0 +1,280 @@
+//! Evidence commitment for later audit
+//!
+//! Every proof commits a domain-separated SHA-256 of each guest input: the
+//! spec JSON, the system data, and any extra evidence files the prover
+//! attached (e.g. the original media file a C2PA manifest was extracted
+//! from). Under a dispute the prover hands over the evidence and an
+//! auditor confirms with `fuse-audit-evidence` that it matches what was
+//! proven.
+//!
+//! Evidence that spans several sources (IAM export, access logs, storage
+//! inventory) can be passed as a bundle of named JSON documents instead of
+//! a single system data file. Each document is committed under its name,
+//! and the checkers see an object keyed by name, so rules and disclosure
+//! paths address them as `/iam/...`, `/access_logs/...`.
+//!
+//! Digest: `SHA256("fuse.evidence.v1" || u64_be(len(name)) || name || bytes)`.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::borrow::Cow;
+use std::collections::BTreeSet;
+use std::io::Read;
+use std::path::Path;
+use crate::error::{Result, VceError};
+use crate::proof::JournalOutput;
+use crate::zkvm::{BatchData, ProofInputs, ProofMode};
+
+const EVIDENCE_DOMAIN: &[u8] = b"fuse.evidence.v1";
+
+/// Evidence name of the spec JSON
+pub const SPEC: &str = "spec";
+/// Evidence name of the system data
+pub const SYSTEM_DATA: &str = "system_data";
+
+/// A named evidence file passed to the guest, either attached or as part
+/// of an evidence bundle
+///
+/// Mirrors the guest program's `EvidenceFile`; field order must match.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EvidenceFile {
+    pub name: String,
+    pub bytes: Vec<u8>,
+}
+
+/// Digest of one piece of evidence, committed to the journal
+///
+/// Mirrors the guest program's `EvidenceCommitment`; field order must match.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EvidenceCommitment {
+    pub name: String,
+    pub digest: Vec<u8>,
+}
+
+impl EvidenceCommitment {
+    /// Whether `bytes` are the evidence this commitment was made over
+    #[must_use]
+    pub fn matches(&self, bytes: &[u8]) -> bool {
+        digest(&self.name, bytes).as_slice() == self.digest.as_slice()
+    }
+}
+
+/// Digest of `bytes` committed under `name`
+#[must_use]
+pub fn digest(name: &str, bytes: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(EVIDENCE_DOMAIN);
+    hasher.update((name.len() as u64).to_be_bytes());
+    hasher.update(name.as_bytes());
+    hasher.update(bytes);
+    hasher.finalize().into()
+}
+
+/// Check evidence files have distinct, non-empty names that do not shadow
+/// the spec or system data
+pub fn validate_files<'a>(files: impl IntoIterator<Item = &'a EvidenceFile>) -> Result<()> {
+    let mut seen = BTreeSet::new();
+    for file in files {
+        if file.name.is_empty() || file.name == SPEC || file.name == SYSTEM_DATA {
+            return Err(VceError::InputSerialization(format!(
+                "Evidence file name '{}' is empty or reserved", file.name
+            )));
+        }
+        if !seen.insert(file.name.as_str()) {
+            return Err(VceError::InputSerialization(format!(
+                "Evidence file name '{}' is used more than once", file.name
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// System data document formed by an evidence bundle: each file parsed as
+/// JSON under its name
+pub fn bundle_document(files: &[EvidenceFile]) -> Result<serde_json::Value> {
+    validate_files(files)?;
+    let mut document = serde_json::Map::new();
+    for file in files {
+        let value = serde_json::from_slice(&file.bytes).map_err(|e| {
+            VceError::InputSerialization(format!("Evidence file '{}' is not valid JSON: {e}", file.name))
+        })?;
+        document.insert(file.name.clone(), value);
+    }
+    Ok(serde_json::Value::Object(document))
+}
+
+/// Load an evidence bundle from a directory, a `.tar` archive, or a JSON
+/// manifest mapping names to file paths (relative to the manifest)
+///
+/// Files in a directory or archive are named by their file stem, so
+/// `iam.json` becomes `iam`. Directories are not searched recursively.
+pub fn load_bundle(path: &Path) -> Result<Vec<EvidenceFile>> {
+    let mut files = Vec::new();
+    if path.is_dir() {
+        let mut entries = std::fs::read_dir(path)?.collect::<std::io::Result<Vec<_>>>()?;
+        entries.sort_by_key(std::fs::DirEntry::file_name);
+        for entry in entries {
+            if entry.file_type()?.is_file() {
+                files.push(EvidenceFile { name: file_stem(&entry.path()), bytes: std::fs::read(entry.path())? });
+            }
+        }
+    } else if path.extension().and_then(|ext| ext.to_str()) == Some("tar") {
+        let mut archive = tar::Archive::new(std::fs::File::open(path)?);
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            if !entry.header().entry_type().is_file() {
+                continue;
+            }
+            let name = file_stem(&entry.path()?);
+            let mut bytes = Vec::new();
+            entry.read_to_end(&mut bytes)?;
+            files.push(EvidenceFile { name, bytes });
+        }
+        files.sort_by(|a, b| a.name.cmp(&b.name));
+    } else {
+        let manifest: std::collections::BTreeMap<String, String> =
+            serde_json::from_str(&std::fs::read_to_string(path)?)?;
+        let base = path.parent().unwrap_or_else(|| Path::new("."));
+        for (name, file) in manifest {
+            files.push(EvidenceFile { name, bytes: std::fs::read(base.join(file))? });
+        }
+    }
+    validate_files(&files)?;
+    Ok(files)
+}
+
+/// Fail unless the journal commits to every piece of evidence in `inputs`
+///
+/// A receipt from an untrusted source (a remote prover, the proof cache)
+/// verifies whatever inputs it was proved over; this ties it to these.
+pub(crate) fn check_journal(journal: &JournalOutput, inputs: &ProofInputs<'_>) -> Result<()> {
+    let committed = || journal.evidence.iter().chain(journal.batch.iter().flat_map(|entry| &entry.evidence));
+    for (name, bytes) in expected(inputs) {
+        if !committed().any(|commitment| commitment.name == name && commitment.matches(&bytes)) {
+            return Err(VceError::ProofVerificationFailed(format!("Receipt does not commit to the '{name}' it was meant to prove")));
+        }
+    }
+    Ok(())
+}
+
+/// Name and bytes of each piece of evidence the guest commits for `inputs`
+fn expected<'a>(inputs: &ProofInputs<'a>) -> Vec<(&'a str, Cow<'a, [u8]>)> {
+    let named = |name: &'a str, bytes: &'a [u8]| (name, Cow::Borrowed(bytes));
+    let documents = |documents: &'a [EvidenceFile]| documents.iter().map(move |file| named(&file.name, &file.bytes));
+    let mut expected: Vec<_> = match inputs.mode {
+        ProofMode::Single { spec_json, system_data_json, .. } => {
+            vec![named(SPEC, spec_json.as_bytes()), named(SYSTEM_DATA, system_data_json.as_bytes())]
+        }
+        // A bundle replaces the system data commitment with one per document
+        ProofMode::Bundle { spec_json, documents: bundle } => {
+            std::iter::once(named(SPEC, spec_json.as_bytes())).chain(documents(bundle)).collect()
+        }
+        // Typed input is committed as its canonical JSON form
+        ProofMode::Typed { spec_json, system_data } => {
+            vec![named(SPEC, spec_json.as_bytes()), (SYSTEM_DATA, Cow::Owned(system_data.canonical_json().into_bytes()))]
+        }
+        ProofMode::Batch { spec_jsons, system_data } => {
+            let specs = spec_jsons.iter().map(|spec_json| named(SPEC, spec_json.as_bytes()));
+            match system_data {
+                BatchData::Json(system_data_json) => specs.chain([named(SYSTEM_DATA, system_data_json.as_bytes())]).collect(),
+                BatchData::Bundle(bundle) => specs.chain(documents(bundle)).collect(),
+            }
+        }
+        ProofMode::Stream { spec_json, records } | ProofMode::Incremental { spec_json, records, .. } => {
+            vec![named(SPEC, spec_json.as_bytes()), named(SYSTEM_DATA, records)]
+        }
+        // An aggregate commits its spec; its children are bound as assumptions
+        ProofMode::Aggregate { spec_json, .. } => return vec![named(SPEC, spec_json.as_bytes())],
+    };
+    expected.extend(documents(inputs.evidence_files));
+    expected
+}
+
+fn file_stem(path: &Path) -> String {
+    path.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::typed::TypedSystemData;
+
+    fn file(name: &str) -> EvidenceFile {
+        EvidenceFile { name: name.to_string(), bytes: b"evidence".to_vec() }
+    }
+
+    fn names(inputs: &ProofInputs<'_>) -> Vec<String> {
+        expected(inputs).into_iter().map(|(name, _)| name.to_string()).collect()
+    }
+
+    #[test]
+    fn test_commitment_binds_name_and_bytes() {
+        let commitment = EvidenceCommitment { name: SYSTEM_DATA.to_string(), digest: digest(SYSTEM_DATA, b"{}").to_vec() };
+        assert!(commitment.matches(b"{}"));
+        assert!(!commitment.matches(b"{ }"));
+        // The same bytes under another name commit differently
+        assert_ne!(digest(SPEC, b"{}"), digest(SYSTEM_DATA, b"{}"));
+    }
+
+    #[test]
+    fn test_validate_files_rejects_reserved_and_duplicate_names() {
+        assert!(validate_files(&[file("photo.jpg"), file("iam.json")]).is_ok());
+        assert!(validate_files(&[file(SPEC)]).is_err());
+        assert!(validate_files(&[file("")]).is_err());
+        assert!(validate_files(&[file("a"), file("a")]).is_err());
+    }
+
+    #[test]
+    fn test_bundle_document_keys_files_by_name() {
+        let files = [
+            EvidenceFile { name: "iam".to_string(), bytes: br#"{"users": []}"#.to_vec() },
+            EvidenceFile { name: "access_logs".to_string(), bytes: b"[1, 2]".to_vec() },
+        ];
+        let document = bundle_document(&files).unwrap();
+        assert_eq!(document, serde_json::json!({"iam": {"users": []}, "access_logs": [1, 2]}));
+
+        let invalid = [EvidenceFile { name: "notes".to_string(), bytes: b"not json".to_vec() }];
+        assert!(bundle_document(&invalid).is_err());
+    }
+
+    #[test]
+    fn test_load_bundle_from_manifest() {
+        let dir = std::env::temp_dir().join(format!("fuse-bundle-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("iam-export.json"), r#"{"users": []}"#).unwrap();
+        std::fs::write(dir.join("manifest.json"), r#"{"iam": "iam-export.json"}"#).unwrap();
+
+        let files = load_bundle(&dir.join("manifest.json")).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].name, "iam");
+        assert_eq!(files[0].bytes, br#"{"users": []}"#);
+    }
+
+    #[test]
+    fn test_expected_evidence_follows_guest_commitments() {
+        let files = [EvidenceFile { name: "report.pdf".to_string(), bytes: b"%PDF".to_vec() }];
+        let bundle = [EvidenceFile { name: "config".to_string(), bytes: b"{}".to_vec() }];
+        let single = ProofInputs { evidence_files: &files, ..ProofInputs::new("{}", r#"{"a": 1}"#) };
+        assert_eq!(names(&single), ["spec", "system_data", "report.pdf"]);
+
+        // A bundle replaces the system data commitment with one per document
+        let bundled = ProofInputs::with_mode(ProofMode::Bundle { spec_json: "{}", documents: &bundle });
+        assert_eq!(names(&bundled), ["spec", "config"]);
+
+        let specs = ["{\"a\": 1}".to_string(), "{\"b\": 2}".to_string()];
+        let batch = ProofInputs::with_mode(ProofMode::Batch { spec_jsons: &specs, system_data: BatchData::Json("{}") });
+        assert_eq!(names(&batch), ["spec", "spec", "system_data"]);
+        let batch = ProofInputs::with_mode(ProofMode::Batch { spec_jsons: &specs, system_data: BatchData::Bundle(&bundle) });
+        assert_eq!(names(&batch), ["spec", "spec", "config"]);
+
+        let typed = TypedSystemData::from_json(&serde_json::json!({"b": true, "a": 1}));
+        let typed_inputs = ProofInputs::with_mode(ProofMode::Typed { spec_json: "{}", system_data: &typed });
+        assert_eq!(expected(&typed_inputs)[1].1.as_ref(), typed.canonical_json().as_bytes());
+
+        let children = [Vec::new()];
+        let aggregate = ProofInputs { evidence_files: &files, ..ProofInputs::with_mode(ProofMode::Aggregate { spec_json: "{}", child_receipts: &children }) };
+        assert_eq!(names(&aggregate), ["spec"]);
+    }
+}
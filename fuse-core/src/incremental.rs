@@ -0,0 +1,86 @@
+//! Incremental proofs over append-only logs
+//!
+//! Instead of re-proving a whole year of access logs every week, each
+//! period streams only the records appended since the last one and passes
+//! the previous period's receipt. The guest verifies it through RISC Zero
+//! composition, resumes the checker's counters from its journal and tallies
+//! only the new records, so each envelope attests to the cumulative log
+//! while proving only the delta.
+//!
+//! The cumulative log is committed as a hash chain over each period's
+//! `system_data` evidence digest, so an auditor holding every period's JSON
+//! Lines file recomputes it with [`log_root`]. The previous period is
+//! committed like an aggregated child; it must be an incremental proof of
+//! the same spec and private parameters that did not error.
+//!
+//! Mirrors the guest program's `incremental` module; types, field order and
+//! the chain hashing must match.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use crate::evidence;
+use crate::stream::TallyState;
+
+const LOG_DOMAIN: &[u8] = b"fuse.log.v1";
+
+/// The previous period's proof, passed to the guest; no previous journal
+/// for the first period
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Incremental {
+    pub image_id: [u32; 8],
+    pub previous_journal: Vec<u8>,
+}
+
+/// State carried from one period to the next, committed to the journal
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LogState {
+    // Hash chain over every period's system data digest; empty unless incremental
+    pub root: Vec<u8>,
+    pub periods: u64,
+    pub tally: TallyState,
+}
+
+impl LogState {
+    /// Whether this is an incremental proof's state
+    #[must_use]
+    pub fn is_incremental(&self) -> bool {
+        !self.root.is_empty()
+    }
+}
+
+/// Next chain root after a period whose records have the given
+/// `system_data` evidence digest
+#[must_use]
+pub fn chain(root: &[u8], digest: &[u8]) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    hasher.update(LOG_DOMAIN);
+    hasher.update((root.len() as u64).to_be_bytes());
+    hasher.update(root);
+    hasher.update(digest);
+    hasher.finalize().to_vec()
+}
+
+/// Root committed after the given periods' JSON Lines files, in order
+#[must_use]
+pub fn log_root<'a>(periods: impl IntoIterator<Item = &'a [u8]>) -> Vec<u8> {
+    periods
+        .into_iter()
+        .fold(Vec::new(), |root, records| chain(&root, &evidence::digest(evidence::SYSTEM_DATA, records)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_root_chains_periods_in_order() {
+        let first: &[u8] = b"{\"user\": \"alice\"}\n";
+        let second: &[u8] = b"{\"user\": \"bob\"}\n";
+        let root = log_root([first, second]);
+        assert_eq!(root, chain(&log_root([first]), &evidence::digest(evidence::SYSTEM_DATA, second)));
+        assert_ne!(root, log_root([second, first]));
+        // Splitting the log differently is a different history
+        assert_ne!(root, log_root([[first, second].concat().as_slice()]));
+        assert!(log_root([]).is_empty());
+    }
+}
@@ -8,10 +8,31 @@ pub mod envelope;
 pub mod proof;
 pub mod error;
 pub mod zkvm;
+pub mod parallel;
+pub mod progress;
+pub mod score;
+pub mod composite;
+pub mod disclosure;
+pub mod evidence;
+pub mod merkle;
+pub mod private_params;
+pub mod stream;
+pub mod typed;
+pub mod aggregate;
+pub mod incremental;
+pub mod chain;
+pub mod cache;
+pub mod registry;
+#[cfg(feature = "async")]
+pub mod pool;
+#[cfg(feature = "remote")]
+pub mod remote;
 
 pub use spec::ComplianceSpec;
 pub use envelope::VerifiableComplianceEnvelope;
-pub use proof::{ComplianceProof, ComplianceResult, JournalOutput};
+pub use proof::{ComplianceProof, ComplianceResult, ErrorCode, JournalOutput};
 pub use error::{VceError, Result};
 pub use zkvm::ProverType;
+pub use score::Score;
+pub use composite::{ClaimNode, NodeResult};
 
@@ -0,0 +1,296 @@
+//! Salted per-field commitments
+//!
+//! Mirrors the guest program's field tree. Every scalar in the system data
+//! (and every empty array or object) becomes a leaf keyed by its JSON
+//! Pointer. Leaves are sorted by pointer and salted with a value derived
+//! from a private 32-byte seed; the guest commits only the root. Holding
+//! the seed, the prover can later open individual fields to an auditor
+//! with an inclusion proof against the root in an existing envelope.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+const SALT_DOMAIN: &[u8] = b"fuse.merkle.salt.v1";
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+/// A field opened against a committed root
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldOpening {
+    /// JSON Pointer of the field in the system data
+    pub path: String,
+    /// The field's value
+    pub value: Value,
+    /// Hex-encoded salt for this field only
+    pub salt: String,
+    /// Position of the leaf in pointer order
+    pub index: usize,
+    /// Total number of leaves in the tree
+    pub leaf_count: usize,
+    /// Hex-encoded sibling hashes from the leaf up to the root
+    pub siblings: Vec<String>,
+}
+
+/// Fields revealed to an auditor, bound to one envelope
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldReveal {
+    /// Hash of the spec in the envelope the fields were committed under
+    pub spec_hash: String,
+    /// Hex-encoded committed root
+    pub fields_root: String,
+    pub openings: Vec<FieldOpening>,
+}
+
+impl FieldReveal {
+    /// Check every opening against the root committed in a verified journal
+    pub fn verify(&self, spec_hash: &str, fields_root: &[u8]) -> crate::Result<()> {
+        if self.spec_hash != spec_hash {
+            return Err(crate::VceError::ProofVerificationFailed(
+                "Reveal was produced for a different spec".to_string(),
+            ));
+        }
+        if fields_root.is_empty() {
+            return Err(crate::VceError::ProofVerificationFailed(
+                "Envelope does not commit to its fields (spec did not set commit_fields)".to_string(),
+            ));
+        }
+        if hex::decode(&self.fields_root).ok().as_deref() != Some(fields_root) {
+            return Err(crate::VceError::ProofVerificationFailed(
+                "Reveal root does not match the envelope's committed root".to_string(),
+            ));
+        }
+        self.openings.iter().try_for_each(|opening| opening.verify(fields_root))
+    }
+}
+
+/// Salted field tree over a system data document
+pub struct FieldTree {
+    leaves: Vec<(String, Value, [u8; 32])>,
+    levels: Vec<Vec<[u8; 32]>>,
+}
+
+impl FieldTree {
+    /// Build the tree the guest commits to for `document` and `seed`
+    #[must_use]
+    pub fn new(document: &Value, seed: &[u8; 32]) -> Self {
+        let mut leaves = Vec::new();
+        collect_leaves(document, &mut String::new(), &mut leaves);
+        leaves.sort_by(|a, b| a.0.as_bytes().cmp(b.0.as_bytes()));
+
+        let leaves: Vec<(String, Value, [u8; 32])> = leaves
+            .into_iter()
+            .map(|(path, value)| {
+                let salt = salt(seed, &path);
+                (path, value.clone(), salt)
+            })
+            .collect();
+
+        let mut levels = vec![leaves.iter().map(|(path, value, salt)| leaf_hash(salt, path, value)).collect::<Vec<_>>()];
+        while levels.last().is_some_and(|level| level.len() > 1) {
+            let next = levels.last().map(|level| next_level(level)).unwrap_or_default();
+            levels.push(next);
+        }
+
+        Self { leaves, levels }
+    }
+
+    /// Committed root; all zeros for an empty document
+    #[must_use]
+    pub fn root(&self) -> [u8; 32] {
+        self.levels.last().and_then(|level| level.first()).copied().unwrap_or([0; 32])
+    }
+
+    /// Open every leaf at or below `path` (a JSON Pointer, "" for all)
+    pub fn open(&self, path: &str) -> crate::Result<Vec<FieldOpening>> {
+        let openings: Vec<FieldOpening> = self
+            .leaves
+            .iter()
+            .enumerate()
+            .filter(|(_, (leaf_path, _, _))| {
+                leaf_path == path || (leaf_path.starts_with(path) && leaf_path.as_bytes().get(path.len()) == Some(&b'/'))
+            })
+            .map(|(index, (leaf_path, value, salt))| FieldOpening {
+                path: leaf_path.clone(),
+                value: value.clone(),
+                salt: hex::encode(salt),
+                index,
+                leaf_count: self.leaves.len(),
+                siblings: self.siblings(index).iter().map(hex::encode).collect(),
+            })
+            .collect();
+
+        if openings.is_empty() {
+            return Err(crate::VceError::InvalidSpec(format!("No field at '{path}' in the system data")));
+        }
+        Ok(openings)
+    }
+
+    fn siblings(&self, mut index: usize) -> Vec<[u8; 32]> {
+        let mut siblings = Vec::new();
+        for level in &self.levels[..self.levels.len() - 1] {
+            let sibling = index ^ 1;
+            if sibling < level.len() {
+                siblings.push(level[sibling]);
+            }
+            index /= 2;
+        }
+        siblings
+    }
+}
+
+impl FieldOpening {
+    /// Check this opening against a committed root
+    pub fn verify(&self, root: &[u8]) -> crate::Result<()> {
+        let invalid = |reason: &str| {
+            crate::VceError::ProofVerificationFailed(format!("Field '{}' does not open: {reason}", self.path))
+        };
+
+        let salt: [u8; 32] = hex::decode(&self.salt)
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| invalid("salt must be 32 hex-encoded bytes"))?;
+        if self.index >= self.leaf_count {
+            return Err(invalid("leaf index out of range"));
+        }
+
+        let mut hash = leaf_hash(&salt, &self.path, &self.value);
+        let mut siblings = self.siblings.iter();
+        let (mut index, mut width) = (self.index, self.leaf_count);
+        while width > 1 {
+            let has_sibling = (index ^ 1) < width;
+            if has_sibling {
+                let sibling: [u8; 32] = siblings
+                    .next()
+                    .and_then(|s| hex::decode(s).ok())
+                    .and_then(|bytes| bytes.try_into().ok())
+                    .ok_or_else(|| invalid("missing or malformed sibling hash"))?;
+                hash = if index % 2 == 0 { node_hash(&hash, &sibling) } else { node_hash(&sibling, &hash) };
+            }
+            index /= 2;
+            width = width.div_ceil(2);
+        }
+
+        if siblings.next().is_some() {
+            return Err(invalid("too many sibling hashes"));
+        }
+        if hash.as_slice() != root {
+            return Err(invalid("root mismatch"));
+        }
+        Ok(())
+    }
+}
+
+/// Collect `(pointer, value)` pairs for every leaf under `value`
+fn collect_leaves<'a>(value: &'a Value, path: &mut String, leaves: &mut Vec<(String, &'a Value)>) {
+    let len = path.len();
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (key, child) in map {
+                path.push('/');
+                path.push_str(&key.replace('~', "~0").replace('/', "~1"));
+                collect_leaves(child, path, leaves);
+                path.truncate(len);
+            }
+        }
+        Value::Array(items) if !items.is_empty() => {
+            for (index, child) in items.iter().enumerate() {
+                path.push('/');
+                path.push_str(&index.to_string());
+                collect_leaves(child, path, leaves);
+                path.truncate(len);
+            }
+        }
+        _ => leaves.push((path.clone(), value)),
+    }
+}
+
+/// Per-field salt derived from the private seed
+fn salt(seed: &[u8; 32], path: &str) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(SALT_DOMAIN);
+    hasher.update(seed);
+    hasher.update(path.as_bytes());
+    hasher.finalize().into()
+}
+
+fn leaf_hash(salt: &[u8; 32], path: &str, value: &Value) -> [u8; 32] {
+    let value = serde_json::to_string(value).unwrap_or_default();
+    let mut hasher = Sha256::new();
+    hasher.update([LEAF_PREFIX]);
+    hasher.update(salt);
+    hasher.update((path.len() as u64).to_be_bytes());
+    hasher.update(path.as_bytes());
+    hasher.update(value.as_bytes());
+    hasher.finalize().into()
+}
+
+/// Pair up nodes; an unpaired last node is promoted unchanged
+fn next_level(level: &[[u8; 32]]) -> Vec<[u8; 32]> {
+    level
+        .chunks(2)
+        .map(|pair| match pair {
+            [left, right] => node_hash(left, right),
+            [single] => *single,
+            _ => unreachable!("chunks(2) yields one or two items"),
+        })
+        .collect()
+}
+
+fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([NODE_PREFIX]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn document() -> Value {
+        json!({
+            "claim": {"issuer": "CA", "a/b": true, "tags": []},
+            "storage_locations": [{"region": "EU"}, {"region": "US"}, {"region": "EU"}],
+            "count": 3
+        })
+    }
+
+    #[test]
+    fn test_open_and_verify_every_leaf() {
+        let tree = FieldTree::new(&document(), &[7; 32]);
+        let root = tree.root();
+        let openings = tree.open("").unwrap();
+        assert_eq!(openings.len(), 7);
+        for opening in &openings {
+            opening.verify(&root).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_open_subtree_and_escaped_keys() {
+        let tree = FieldTree::new(&document(), &[7; 32]);
+        let openings = tree.open("/storage_locations").unwrap();
+        assert_eq!(openings.len(), 3);
+        assert_eq!(tree.open("/claim/a~1b").unwrap()[0].value, json!(true));
+        assert!(tree.open("/claim/missing").is_err());
+        // A prefix that is not a path boundary does not match
+        assert!(tree.open("/storage").is_err());
+    }
+
+    #[test]
+    fn test_tampered_opening_rejected() {
+        let tree = FieldTree::new(&document(), &[7; 32]);
+        let root = tree.root();
+        let mut opening = tree.open("/claim/issuer").unwrap().remove(0);
+        opening.value = json!("Other CA");
+        assert!(opening.verify(&root).is_err());
+    }
+
+    #[test]
+    fn test_seed_changes_root() {
+        assert_ne!(FieldTree::new(&document(), &[1; 32]).root(), FieldTree::new(&document(), &[2; 32]).root());
+    }
+}
@@ -0,0 +1,244 @@
+//! Parallel segment proving
+//!
+//! `ProverServer::prove_session` proves a session's segments one after
+//! another, which is why CPU proofs take 10-20+ minutes. Segments are
+//! independent, so with more than one worker each worker thread proves
+//! segments with its own prover (provers are not `Send`), and the segment
+//! receipts are lifted and joined pairwise into one succinct receipt, again
+//! spread over the workers. Assumptions (aggregated children, previous
+//! periods) are then resolved as `ProverServer::compress` would, and the
+//! receipt's claim is checked against the session's, as `prove_session`
+//! does. Proving segment by segment is what lets each segment be reported
+//! to the proof's observer and cancellation be checked between segments
+//! (see [`crate::progress`]).
+//!
+//! With one worker, lifting and joining would only add recursion work, so
+//! the session goes to `prove_session` itself and comes back as a
+//! composite receipt, as it always did. So does a session with unresolved
+//! assumptions (accelerator calls such as keccak, whose proof requests only
+//! risc0 can see), whatever the worker count. On that path segments are
+//! reported only once all of them are proved, and cancellation takes
+//! effect only before proving starts or after it ends.
+//!
+//! Each worker holds a segment's proving memory (several GiB at the default
+//! segment size), so choose the worker count for the machine's memory as
+//! well as its cores; smaller segments (`SessionLimits::segment_po2`) trade
+//! per-segment memory for more recursion work.
+
+use std::sync::Mutex;
+use risc0_zkvm::sha::Digestible;
+use risc0_zkvm::{
+    AssumptionReceipt, InnerAssumptionReceipt, InnerReceipt, MaybePruned, Output, Receipt, ReceiptClaim, SegmentReceipt, Session, SuccinctReceipt, VerifierContext,
+};
+use crate::error::{Result, VceError};
+use crate::progress::{ProofPhase, Progress};
+use crate::zkvm::{self, ProverType};
+
+/// Run `task` over every item on up to `workers` threads, each with its
+/// own state from `init`, returning the results in input order
+///
+/// Stops at the first error; items not yet started are skipped.
+pub(crate) fn on_workers<S, T, R>(
+    items: Vec<T>,
+    workers: usize,
+    init: impl Fn() -> Result<S> + Sync,
+    task: impl Fn(&S, T) -> Result<R> + Sync,
+) -> Result<Vec<R>>
+where
+    T: Send,
+    R: Send,
+{
+    let count = items.len();
+    let queue = Mutex::new(items.into_iter().enumerate());
+    let results = Mutex::new((0..count).map(|_| None).collect::<Vec<Option<R>>>());
+    let failure = Mutex::new(None);
+
+    std::thread::scope(|scope| {
+        for _ in 0..workers.clamp(1, count.max(1)) {
+            scope.spawn(|| {
+                let state = match init() {
+                    Ok(state) => state,
+                    Err(e) => {
+                        failure.lock().unwrap_or_else(std::sync::PoisonError::into_inner).get_or_insert(e);
+                        return;
+                    }
+                };
+                loop {
+                    if failure.lock().unwrap_or_else(std::sync::PoisonError::into_inner).is_some() {
+                        return;
+                    }
+                    let next = queue.lock().unwrap_or_else(std::sync::PoisonError::into_inner).next();
+                    let Some((index, item)) = next else { return };
+                    match task(&state, item) {
+                        Ok(result) => results.lock().unwrap_or_else(std::sync::PoisonError::into_inner)[index] = Some(result),
+                        Err(e) => {
+                            failure.lock().unwrap_or_else(std::sync::PoisonError::into_inner).get_or_insert(e);
+                            return;
+                        }
+                    }
+                }
+            });
+        }
+    });
+
+    if let Some(e) = failure.into_inner().unwrap_or_else(std::sync::PoisonError::into_inner) {
+        return Err(e);
+    }
+    Ok(results
+        .into_inner()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .into_iter()
+        .flatten()
+        .collect())
+}
+
+/// Prove the session's segments on `workers` threads and combine them into
+/// one succinct receipt; with one worker, prove it with `prove_session`
+pub(crate) fn prove_session(session: &mut Session, prover_type: &ProverType, workers: usize, progress: &Progress) -> Result<Receipt> {
+    if workers <= 1 || session.assumptions.iter().any(|(_, receipt)| matches!(receipt, AssumptionReceipt::Unresolved(_))) {
+        return prove_serially(session, prover_type, progress);
+    }
+
+    // Taken before the segments are, as `prove_session` computes it
+    let session_claim = session.claim().map_err(failed("compute the session claim"))?;
+    let receipt = prove_in_parallel(session, prover_type, workers, progress)?;
+
+    receipt.verify_integrity_with_context(&VerifierContext::default())
+        .map_err(|e| VceError::ProofGenerationFailed(format!("Segment receipts do not verify: {e}")))?;
+    let claim = receipt.claim().map_err(|e| VceError::ProofGenerationFailed(format!("Receipt has no claim: {e}")))?;
+    if claim.digest() != session_claim.digest() {
+        return Err(VceError::ProofGenerationFailed(format!(
+            "Receipt claim {} does not match the session claim {}",
+            hex::encode(claim.digest()),
+            hex::encode(session_claim.digest())
+        )));
+    }
+    Ok(receipt)
+}
+
+/// Prove the session with risc0's own `prove_session`, which also proves
+/// its pending accelerator requests and checks the receipt's claim
+///
+/// risc0 proves the segments internally, so they are reported together
+/// once it returns, and a cancellation during the call is seen only after.
+fn prove_serially(session: &Session, prover_type: &ProverType, progress: &Progress) -> Result<Receipt> {
+    progress.check()?;
+    let prover = zkvm::get_prover_for_type(prover_type)?;
+    let receipt = prover.prove_session(&VerifierContext::default(), session)
+        .map_err(|e| VceError::ProofGenerationFailed(format!("RISC Zero proof generation failed: {e}. This step can take 10-20+ minutes for real proofs. For testing, use RISC0_DEV_MODE=1.")))?
+        .receipt;
+    let total = session.segments.len();
+    for index in 0..total {
+        progress.segment_proved(index, total);
+    }
+    progress.check()?;
+    Ok(receipt)
+}
+
+fn prove_in_parallel(session: &mut Session, prover_type: &ProverType, workers: usize, progress: &Progress) -> Result<Receipt> {
+    // Neither provers nor verifier contexts can be shared between threads
+    let segments: Vec<_> = std::mem::take(&mut session.segments).into_iter().enumerate().collect();
+    let total = segments.len();
+    let worker = || Ok((zkvm::get_prover_for_type(prover_type)?, VerifierContext::default()));
+    let mut receipts = on_workers(segments, workers, worker, |(prover, ctx), (index, segment)| {
+        progress.check()?;
+        let segment = segment.resolve().map_err(failed("load segment"))?;
+        let receipt = prover.prove_segment(ctx, &segment).map_err(failed("prove segment"))?;
+        progress.segment_proved(index, total);
+        Ok(receipt)
+    })?;
+
+    // The last segment's claim carries the journal, if the guest committed
+    // one, and the assumptions, as in `prove_session`
+    let (assumptions, assumption_receipts): (Vec<_>, Vec<_>) = session.assumptions.iter().cloned().unzip();
+    let last: &mut SegmentReceipt = receipts
+        .last_mut()
+        .ok_or_else(|| VceError::ProofGenerationFailed("Session has no segments".to_string()))?;
+    last.claim.output = session
+        .journal
+        .as_ref()
+        .map(|journal| Output { journal: MaybePruned::Pruned(journal.digest()), assumptions: assumptions.into() })
+        .into();
+
+    progress.check()?;
+    progress.report(ProofPhase::Joining);
+    let journal = session.journal.clone().unwrap_or_default();
+    join(receipts, assumption_receipts, journal.bytes, prover_type, workers, progress)
+}
+
+/// Lift and join segment receipts on the workers and resolve the
+/// assumptions, into a succinct receipt
+fn join(
+    receipts: Vec<SegmentReceipt>,
+    assumption_receipts: Vec<AssumptionReceipt>,
+    journal: Vec<u8>,
+    prover_type: &ProverType,
+    workers: usize,
+    progress: &Progress,
+) -> Result<Receipt> {
+    let mut joined = on_workers(receipts, workers, || zkvm::get_prover_for_type(prover_type), |prover, receipt| {
+        prover.lift(&receipt).map_err(failed("lift segment receipt"))
+    })?;
+    // Join neighbours level by level, so the work at each level spreads
+    // over the workers
+    while joined.len() > 1 {
+        let mut pairs = Vec::with_capacity(joined.len().div_ceil(2));
+        let mut level = joined.into_iter();
+        while let Some(left) = level.next() {
+            pairs.push((left, level.next()));
+        }
+        joined = on_workers(pairs, workers, || zkvm::get_prover_for_type(prover_type), |prover, (left, right): (SuccinctReceipt<ReceiptClaim>, Option<_>)| {
+            progress.check()?;
+            match right {
+                Some(right) => prover.join(&left, &right).map_err(failed("join segment receipts")),
+                None => Ok(left),
+            }
+        })?;
+    }
+    let mut succinct = joined.pop().ok_or_else(|| VceError::ProofGenerationFailed("Session has no segments".to_string()))?;
+
+    let prover = zkvm::get_prover_for_type(prover_type)?;
+    for assumption in assumption_receipts {
+        progress.check()?;
+        succinct = match assumption {
+            AssumptionReceipt::Proven(InnerAssumptionReceipt::Succinct(assumption)) => prover.resolve(&succinct, &assumption),
+            AssumptionReceipt::Proven(InnerAssumptionReceipt::Composite(assumption)) => prover
+                .composite_to_succinct(&assumption)
+                .and_then(|assumption| prover.resolve(&succinct, &assumption.into_unknown())),
+            _ => Err(anyhow::anyhow!("only succinct or composite assumption receipts can be resolved")),
+        }
+        .map_err(failed("resolve assumption"))?;
+    }
+
+    Ok(Receipt::new(InnerReceipt::Succinct(succinct), journal))
+}
+
+fn failed(step: &'static str) -> impl Fn(anyhow::Error) -> VceError {
+    move |e| VceError::ProofGenerationFailed(format!("Failed to {step}: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_workers_keep_input_order() {
+        let squares = on_workers((0..50u64).collect(), 4, || Ok(()), |(), n| Ok(n * n)).unwrap();
+        assert_eq!(squares, (0..50u64).map(|n| n * n).collect::<Vec<_>>());
+        assert!(on_workers(Vec::<u64>::new(), 4, || Ok(()), |(), n| Ok(n)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_workers_stop_at_first_error() {
+        let result = on_workers((0..50u64).collect(), 3, || Ok(()), |(), n| {
+            if n == 7 {
+                Err(VceError::ProofGenerationFailed("segment 7".to_string()))
+            } else {
+                Ok(n)
+            }
+        });
+        assert!(matches!(result, Err(VceError::ProofGenerationFailed(message)) if message == "segment 7"));
+        let result = on_workers(vec![1u64], 2, || Err::<(), _>(VceError::ProofGenerationFailed("no prover".to_string())), |(), n| Ok(n));
+        assert!(result.is_err());
+    }
+}
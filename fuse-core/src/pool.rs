@@ -0,0 +1,229 @@
+//! Async proving for services
+//!
+//! [`zkvm::generate_proof_with_inputs`] blocks for minutes, which would tie
+//! up an async runtime's thread. A [`ProverPool`] runs proofs on its own
+//! threads, at most as many at once as it has threads since each proof
+//! saturates every core, and queues the rest. [`ProverPool::submit`]
+//! returns a [`ProofHandle`] right away to poll for status, cancel, or
+//! await the result. Needs no particular runtime; enable the `async`
+//! feature.
+
+use std::collections::VecDeque;
+use std::panic::AssertUnwindSafe;
+use std::sync::{Arc, Condvar, Mutex, PoisonError};
+use tokio::sync::oneshot;
+use crate::error::{Result, VceError};
+use crate::progress::{CancellationToken, ProofObserver, ProofPhase};
+use crate::proof::JournalOutput;
+use crate::zkvm::{self, OwnedProofInputs, ProofOptions};
+
+/// Serialized receipt, journal output and journal bytes, as returned by
+/// [`zkvm::generate_proof_with_inputs`]
+type Proof = (Vec<u8>, JournalOutput, Vec<u8>);
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// Where a submitted proof is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProofStatus {
+    /// Waiting for a free prover thread
+    Queued,
+    /// Proving; the latest phase reported
+    Running(ProofPhase),
+    /// The proof is ready to collect
+    Done,
+    /// Proving failed or was cancelled
+    Failed,
+}
+
+/// A submitted proof
+#[derive(Debug)]
+pub struct ProofHandle {
+    status: Arc<Mutex<ProofStatus>>,
+    cancellation: CancellationToken,
+    result: oneshot::Receiver<Result<Proof>>,
+}
+
+impl ProofHandle {
+    #[must_use]
+    pub fn status(&self) -> ProofStatus {
+        *self.status.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Stop the proof at its next segment boundary, or before it starts if
+    /// still queued; dropping the handle does not
+    pub fn cancel(&self) {
+        self.cancellation.cancel();
+    }
+
+    /// Take the result without waiting, for callers outside an async
+    /// runtime; `None` while the proof is queued or running
+    pub fn try_result(&mut self) -> Option<Result<Proof>> {
+        match self.result.try_recv() {
+            Ok(proof) => Some(proof),
+            Err(oneshot::error::TryRecvError::Empty) => None,
+            Err(oneshot::error::TryRecvError::Closed) => {
+                Some(Err(VceError::ProofGenerationFailed("Prover thread stopped before finishing the proof".to_string())))
+            }
+        }
+    }
+
+    /// Wait for the proof
+    pub async fn wait(self) -> Result<Proof> {
+        self.result
+            .await
+            .unwrap_or_else(|_| Err(VceError::ProofGenerationFailed("Prover thread stopped before finishing the proof".to_string())))
+    }
+}
+
+/// Jobs waiting for a prover thread
+#[derive(Default)]
+struct Queue {
+    jobs: Mutex<(VecDeque<Job>, bool)>,
+    ready: Condvar,
+}
+
+/// Dedicated threads that prove submitted inputs, a bounded number at once
+///
+/// Dropping the pool does not wait: its threads finish the queued proofs
+/// in the background, then exit.
+pub struct ProverPool {
+    queue: Arc<Queue>,
+}
+
+impl std::fmt::Debug for ProverPool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let queued = self.queue.jobs.lock().unwrap_or_else(PoisonError::into_inner).0.len();
+        f.debug_struct("ProverPool").field("queued", &queued).finish()
+    }
+}
+
+impl ProverPool {
+    /// Pool proving at most `max_concurrent` proofs at once (at least one)
+    pub fn new(max_concurrent: usize) -> Result<Self> {
+        let queue = Arc::new(Queue::default());
+        for index in 0..max_concurrent.max(1) {
+            let queue = Arc::clone(&queue);
+            std::thread::Builder::new()
+                .name(format!("fuse-prover-{index}"))
+                .spawn(move || work(&queue))?;
+        }
+        Ok(Self { queue })
+    }
+
+    /// Queue a proof of `inputs`
+    ///
+    /// The handle's status follows the proof's phases, and any observer or
+    /// cancellation token in `options` keeps working alongside it.
+    #[must_use]
+    pub fn submit(&self, inputs: OwnedProofInputs, options: impl Into<ProofOptions>) -> ProofHandle {
+        self.run(options.into(), move |options| zkvm::generate_proof_with_inputs(&inputs.as_inputs(), options))
+    }
+
+    fn run(&self, mut options: ProofOptions, prove: impl FnOnce(ProofOptions) -> Result<Proof> + Send + 'static) -> ProofHandle {
+        let status = Arc::new(Mutex::new(ProofStatus::Queued));
+        let cancellation = options.cancellation.get_or_insert_with(CancellationToken::new).clone();
+        let caller = options.observer.take();
+        let seen = Arc::clone(&status);
+        let observer = move |phase: ProofPhase| {
+            *seen.lock().unwrap_or_else(PoisonError::into_inner) = ProofStatus::Running(phase);
+            if let Some(caller) = &caller {
+                caller.on_phase(phase);
+            }
+        };
+        options.observer = Some(Arc::new(observer) as Arc<dyn ProofObserver>);
+
+        let (sender, result) = oneshot::channel();
+        let finished = Arc::clone(&status);
+        let job: Job = Box::new(move || {
+            let proof = std::panic::catch_unwind(AssertUnwindSafe(|| prove(options)))
+                .unwrap_or_else(|_| Err(VceError::ProofGenerationFailed("Proving panicked".to_string())));
+            *finished.lock().unwrap_or_else(PoisonError::into_inner) = if proof.is_ok() { ProofStatus::Done } else { ProofStatus::Failed };
+            // The handle may have been dropped; the proof is then discarded
+            let _ = sender.send(proof);
+        });
+
+        self.queue.jobs.lock().unwrap_or_else(PoisonError::into_inner).0.push_back(job);
+        self.queue.ready.notify_one();
+        ProofHandle { status, cancellation, result }
+    }
+}
+
+impl Drop for ProverPool {
+    fn drop(&mut self) {
+        self.queue.jobs.lock().unwrap_or_else(PoisonError::into_inner).1 = true;
+        self.queue.ready.notify_all();
+    }
+}
+
+/// Run queued jobs until the queue is empty and the pool dropped
+fn work(queue: &Queue) {
+    loop {
+        let job = {
+            let mut jobs = queue.jobs.lock().unwrap_or_else(PoisonError::into_inner);
+            loop {
+                match jobs.0.pop_front() {
+                    Some(job) => break job,
+                    None if jobs.1 => return,
+                    None => jobs = queue.ready.wait(jobs).unwrap_or_else(PoisonError::into_inner),
+                }
+            }
+        };
+        job();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::zkvm::{ProofInputs, ProverType};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Barrier;
+
+    #[test]
+    fn test_pool_bounds_concurrent_proofs() {
+        let pool = ProverPool::new(2).unwrap();
+        let running = Arc::new(AtomicUsize::new(0));
+        let most = Arc::new(AtomicUsize::new(0));
+        // Each proof waits for another to run alongside it
+        let together = Arc::new(Barrier::new(2));
+        let handles: Vec<_> = (0..6)
+            .map(|_| {
+                let (running, most, together) = (Arc::clone(&running), Arc::clone(&most), Arc::clone(&together));
+                pool.run(ProofOptions::new(ProverType::Local), move |_| {
+                    most.fetch_max(running.fetch_add(1, Ordering::SeqCst) + 1, Ordering::SeqCst);
+                    together.wait();
+                    running.fetch_sub(1, Ordering::SeqCst);
+                    Err(VceError::ProofGenerationFailed("no guest".to_string()))
+                })
+            })
+            .collect();
+        for handle in handles {
+            assert!(tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(handle.wait()).is_err());
+        }
+        assert_eq!(most.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_cancelled_handle_fails_before_proving() {
+        let pool = ProverPool::new(1).unwrap();
+        let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+        // Occupy the only thread so the next proof stays queued
+        let (release, blocked) = std::sync::mpsc::channel::<()>();
+        let busy = pool.run(ProofOptions::new(ProverType::Local), move |_| {
+            let _ = blocked.recv();
+            Err(VceError::Cancelled)
+        });
+
+        let inputs = OwnedProofInputs::from(&ProofInputs::new(r#"{"claim": "test"}"#, "{}"));
+        let handle = pool.submit(inputs, ProverType::Local);
+        assert_eq!(handle.status(), ProofStatus::Queued);
+        handle.cancel();
+        release.send(()).unwrap();
+
+        let status = Arc::clone(&handle.status);
+        assert!(matches!(runtime.block_on(handle.wait()), Err(VceError::Cancelled)));
+        assert_eq!(*status.lock().unwrap(), ProofStatus::Failed);
+        assert!(runtime.block_on(busy.wait()).is_err());
+    }
+}
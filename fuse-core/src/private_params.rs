@@ -0,0 +1,168 @@
+//! Private spec parameters committed by hash
+//!
+//! Confidential constraint values (internal risk thresholds, approved
+//! vendor lists) stay out of the public spec. The spec's
+//! `private_constraints` maps each name to a salted commitment; the values
+//! and salts travel to the guest as a separate private input, and the guest
+//! commits the verified commitments to the journal. A regulator holding
+//! the parameters file can confirm which policy was applied.
+//!
+//! Commitment: `SHA256("fuse.private-param.v1" || salt || u64_be(len(name)) || name || value)`.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use crate::error::{Result, VceError};
+use crate::spec::ComplianceSpec;
+
+const COMMITMENT_DOMAIN: &[u8] = b"fuse.private-param.v1";
+
+/// A private constraint value with its salt
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PrivateParam {
+    pub value: String,
+    /// Hex-encoded 32-byte salt
+    pub salt: String,
+}
+
+/// Private parameters by constraint name, as passed to the guest
+pub type PrivateParams = BTreeMap<String, PrivateParam>;
+
+/// A private parameter's commitment, committed to the journal
+///
+/// Mirrors the guest program's `ParamCommitment`; field order must match.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParamCommitment {
+    pub name: String,
+    pub commitment: Vec<u8>,
+}
+
+impl PrivateParam {
+    /// Commitment to this value under `name`
+    pub fn commitment(&self, name: &str) -> Result<[u8; 32]> {
+        let salt = hex::decode(&self.salt)
+            .map_err(|e| VceError::InputSerialization(format!("Salt for private parameter '{name}' is not hex: {e}")))?;
+        let mut hasher = Sha256::new();
+        hasher.update(COMMITMENT_DOMAIN);
+        hasher.update(&salt);
+        hasher.update((name.len() as u64).to_be_bytes());
+        hasher.update(name.as_bytes());
+        hasher.update(self.value.as_bytes());
+        Ok(hasher.finalize().into())
+    }
+}
+
+/// Salt plaintext values, returning the public commitments (hex) and the
+/// private parameters to keep
+#[must_use]
+pub fn seal(values: &BTreeMap<String, String>) -> (BTreeMap<String, String>, PrivateParams) {
+    let mut commitments = BTreeMap::new();
+    let mut params = PrivateParams::new();
+    for (name, value) in values {
+        let param = PrivateParam { value: value.clone(), salt: hex::encode(rand::random::<[u8; 32]>()) };
+        let commitment = param.commitment(name).expect("freshly generated salt is valid hex");
+        commitments.insert(name.clone(), hex::encode(commitment));
+        params.insert(name.clone(), param);
+    }
+    (commitments, params)
+}
+
+/// Check every committed parameter is present and matches its commitment
+pub fn verify(spec: &ComplianceSpec, params: &PrivateParams) -> Result<Vec<ParamCommitment>> {
+    let Some(commitments) = &spec.private_constraints else {
+        return Ok(Vec::new());
+    };
+    commitments
+        .iter()
+        .map(|(name, expected)| {
+            let param = params.get(name).ok_or_else(|| {
+                VceError::InvalidSpec(format!("Private parameter '{name}' is missing"))
+            })?;
+            let commitment = param.commitment(name)?;
+            if hex::encode(commitment) != expected.to_lowercase() {
+                return Err(VceError::InvalidSpec(format!(
+                    "Private parameter '{name}' does not match its commitment"
+                )));
+            }
+            Ok(ParamCommitment { name: name.clone(), commitment: commitment.to_vec() })
+        })
+        .collect()
+}
+
+/// Spec with verified private values injected into `constraints`, for host-side checkers
+pub fn apply(spec: &ComplianceSpec, params: &PrivateParams) -> Result<ComplianceSpec> {
+    let verified = verify(spec, params)?;
+    let mut applied = spec.clone();
+    for ParamCommitment { name, .. } in verified {
+        applied.constraints.insert(name.clone(), params[&name].value.clone());
+    }
+    Ok(applied)
+}
+
+/// Check commitments are well formed and do not shadow public constraints
+pub fn validate_commitments(spec: &ComplianceSpec) -> Result<()> {
+    for (name, commitment) in spec.private_constraints.iter().flatten() {
+        if spec.constraints.contains_key(name) {
+            return Err(VceError::InvalidSpec(format!(
+                "Constraint '{name}' cannot be both public and private"
+            )));
+        }
+        if hex::decode(commitment).map(|bytes| bytes.len()) != Ok(32) {
+            return Err(VceError::InvalidSpec(format!(
+                "Commitment for private constraint '{name}' must be 32 hex-encoded bytes"
+            )));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn spec_with(commitments: BTreeMap<String, String>) -> ComplianceSpec {
+        let mut spec = ComplianceSpec::new(
+            "ML model usage constraint".to_string(),
+            "abc123".to_string(),
+            BTreeMap::new(),
+            "US".to_string(),
+            "1.0".to_string(),
+            Utc::now() + chrono::Duration::days(365),
+        );
+        spec.private_constraints = Some(commitments);
+        spec
+    }
+
+    #[test]
+    fn test_seal_and_apply() {
+        let values = BTreeMap::from([("max_usage".to_string(), "1000".to_string())]);
+        let (commitments, params) = seal(&values);
+        let spec = spec_with(commitments);
+        assert!(spec.validate().is_ok());
+
+        let applied = apply(&spec, &params).unwrap();
+        assert_eq!(applied.constraints.get("max_usage").map(String::as_str), Some("1000"));
+        // The public spec never carries the value
+        assert!(!serde_json::to_string(&spec).unwrap().contains("1000"));
+    }
+
+    #[test]
+    fn test_wrong_value_rejected() {
+        let values = BTreeMap::from([("max_usage".to_string(), "1000".to_string())]);
+        let (commitments, mut params) = seal(&values);
+        let spec = spec_with(commitments);
+
+        params.get_mut("max_usage").unwrap().value = "5000".to_string();
+        assert!(verify(&spec, &params).is_err());
+        assert!(verify(&spec, &PrivateParams::new()).is_err());
+    }
+
+    #[test]
+    fn test_shadowed_constraint_rejected() {
+        let (commitments, _) = seal(&BTreeMap::from([("max_usage".to_string(), "1".to_string())]));
+        let mut spec = spec_with(commitments);
+        spec.constraints.insert("max_usage".to_string(), "2".to_string());
+        assert!(spec.validate().is_err());
+    }
+}
@@ -0,0 +1,151 @@
+//! Progress reporting and cancellation for long-running proofs
+//!
+//! A real proof takes minutes to hours. Each phase of
+//! [`generate_proof_with_inputs`](crate::zkvm::generate_proof_with_inputs)
+//! is emitted as a `tracing` event and, when [`ProofOptions::observer`] is
+//! set, reported to a [`ProofObserver`] so an embedder can drive a progress
+//! bar. A [`CancellationToken`] in [`ProofOptions::cancellation`] stops the
+//! proof at the next segment boundary with [`VceError::Cancelled`]; guest
+//! execution itself runs to completion (bound it with
+//! [`SessionLimits::max_cycles`](crate::zkvm::SessionLimits::max_cycles)).
+//!
+//! Segments are reported one by one, and cancellation seen between them,
+//! only when they are proved on more than one worker. A session proved by
+//! risc0's `prove_session` (one worker, or unresolved accelerator
+//! assumptions; see [`crate::parallel`]) reports its segments once they are
+//! all proved and is cancelled only before or after proving.
+//!
+//! [`ProofOptions::observer`]: crate::zkvm::ProofOptions::observer
+//! [`ProofOptions::cancellation`]: crate::zkvm::ProofOptions::cancellation
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use crate::error::{Result, VceError};
+
+/// A step of proof generation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ProofPhase {
+    /// Running the guest program over the inputs
+    Executing,
+    /// Execution finished, split into this many segments to prove
+    Executed { segments: usize, total_cycles: u64 },
+    /// Segment `index` (from 0) is proved, the `proved`th of `total`;
+    /// parallel workers finish segments out of order
+    SegmentProved { index: usize, proved: usize, total: usize },
+    /// Combining segment receipts and resolving assumptions into one receipt
+    Joining,
+    /// The receipt is ready, proved or taken from the cache
+    Done,
+}
+
+/// Receives the phases of a proof as they happen
+///
+/// Called from proving worker threads, so implementations must be cheap
+/// and thread-safe.
+pub trait ProofObserver: Send + Sync {
+    fn on_phase(&self, phase: ProofPhase);
+}
+
+impl std::fmt::Debug for dyn ProofObserver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ProofObserver")
+    }
+}
+
+impl<F: Fn(ProofPhase) + Send + Sync> ProofObserver for F {
+    fn on_phase(&self, phase: ProofPhase) {
+        self(phase);
+    }
+}
+
+/// Shared flag that asks a running proof to stop; clones cancel together
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ask every proof holding this token to stop
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Observer, cancellation and segment count of one proof
+#[derive(Debug, Default)]
+pub(crate) struct Progress {
+    observer: Option<Arc<dyn ProofObserver>>,
+    cancellation: Option<CancellationToken>,
+    proved: AtomicUsize,
+}
+
+impl Progress {
+    pub(crate) fn new(observer: Option<Arc<dyn ProofObserver>>, cancellation: Option<CancellationToken>) -> Self {
+        Self { observer, cancellation, proved: AtomicUsize::new(0) }
+    }
+
+    /// Emit the phase as a `tracing` event and to the observer
+    pub(crate) fn report(&self, phase: ProofPhase) {
+        match phase {
+            ProofPhase::Executing => tracing::info!("Executing guest program"),
+            ProofPhase::Executed { segments, total_cycles } => tracing::info!(segments, total_cycles, "Executed guest program"),
+            ProofPhase::SegmentProved { index, proved, total } => tracing::info!(index, proved, total, "Proved segment {proved} of {total}"),
+            ProofPhase::Joining => tracing::info!("Joining segment receipts"),
+            ProofPhase::Done => tracing::info!("Proof ready"),
+        }
+        if let Some(observer) = &self.observer {
+            observer.on_phase(phase);
+        }
+    }
+
+    /// Report segment `index` of `total` as proved
+    pub(crate) fn segment_proved(&self, index: usize, total: usize) {
+        let proved = self.proved.fetch_add(1, Ordering::Relaxed) + 1;
+        self.report(ProofPhase::SegmentProved { index, proved, total });
+    }
+
+    /// Fail with [`VceError::Cancelled`] once cancellation was requested
+    pub(crate) fn check(&self) -> Result<()> {
+        match &self.cancellation {
+            Some(token) if token.is_cancelled() => Err(VceError::Cancelled),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_progress_reports_and_cancels() {
+        let phases = Arc::new(Mutex::new(Vec::new()));
+        let seen = Arc::clone(&phases);
+        let token = CancellationToken::new();
+        let progress = Progress::new(Some(Arc::new(move |phase| seen.lock().unwrap().push(phase))), Some(token.clone()));
+
+        progress.report(ProofPhase::Executing);
+        progress.segment_proved(1, 2);
+        progress.segment_proved(0, 2);
+        assert!(progress.check().is_ok());
+        assert_eq!(*phases.lock().unwrap(), vec![
+            ProofPhase::Executing,
+            ProofPhase::SegmentProved { index: 1, proved: 1, total: 2 },
+            ProofPhase::SegmentProved { index: 0, proved: 2, total: 2 },
+        ]);
+
+        token.clone().cancel();
+        assert!(matches!(progress.check(), Err(VceError::Cancelled)));
+        assert!(Progress::default().check().is_ok());
+    }
+}
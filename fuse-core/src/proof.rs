@@ -1,13 +1,77 @@
 //! Zero-knowledge proof structures and operations
 
 use serde::{Deserialize, Serialize};
-use crate::error::Result;
+use crate::error::{Result, VceError};
+use crate::aggregate::ChildCommitment;
+use crate::composite::NodeResult;
+use crate::disclosure::PredicateResult;
+use crate::evidence::EvidenceCommitment;
+use crate::incremental::LogState;
+use crate::private_params::ParamCommitment;
+use crate::registry::{CheckerId, ImageRegistry};
+use crate::score::Score;
 
 /// Result of a compliance check
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ComplianceResult {
     Pass = 0,
     Fail = 1,
+    /// The check was inconclusive because the evidence or spec was malformed.
+    /// The reason is available in `JournalOutput::error_code`.
+    Error = 2,
+}
+
+/// Reason a guest check was inconclusive
+///
+/// Mirrors the guest program's `ErrorCode`; variant order must match.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ErrorCode {
+    /// No error (result is Pass or Fail)
+    #[default]
+    None = 0,
+    /// The compliance spec is not valid JSON
+    SpecParse = 1,
+    /// The system data is not valid JSON
+    SystemDataParse = 2,
+    /// A constraint required by the checker is missing from the spec
+    MissingConstraint = 3,
+    /// A field required by the checker is missing from the system data
+    MissingField = 4,
+    /// A record in the system data has an unexpected shape
+    MalformedRecord = 5,
+    /// A hex-encoded value could not be decoded
+    InvalidEncoding = 6,
+    /// A key or signature has the wrong length
+    InvalidLength = 7,
+    /// A public key could not be parsed
+    InvalidKey = 8,
+    /// The spec's `pass_threshold` is not a fraction between 0 and 1
+    InvalidThreshold = 9,
+    /// The spec's `composite` tree is malformed
+    InvalidComposite = 10,
+    /// A composite node is inconclusive because some of its sub-claims errored
+    SubClaimError = 11,
+    /// The spec's `rule` is not a valid expression
+    InvalidRule = 12,
+    /// The spec's `rule` applies an operator to values of the wrong type
+    RuleType = 13,
+    /// A private parameter is missing or does not match its commitment
+    PrivateParam = 14,
+    /// No longer produced: a batch of specs cannot be given typed system
+    /// data. Kept so that later codes keep their numbers.
+    TypedInput = 15,
+    /// The spec's checker cannot read streamed records, or the spec needs
+    /// the full JSON document
+    StreamInput = 16,
+    /// A child journal of an aggregation, or the previous period's journal
+    /// of an incremental proof, could not be decoded
+    ChildJournal = 17,
+    /// The previous period of an incremental proof errored, is not
+    /// incremental, or is for a different spec or private parameters
+    IncrementalState = 18,
+    /// The claim's checker is not compiled into the guest program that ran
+    /// it; prove it with the checker's own guest or the general one
+    CheckerNotInGuest = 19,
 }
 
 /// The complete output committed to the journal by the guest
@@ -18,6 +82,41 @@ pub struct JournalOutput {
     pub claim_hash: Vec<u8>,
     // Serialize JSON as string to avoid RISC Zero journal format issues
     pub redacted_json: String,
+    // ErrorCode::None unless result is Error
+    #[serde(default)]
+    pub error_code: ErrorCode,
+    // Zero denominator unless the checker produced a graded score
+    #[serde(default)]
+    pub score: Score,
+    // Per-node results of a composite spec in pre-order; empty otherwise
+    #[serde(default)]
+    pub node_results: Vec<NodeResult>,
+    // Disclosed field paths that did not resolve in the system data
+    #[serde(default)]
+    pub missing_fields: Vec<String>,
+    // Salted Merkle root over all system data fields; empty unless the spec sets commit_fields
+    #[serde(default)]
+    pub fields_root: Vec<u8>,
+    // Outcomes of the spec's disclosure predicates, in spec order
+    #[serde(default)]
+    pub predicates: Vec<PredicateResult>,
+    // Commitments of the private parameters applied, in name order
+    #[serde(default)]
+    pub private_params: Vec<ParamCommitment>,
+    // Digests of the spec, the system data and any extra evidence files
+    #[serde(default)]
+    pub evidence: Vec<EvidenceCommitment>,
+    // One output per spec of a batch, in input order, each committing its
+    // spec's digest; empty for a single spec
+    #[serde(default)]
+    pub batch: Vec<JournalOutput>,
+    // Image ID, journal digest and result of each aggregated child proof,
+    // in input order, or of the previous period of an incremental proof
+    #[serde(default)]
+    pub children: Vec<ChildCommitment>,
+    // Cumulative log commitment and counters; empty root unless incremental
+    #[serde(default)]
+    pub log_state: LogState,
 }
 
 impl std::fmt::Display for ComplianceResult {
@@ -25,10 +124,39 @@ impl std::fmt::Display for ComplianceResult {
         match self {
             ComplianceResult::Pass => write!(f, "PASS"),
             ComplianceResult::Fail => write!(f, "FAIL"),
+            ComplianceResult::Error => write!(f, "ERROR"),
         }
     }
 }
 
+impl std::fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let description = match self {
+            ErrorCode::None => "none",
+            ErrorCode::SpecParse => "compliance spec is not valid JSON",
+            ErrorCode::SystemDataParse => "system data is not valid JSON",
+            ErrorCode::MissingConstraint => "required constraint missing from spec",
+            ErrorCode::MissingField => "required field missing from system data",
+            ErrorCode::MalformedRecord => "malformed record in system data",
+            ErrorCode::InvalidEncoding => "invalid hex encoding",
+            ErrorCode::InvalidLength => "invalid key or signature length",
+            ErrorCode::InvalidKey => "invalid public key",
+            ErrorCode::InvalidThreshold => "invalid pass threshold",
+            ErrorCode::InvalidComposite => "malformed composite spec",
+            ErrorCode::SubClaimError => "a composite sub-claim was inconclusive",
+            ErrorCode::InvalidRule => "invalid rule expression",
+            ErrorCode::RuleType => "rule applied to values of the wrong type",
+            ErrorCode::PrivateParam => "private parameter missing or not matching its commitment",
+            ErrorCode::TypedInput => "typed system data cannot be checked against a batch",
+            ErrorCode::StreamInput => "streamed system data does not fit the spec",
+            ErrorCode::ChildJournal => "aggregated child or previous period journal could not be decoded",
+            ErrorCode::IncrementalState => "previous period does not continue this incremental proof",
+            ErrorCode::CheckerNotInGuest => "the claim's checker is not in this guest program",
+        };
+        write!(f, "{description}")
+    }
+}
+
 /// Zero-knowledge proof of compliance check execution
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ComplianceProof {
@@ -51,6 +179,21 @@ pub struct ComplianceProof {
 
     /// Decoded journal output
     pub journal_output: Option<JournalOutput>,
+
+    /// Graded score, when the checker produced one.
+    /// Duplicated from the journal so dashboards can track it without decoding.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score: Option<Score>,
+
+    /// Entry of the journal's batch this proof stands for, when one proof
+    /// covers several specs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub batch_entry: Option<usize>,
+
+    /// Checker whose guest program produced the receipt, known once the
+    /// proof is verified; never trusted from the file
+    #[serde(skip)]
+    pub checker: Option<CheckerId>,
 }
 
 impl ComplianceProof {
@@ -68,8 +211,48 @@ impl ComplianceProof {
             result: journal_output.result,
             timestamp: chrono::Utc::now(),
             journal,
+            score: Some(journal_output.score).filter(Score::is_graded),
             journal_output: Some(journal_output),
+            batch_entry: None,
+            checker: None,
+        }
+    }
+
+    /// Narrow a proof of a batch to the result for one of its specs
+    pub fn with_batch_entry(mut self, index: usize) -> Result<Self> {
+        self.batch_entry = Some(index);
+        let (result, score) = self.output()
+            .map(|entry| (entry.result, entry.score))
+            .ok_or_else(|| batch_entry_missing(index))?;
+        self.result = result;
+        self.score = Some(score).filter(Score::is_graded);
+        Ok(self)
+    }
+
+    /// Journal output for this proof's spec: its batch entry, if any
+    #[must_use]
+    pub fn output(&self) -> Option<&JournalOutput> {
+        let output = self.journal_output.as_ref()?;
+        match self.batch_entry {
+            Some(index) => output.batch.get(index),
+            None => Some(output),
+        }
+    }
+
+    /// Evidence commitments for this proof's spec; a batch entry commits its
+    /// spec and shares the batch's system data and files
+    #[must_use]
+    pub fn evidence(&self) -> Vec<EvidenceCommitment> {
+        let Some(output) = self.output() else {
+            return Vec::new();
+        };
+        let mut evidence = output.evidence.clone();
+        if self.batch_entry.is_some() {
+            if let Some(batch) = &self.journal_output {
+                evidence.extend(batch.evidence.iter().cloned());
+            }
         }
+        evidence
     }
 
     /// Create a new proof (placeholder for MVP)
@@ -88,6 +271,9 @@ impl ComplianceProof {
             timestamp: chrono::Utc::now(),
             journal,
             journal_output: None,
+            score: None,
+            batch_entry: None,
+            checker: None,
         }
     }
     
@@ -106,8 +292,21 @@ impl ComplianceProof {
         }
 
         // For real proofs, verify using RISC Zero
-        let (output, _) = crate::zkvm::verify_proof(&self.proof_data)?;
+        let (checker, output, _) = crate::zkvm::verify_receipt(&self.proof_data, ImageRegistry::built())?;
+        self.checker = Some(checker);
         self.journal_output = Some(output);
+        let (result, score) = self.output()
+            .map(|entry| (entry.result, entry.score))
+            .ok_or_else(|| batch_entry_missing(self.batch_entry.unwrap_or_default()))?;
+        // The stored result is only a copy of the journal's; a file edited
+        // to claim another result is rejected, not corrected
+        if self.result != result {
+            return Err(VceError::ProofVerificationFailed(format!(
+                "Proof result {} does not match the result {result} committed to the journal",
+                self.result
+            )));
+        }
+        self.score = Some(score).filter(Score::is_graded);
         Ok(())
     }
 
@@ -118,3 +317,7 @@ impl ComplianceProof {
     }
 }
 
+fn batch_entry_missing(index: usize) -> VceError {
+    VceError::ProofVerificationFailed(format!("Proof journal has no batch entry {index}"))
+}
+
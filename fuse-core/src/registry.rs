@@ -0,0 +1,225 @@
+//! Checker guest programs and their image IDs
+//!
+//! Besides the general guest program, `fuse-guest`, which carries every
+//! checker, each checker family is built as its own guest program with
+//! only that family's checker code (`scripts/build-guests.sh`), and a
+//! verifier can tell from a receipt's image ID which checker produced it.
+//!
+//! Only edits to one family's checker are isolated: they change that
+//! family's image ID and the general guest's, and no other. Every guest
+//! program also compiles the shared framework (input reading, evidence
+//! commitments, disclosure, Merkle commitments, composite specs, streaming
+//! and `fuse-rules`), so an edit there, or a dependency upgrade, changes
+//! every image ID and old proofs stop verifying against new builds.
+//!
+//! [`CheckerId::for_spec`] names the guest program a spec is proved with,
+//! following the guest's own claim routing, and an [`ImageRegistry`] maps
+//! each guest program to its image ID. Verification looks the receipt's
+//! image ID up in the registry and rejects an envelope whose spec names a
+//! different checker. Composite and rule specs, batches and aggregates
+//! use the general guest program.
+
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+use risc0_zkvm::sha::Digest;
+use serde_json::Value;
+use crate::error::VceError;
+use crate::spec::ComplianceSpec;
+use crate::zkvm::{self, ProofInputs, ProofMode};
+
+/// A guest program, named for the checker family it carries
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CheckerId {
+    /// Every checker, plus composite and rule specs, batches and aggregates
+    General,
+    Soc2,
+    Gdpr,
+    SupplyChain,
+    MlModel,
+    /// C2PA manifests, Ed25519 signatures and JSON-only parsing
+    C2pa,
+}
+
+impl CheckerId {
+    pub const ALL: [CheckerId; 6] = [Self::General, Self::Soc2, Self::Gdpr, Self::SupplyChain, Self::MlModel, Self::C2pa];
+
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::General => "general",
+            Self::Soc2 => "soc2",
+            Self::Gdpr => "gdpr",
+            Self::SupplyChain => "supply-chain",
+            Self::MlModel => "ml-model",
+            Self::C2pa => "c2pa",
+        }
+    }
+
+    /// Name of the guest program binary
+    #[must_use]
+    pub fn guest(self) -> &'static str {
+        match self {
+            Self::General => "fuse-guest",
+            Self::Soc2 => "fuse-guest-soc2",
+            Self::Gdpr => "fuse-guest-gdpr",
+            Self::SupplyChain => "fuse-guest-supply-chain",
+            Self::MlModel => "fuse-guest-ml-model",
+            Self::C2pa => "fuse-guest-c2pa",
+        }
+    }
+
+    /// Guest program that proves `spec`
+    #[must_use]
+    pub fn for_spec(spec: &ComplianceSpec) -> Self {
+        if spec.composite.is_some() || spec.rule.is_some() {
+            return Self::General;
+        }
+        Self::for_claim(&spec.claim)
+    }
+
+    /// Guest program that proves `inputs`
+    #[must_use]
+    pub fn for_inputs(inputs: &ProofInputs<'_>) -> Self {
+        let spec_json = match inputs.mode {
+            ProofMode::Batch { .. } | ProofMode::Aggregate { .. } => return Self::General,
+            mode => mode.spec_json().unwrap_or_default(),
+        };
+        // A spec that does not parse errors the same in every guest
+        match serde_json::from_str::<Value>(spec_json) {
+            Ok(spec) if spec.get("composite").is_none() && spec.get("rule").is_none() => {
+                Self::for_claim(spec.get("claim").and_then(Value::as_str).unwrap_or_default())
+            }
+            _ => Self::General,
+        }
+    }
+
+    /// Mirrors the guest program's `Route::for_spec`; earlier patterns take
+    /// precedence
+    fn for_claim(claim: &str) -> Self {
+        if claim.contains("JSON parsing only") || claim.contains("C2PA") {
+            Self::C2pa
+        } else if claim.contains("SOC2") {
+            Self::Soc2
+        } else if claim.contains("GDPR") {
+            Self::Gdpr
+        } else if claim.contains("Supply chain") || claim.contains("provenance") {
+            Self::SupplyChain
+        } else if claim.contains("ML model") || claim.contains("usage constraint") {
+            Self::MlModel
+        } else if claim.contains("Ed25519") || claim.contains("signature verification") {
+            Self::C2pa
+        } else {
+            Self::General
+        }
+    }
+}
+
+impl std::fmt::Display for CheckerId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl std::str::FromStr for CheckerId {
+    type Err = VceError;
+
+    fn from_str(name: &str) -> crate::Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|checker| checker.name() == name)
+            .ok_or_else(|| VceError::InvalidSpec(format!("Unknown checker '{name}'")))
+    }
+}
+
+/// Image ID of each known guest program
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImageRegistry {
+    images: BTreeMap<CheckerId, Digest>,
+}
+
+impl ImageRegistry {
+    /// Registry of the guest programs built into this binary
+    ///
+    /// Guests that were not built are left out; so is one whose ELF does
+    /// not load, with a warning.
+    pub fn built() -> &'static ImageRegistry {
+        static BUILT: OnceLock<ImageRegistry> = OnceLock::new();
+        BUILT.get_or_init(|| {
+            let mut registry = Self::default();
+            for checker in CheckerId::ALL {
+                let Some(elf) = zkvm::get_guest_elf(checker) else { continue };
+                match zkvm::compute_image_id(elf) {
+                    Ok(image_id) => registry.insert(checker, image_id),
+                    Err(e) => tracing::warn!(guest = checker.guest(), "Leaving guest program out of the registry: {e}"),
+                }
+            }
+            registry
+        })
+    }
+
+    /// Register `checker`'s guest program as having `image_id`
+    pub fn insert(&mut self, checker: CheckerId, image_id: Digest) {
+        self.images.insert(checker, image_id);
+    }
+
+    #[must_use]
+    pub fn image_id(&self, checker: CheckerId) -> Option<Digest> {
+        self.images.get(&checker).copied()
+    }
+
+    /// Checker whose guest program has `image_id`
+    #[must_use]
+    pub fn checker(&self, image_id: &Digest) -> Option<CheckerId> {
+        self.images.iter().find(|(_, id)| *id == image_id).map(|(checker, _)| *checker)
+    }
+
+    /// Registered guest programs, general first
+    pub fn iter(&self) -> impl Iterator<Item = (CheckerId, Digest)> + '_ {
+        self.images.iter().map(|(checker, image_id)| (*checker, *image_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(claim: &str) -> ComplianceSpec {
+        ComplianceSpec::new(claim.to_string(), "hash".to_string(), Default::default(), "US".to_string(), "1.0".to_string(), chrono::Utc::now())
+    }
+
+    #[test]
+    fn test_spec_routes_to_its_checker_guest() {
+        assert_eq!(CheckerId::for_spec(&spec("SOC2 control CC6.1")), CheckerId::Soc2);
+        assert_eq!(CheckerId::for_spec(&spec("GDPR data residency")), CheckerId::Gdpr);
+        assert_eq!(CheckerId::for_spec(&spec("Supply chain provenance")), CheckerId::SupplyChain);
+        assert_eq!(CheckerId::for_spec(&spec("ML model usage constraint")), CheckerId::MlModel);
+        assert_eq!(CheckerId::for_spec(&spec("Ed25519 signature verification")), CheckerId::C2pa);
+        assert_eq!(CheckerId::for_spec(&spec("Something else")), CheckerId::General);
+        // C2PA takes precedence over SOC2, as in the guest
+        assert_eq!(CheckerId::for_spec(&spec("C2PA and SOC2")), CheckerId::C2pa);
+
+        let mut rule = spec("SOC2 control CC6.1");
+        rule.rule = Some("mfa == true".to_string());
+        assert_eq!(CheckerId::for_spec(&rule), CheckerId::General);
+    }
+
+    #[test]
+    fn test_inputs_route_like_their_spec() {
+        assert_eq!(CheckerId::for_inputs(&ProofInputs::new(r#"{"claim": "GDPR data residency"}"#, "{}")), CheckerId::Gdpr);
+        assert_eq!(CheckerId::for_inputs(&ProofInputs::new(r#"{"claim": "GDPR", "rule": "a"}"#, "{}")), CheckerId::General);
+        assert_eq!(CheckerId::for_inputs(&ProofInputs::new("not json", "{}")), CheckerId::General);
+        let children = [Vec::new()];
+        let aggregate = ProofInputs::with_mode(ProofMode::Aggregate { spec_json: r#"{"claim": "SOC2"}"#, child_receipts: &children });
+        assert_eq!(CheckerId::for_inputs(&aggregate), CheckerId::General);
+    }
+
+    #[test]
+    fn test_registry_finds_checker_by_image_id() {
+        let mut registry = ImageRegistry::default();
+        registry.insert(CheckerId::Gdpr, Digest::from([1; 8]));
+        assert_eq!(registry.checker(&Digest::from([1; 8])), Some(CheckerId::Gdpr));
+        assert_eq!(registry.checker(&Digest::from([2; 8])), None);
+        assert_eq!("supply-chain".parse::<CheckerId>().unwrap(), CheckerId::SupplyChain);
+        assert!("soc3".parse::<CheckerId>().is_err());
+    }
+}
@@ -0,0 +1,200 @@
+//! Proving on a remote service
+//!
+//! [`ProverType::Remote`](crate::zkvm::ProverType::Remote) sends the inputs
+//! to an HTTP proving service such as `fuse-prover-server` and polls until
+//! the receipt is ready. The service is not trusted: a receipt is accepted
+//! only once it verifies against the image ID of the locally built guest
+//! for the spec's checker (see [`crate::registry`]) and its journal
+//! commits to the spec, system data and evidence files that were sent.
+//! The service does see the inputs and the salt seed, so remote proving
+//! keeps a proof honest but not the system data private. Enable the
+//! `remote` feature.
+//!
+//! The protocol, under `/v1/proofs`:
+//!
+//! - `POST /v1/proofs` with a bincode [`ProveRequest`] queues a proof and
+//!   answers `202` with its JSON [`JobStatus`], or `409` when the service
+//!   has a different build of the checker's guest program
+//! - `GET /v1/proofs/{id}` answers the job's [`JobStatus`]
+//! - `GET /v1/proofs/{id}/receipt` answers the bincode receipt once the job
+//!   is done, `409` before; it can be fetched again until the job is deleted
+//! - `DELETE /v1/proofs/{id}` cancels the job, or releases a finished one
+//!
+//! Errors answer a JSON `{"error": ...}` body.
+
+use std::time::Duration;
+use risc0_zkvm::sha::Digest;
+use risc0_zkvm::Receipt;
+use serde::{Deserialize, Serialize};
+use crate::error::{Result, VceError};
+use crate::evidence;
+use crate::progress::{ProofPhase, Progress};
+use crate::proof::JournalOutput;
+use crate::registry::CheckerId;
+use crate::zkvm::{self, OwnedProofInputs, ProofInputs, ProofOptions};
+
+/// Path of the proof jobs on a proving service
+pub const PROOFS_PATH: &str = "/v1/proofs";
+
+/// How often a running job is polled
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// A proof to queue on a proving service
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProveRequest {
+    /// Hex image ID of the checker's guest program the client verifies
+    /// against
+    pub image_id: String,
+    pub inputs: OwnedProofInputs,
+    /// Segment size the client asked for, as a power of two of cycles
+    pub segment_po2: Option<u32>,
+    /// Cycle limit the client asked for; the service may set a lower one
+    pub max_cycles: Option<u64>,
+}
+
+/// Where a job on a proving service is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobState {
+    Queued,
+    Running,
+    Done,
+    Failed,
+}
+
+/// A job on a proving service, as reported to its client
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JobStatus {
+    pub id: String,
+    pub state: JobState,
+    /// Segments to prove, once the guest has executed
+    pub segments: Option<usize>,
+    /// Cycles executed, once the guest has executed
+    pub total_cycles: Option<u64>,
+    /// Indices of the proved segments, in the order they were proved
+    #[serde(default)]
+    pub proved: Vec<usize>,
+    /// Why the job failed
+    pub error: Option<String>,
+}
+
+/// Prove `inputs` on the proving service at `url` and verify the receipt,
+/// refusing a receipt body over `max_receipt_bytes`
+pub(crate) fn prove(url: &str, max_receipt_bytes: u64, inputs: &ProofInputs<'_>, options: &ProofOptions, progress: &Progress) -> Result<Receipt> {
+    let image_id = zkvm::image_id(CheckerId::for_inputs(inputs))?;
+    let request = ProveRequest {
+        image_id: hex::encode(image_id.as_bytes()),
+        inputs: OwnedProofInputs::from(inputs),
+        segment_po2: options.limits.segment_po2,
+        max_cycles: options.limits.max_cycles,
+    };
+    let body = bincode::serialize(&request)
+        .map_err(|e| VceError::InputSerialization(format!("Failed to serialize remote proof request: {e}")))?;
+
+    // Statuses are checked here rather than turned into transport errors
+    let agent = ureq::Agent::new_with_config(ureq::Agent::config_builder().http_status_as_error(false).build());
+    let jobs = format!("{}{PROOFS_PATH}", url.trim_end_matches('/'));
+    tracing::info!(url, "Sending proof to remote prover");
+    progress.report(ProofPhase::Executing);
+    let response = agent.post(&jobs).header("content-type", "application/octet-stream").send(&body[..]);
+    let mut status: JobStatus = read_json(response, 202)?;
+    let job = format!("{jobs}/{}", status.id);
+
+    let mut executed = false;
+    let mut reported = 0;
+    loop {
+        if let (false, Some(segments)) = (executed, status.segments) {
+            progress.report(ProofPhase::Executed { segments, total_cycles: status.total_cycles.unwrap_or_default() });
+            executed = true;
+        }
+        for &index in status.proved.iter().skip(reported) {
+            progress.segment_proved(index, status.segments.unwrap_or_default());
+        }
+        reported = reported.max(status.proved.len());
+        match status.state {
+            JobState::Done => break,
+            JobState::Failed => {
+                return Err(VceError::ProofGenerationFailed(format!(
+                    "Remote prover failed: {}",
+                    status.error.unwrap_or_else(|| "no reason given".to_string())
+                )));
+            }
+            JobState::Queued | JobState::Running => {}
+        }
+        if let Err(e) = progress.check() {
+            // The job is abandoned either way
+            let _ = agent.delete(&job).call();
+            return Err(e);
+        }
+        std::thread::sleep(POLL_INTERVAL);
+        status = read_json(agent.get(&job).call(), 200)?;
+    }
+
+    let mut response = agent.get(format!("{job}/receipt")).call().map_err(transport)?;
+    if response.status().as_u16() != 200 {
+        return Err(remote_error(response.status().as_u16(), response.body_mut().read_to_string().unwrap_or_default()));
+    }
+    // The service is not trusted to send a receipt of sensible size
+    let receipt_bytes = response.body_mut().with_config().limit(max_receipt_bytes).read_to_vec().map_err(|e| match e {
+        ureq::Error::BodyExceedsLimit(limit) => VceError::ProofGenerationFailed(format!("Remote receipt is larger than the {limit} byte limit")),
+        e => transport(e),
+    })?;
+    // The service keeps the receipt until told it was received
+    let _ = agent.delete(&job).call();
+    let receipt: Receipt = bincode::deserialize(&receipt_bytes)
+        .map_err(|e| VceError::ReceiptDeserialization(format!("Failed to deserialize remote receipt: {e}")))?;
+    check_receipt(&receipt, image_id, inputs)?;
+    Ok(receipt)
+}
+
+/// Accept a receipt only if it is a valid proof of exactly these inputs
+fn check_receipt(receipt: &Receipt, image_id: Digest, inputs: &ProofInputs<'_>) -> Result<()> {
+    if risc0_zkvm::is_dev_mode() {
+        tracing::warn!("RISC0_DEV_MODE is set: accepting the remote receipt without verifying it");
+    } else {
+        receipt.verify(image_id)
+            .map_err(|e| VceError::ProofVerificationFailed(format!("Remote receipt does not verify against the guest image ID: {e}")))?;
+    }
+    let journal: JournalOutput = receipt.journal.decode()
+        .map_err(|e| VceError::RiscZero(format!("Failed to decode JournalOutput from remote journal: {e}")))?;
+    // A service could otherwise return a valid proof over other system data
+    evidence::check_journal(&journal, inputs)
+}
+
+/// Read a JSON body from a response expected to have status `expected`
+fn read_json<T: for<'de> Deserialize<'de>>(response: std::result::Result<ureq::http::Response<ureq::Body>, ureq::Error>, expected: u16) -> Result<T> {
+    let mut response = response.map_err(transport)?;
+    let status = response.status().as_u16();
+    let body = response.body_mut().read_to_string().map_err(transport)?;
+    if status != expected {
+        return Err(remote_error(status, body));
+    }
+    serde_json::from_str(&body)
+        .map_err(|e| VceError::ProofGenerationFailed(format!("Unexpected response from remote prover: {e}")))
+}
+
+fn remote_error(status: u16, body: String) -> VceError {
+    #[derive(Deserialize)]
+    struct ErrorBody {
+        error: String,
+    }
+    let reason = serde_json::from_str::<ErrorBody>(&body).map(|body| body.error).unwrap_or(body);
+    VceError::ProofGenerationFailed(format!("Remote prover answered {status}: {reason}"))
+}
+
+fn transport(e: ureq::Error) -> VceError {
+    VceError::ProofGenerationFailed(format!("Could not reach remote prover: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_job_status_wire_format() {
+        let status = JobStatus { id: "ab".to_string(), state: JobState::Running, segments: Some(2), total_cycles: Some(1 << 20), proved: vec![1], error: None };
+        let json = serde_json::to_value(&status).unwrap();
+        assert_eq!(json["state"], "running");
+        assert_eq!(serde_json::from_value::<JobStatus>(json).unwrap(), status);
+    }
+}
@@ -0,0 +1,149 @@
+//! Graded compliance scores
+//!
+//! Mirrors the guest program's score type. A score is an exact fraction
+//! compared against the spec's `pass_threshold` using integer arithmetic.
+
+use serde::{Deserialize, Serialize};
+use crate::proof::ComplianceResult;
+
+/// Largest number of fractional digits accepted in a threshold
+const MAX_THRESHOLD_DECIMALS: u32 = 9;
+
+/// Numeric score committed to the journal by graded checkers
+///
+/// A zero `denominator` means the checker did not produce a score.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Score {
+    pub numerator: u64,
+    pub denominator: u64,
+    pub threshold_numerator: u64,
+    pub threshold_denominator: u64,
+}
+
+impl Score {
+    #[must_use]
+    pub fn new(numerator: u64, denominator: u64, threshold: (u64, u64)) -> Self {
+        Self {
+            numerator,
+            denominator,
+            threshold_numerator: threshold.0,
+            threshold_denominator: threshold.1,
+        }
+    }
+
+    /// Whether a checker produced this score
+    #[must_use]
+    pub fn is_graded(&self) -> bool {
+        self.denominator > 0
+    }
+
+    /// Derive Pass/Fail by comparing the score to the threshold.
+    /// An empty sample is vacuously compliant.
+    #[must_use]
+    pub fn result(&self) -> ComplianceResult {
+        if !self.is_graded() {
+            return ComplianceResult::Pass;
+        }
+        let lhs = u128::from(self.numerator) * u128::from(self.threshold_denominator);
+        let rhs = u128::from(self.threshold_numerator) * u128::from(self.denominator);
+        if lhs >= rhs {
+            ComplianceResult::Pass
+        } else {
+            ComplianceResult::Fail
+        }
+    }
+
+    /// Score as a percentage, for display
+    #[must_use]
+    pub fn percent(&self) -> f64 {
+        ratio_percent(self.numerator, self.denominator)
+    }
+
+    /// Threshold as a percentage, for display
+    #[must_use]
+    pub fn threshold_percent(&self) -> f64 {
+        ratio_percent(self.threshold_numerator, self.threshold_denominator)
+    }
+}
+
+impl std::fmt::Display for Score {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:.2}% ({}/{}), threshold {:.2}%",
+            self.percent(),
+            self.numerator,
+            self.denominator,
+            self.threshold_percent()
+        )
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn ratio_percent(numerator: u64, denominator: u64) -> f64 {
+    if denominator == 0 {
+        return 0.0;
+    }
+    numerator as f64 * 100.0 / denominator as f64
+}
+
+/// Parse a pass threshold such as "0.99", "1" or "99.5%" into
+/// (numerator, denominator). Returns `None` for malformed input or
+/// thresholds above 100%.
+#[must_use]
+pub fn parse_threshold(input: &str) -> Option<(u64, u64)> {
+    let input = input.trim();
+    let (digits, percent) = match input.strip_suffix('%') {
+        Some(rest) => (rest.trim_end(), true),
+        None => (input, false),
+    };
+    let (whole, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    if whole.is_empty() && fraction.is_empty() {
+        return None;
+    }
+    if !whole.bytes().chain(fraction.bytes()).all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let decimals = u32::try_from(fraction.len()).ok()?;
+    if decimals > MAX_THRESHOLD_DECIMALS {
+        return None;
+    }
+
+    let mut numerator: u64 = 0;
+    for b in whole.bytes().chain(fraction.bytes()) {
+        numerator = numerator.checked_mul(10)?.checked_add(u64::from(b - b'0'))?;
+    }
+    let mut denominator = 10u64.pow(decimals);
+    if percent {
+        denominator = denominator.checked_mul(100)?;
+    }
+
+    // A threshold above 100% can never be met
+    if numerator > denominator {
+        return None;
+    }
+    Some((numerator, denominator))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_threshold() {
+        assert_eq!(parse_threshold("0.99"), Some((99, 100)));
+        assert_eq!(parse_threshold("99.5%"), Some((995, 1000)));
+        assert_eq!(parse_threshold("1"), Some((1, 1)));
+        assert_eq!(parse_threshold("1.5"), None);
+        assert_eq!(parse_threshold("-0.5"), None);
+        assert_eq!(parse_threshold("%"), None);
+    }
+
+    #[test]
+    fn test_score_against_threshold() {
+        let threshold = parse_threshold("99%").unwrap();
+        assert_eq!(Score::new(987, 1000, threshold).result(), ComplianceResult::Fail);
+        assert_eq!(Score::new(990, 1000, threshold).result(), ComplianceResult::Pass);
+        assert_eq!(Score::new(0, 0, threshold).result(), ComplianceResult::Pass);
+    }
+}
@@ -4,6 +4,8 @@ use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::collections::BTreeMap;
 use chrono::{DateTime, Utc};
+use crate::composite::ClaimNode;
+use crate::disclosure::DisclosurePredicate;
 
 /// Compliance specification that defines what needs to be verified
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -26,14 +28,48 @@ pub struct ComplianceSpec {
     /// Expiry date for this specification
     pub expiry: DateTime<Utc>,
 
+    /// Salted commitments (hex SHA-256) to confidential constraint values,
+    /// by constraint name. The values are supplied to the prover separately
+    /// and injected into `constraints` inside the guest.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub private_constraints: Option<BTreeMap<String, String>>,
+
     /// Optional metadata
     #[serde(default)]
     pub metadata: BTreeMap<String, String>,
 
-    /// Optional list of top-level JSON fields to disclose in the proof journal.
-    /// Used for selective disclosure (e.g., in C2PA manifest verification).
+    /// Optional list of fields to disclose in the proof journal. Entries
+    /// starting with `/` are JSON Pointers into the system data (`*` matches
+    /// every array element); bare names are top-level keys of the checker's
+    /// disclosure root (the `claim` object for C2PA).
     #[serde(default)]
     pub disclosed_fields: Option<Vec<String>>,
+
+    /// Optional named rule expressions whose boolean outcomes are committed
+    /// to the journal instead of the underlying values
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disclosure_predicates: Option<Vec<DisclosurePredicate>>,
+
+    /// Optional pass threshold for graded checkers, as a fraction ("0.99")
+    /// or percentage ("99%"). Without it every record must conform.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pass_threshold: Option<String>,
+
+    /// Optional tree of sub-claims combined with `all`/`any`/`threshold`.
+    /// When present it is evaluated instead of routing `claim` to a checker.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub composite: Option<ClaimNode>,
+
+    /// Optional rule expression evaluated against the system data
+    /// (e.g. `count(usage_logs) <= 1000`). When present it decides the
+    /// result instead of a built-in checker; see the `fuse-rules` crate.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rule: Option<String>,
+
+    /// Commit a salted Merkle root over every field of the system data, so
+    /// fields can be revealed later with `fuse-reveal` without re-proving
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub commit_fields: bool,
 }
 
 impl ComplianceSpec {
@@ -54,8 +90,14 @@ impl ComplianceSpec {
             jurisdiction,
             version,
             expiry,
+            private_constraints: None,
             metadata: BTreeMap::new(),
             disclosed_fields: None,
+            disclosure_predicates: None,
+            pass_threshold: None,
+            composite: None,
+            rule: None,
+            commit_fields: false,
         }
     }
 
@@ -69,12 +111,35 @@ impl ComplianceSpec {
         hex::encode(hasher.finalize())
     }
 
+    /// The JSON this specification must be proved over: `serde_json`'s
+    /// compact serialization, the same bytes [`hash`](Self::hash) covers
+    ///
+    /// A proof commits to the exact spec bytes it was given, and
+    /// [`VerifiableComplianceEnvelope::verify`](crate::VerifiableComplianceEnvelope::verify)
+    /// checks that commitment against this form. A proof over the same spec
+    /// formatted or ordered differently, such as the spec file as written,
+    /// is valid but does not verify in an envelope.
+    pub fn to_proof_json(&self) -> crate::Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+
     /// Check if the specification has expired
     #[must_use] 
     pub fn is_expired(&self) -> bool {
         Utc::now() > self.expiry
     }
 
+    /// Whether evaluating the spec reads more of the system data than its
+    /// checker does (rules, composites, disclosure, field commitments)
+    #[must_use]
+    pub fn needs_full_document(&self) -> bool {
+        self.rule.is_some()
+            || self.composite.is_some()
+            || self.disclosed_fields.is_some()
+            || self.disclosure_predicates.is_some()
+            || self.commit_fields
+    }
+
     /// Validate the specification
     pub fn validate(&self) -> crate::Result<()> {
         if self.claim.is_empty() {
@@ -87,6 +152,32 @@ impl ComplianceSpec {
             ));
         }
 
+        if let Some(threshold) = &self.pass_threshold {
+            if crate::score::parse_threshold(threshold).is_none() {
+                return Err(crate::VceError::InvalidSpec(format!(
+                    "Pass threshold must be a fraction between 0 and 1 or a percentage, got '{threshold}'"
+                )));
+            }
+        }
+
+        crate::private_params::validate_commitments(self)?;
+
+        for path in self.disclosed_fields.iter().flatten() {
+            crate::disclosure::validate_path(path)?;
+        }
+
+        if let Some(predicates) = &self.disclosure_predicates {
+            crate::disclosure::validate_predicates(predicates)?;
+        }
+
+        if let Some(rule) = &self.rule {
+            validate_rule(rule)?;
+        }
+
+        if let Some(composite) = &self.composite {
+            composite.validate()?;
+        }
+
         if self.is_expired() {
             return Err(crate::VceError::SpecExpired(
                 self.expiry.to_rfc3339(),
@@ -120,6 +211,13 @@ impl ComplianceSpec {
     }
 }
 
+/// Check a rule expression parses
+pub(crate) fn validate_rule(rule: &str) -> crate::Result<()> {
+    fuse_rules::compile(rule)
+        .map(|_| ())
+        .map_err(|e| crate::VceError::InvalidSpec(format!("Invalid rule '{rule}': {e}")))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -152,5 +250,40 @@ mod tests {
         assert!(spec.is_expired());
         assert!(spec.validate().is_err());
     }
-}
 
+    #[test]
+    fn test_spec_pass_threshold_validation() {
+        let mut spec = ComplianceSpec::new(
+            "GDPR data residency".to_string(),
+            "abc123".to_string(),
+            BTreeMap::new(),
+            "EU, GDPR".to_string(),
+            "1.0".to_string(),
+            Utc::now() + chrono::Duration::days(365),
+        );
+
+        spec.pass_threshold = Some("99%".to_string());
+        assert!(spec.validate().is_ok());
+
+        spec.pass_threshold = Some("150%".to_string());
+        assert!(spec.validate().is_err());
+    }
+
+    #[test]
+    fn test_spec_rule_validation() {
+        let mut spec = ComplianceSpec::new(
+            "Usage policy".to_string(),
+            "abc123".to_string(),
+            BTreeMap::new(),
+            "US".to_string(),
+            "1.0".to_string(),
+            Utc::now() + chrono::Duration::days(365),
+        );
+
+        spec.rule = Some("count(usage_logs) <= 1000".to_string());
+        assert!(spec.validate().is_ok());
+
+        spec.rule = Some("count(usage_logs) <=".to_string());
+        assert!(spec.validate().is_err());
+    }
+}
@@ -0,0 +1,104 @@
+//! Streamed system data
+//!
+//! For evidence too large for guest memory (e.g. millions of SOC2 access
+//! log lines), the host streams the checker's records to the guest as JSON
+//! Lines in chunks instead of passing one JSON document. The guest hashes
+//! the stream as it arrives and keeps only constant-size tallies, so the
+//! `system_data` evidence digest is over the JSON Lines file as given.
+//!
+//! Each line is one element of the array the checker reads, e.g. one
+//! access log for SOC2. Specs that need the full document (rules,
+//! composites, disclosure, field commitments) cannot be streamed.
+//!
+//! Mirrors the guest program's `stream` module.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use crate::error::{Result, VceError};
+use crate::spec::ComplianceSpec;
+
+/// Bytes sent to the guest per chunk
+pub const CHUNK_BYTES: usize = 1 << 20;
+
+/// Counters a checker keeps over the records it has seen; carried from
+/// one period to the next by incremental proofs
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TallyState {
+    pub records: u64,
+    pub conforming: u64,
+}
+
+/// Key of the record array the spec's checker reads, or `None` if the spec
+/// cannot be streamed
+#[must_use]
+pub fn record_key(spec: &ComplianceSpec) -> Option<&'static str> {
+    if spec.needs_full_document() {
+        return None;
+    }
+
+    // Same precedence as the guest's claim routing
+    let claim = spec.claim.as_str();
+    if claim.contains("JSON parsing only") || claim.contains("C2PA") {
+        None
+    } else if claim.contains("SOC2") {
+        Some("access_logs")
+    } else if claim.contains("GDPR") {
+        Some("storage_locations")
+    } else if claim.contains("Supply chain") || claim.contains("provenance") {
+        Some("components")
+    } else if claim.contains("ML model") || claim.contains("usage constraint") {
+        Some("usage_logs")
+    } else {
+        None
+    }
+}
+
+/// The JSON document the streamed records stand for, for host-side checkers
+pub fn document(spec: &ComplianceSpec, json_lines: &[u8]) -> Result<Value> {
+    let key = record_key(spec).ok_or_else(|| {
+        VceError::InvalidSpec("Only SOC2, GDPR, supply chain and ML model specs without rules, composites, disclosure or field commitments can be streamed".to_string())
+    })?;
+    let records = json_lines
+        .split(|&b| b == b'\n')
+        .filter(|line| !line.iter().all(u8::is_ascii_whitespace))
+        .map(serde_json::from_slice)
+        .collect::<std::result::Result<Vec<Value>, _>>()?;
+    let mut document = Map::new();
+    document.insert(key.to_string(), Value::Array(records));
+    Ok(Value::Object(document))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use std::collections::BTreeMap;
+
+    fn spec(claim: &str) -> ComplianceSpec {
+        ComplianceSpec::new(
+            claim.to_string(),
+            "abc123".to_string(),
+            BTreeMap::new(),
+            "US".to_string(),
+            "1.0".to_string(),
+            Utc::now() + chrono::Duration::days(365),
+        )
+    }
+
+    #[test]
+    fn test_document_collects_lines_under_record_key() {
+        let lines = b"{\"user\": \"alice\"}\r\n\n{\"user\": \"bob\"}";
+        let document = document(&spec("SOC2 control X"), lines).unwrap();
+        assert_eq!(document, serde_json::json!({"access_logs": [{"user": "alice"}, {"user": "bob"}]}));
+    }
+
+    #[test]
+    fn test_full_document_specs_cannot_stream() {
+        assert_eq!(record_key(&spec("C2PA signature")), None);
+        let mut with_rule = spec("GDPR data residency");
+        assert_eq!(record_key(&with_rule), Some("storage_locations"));
+        with_rule.rule = Some("count(storage_locations) > 0".to_string());
+        assert_eq!(record_key(&with_rule), None);
+        assert!(document(&with_rule, b"{}").is_err());
+    }
+}
@@ -0,0 +1,137 @@
+//! Typed binary system data for the guest
+//!
+//! Parsing large log files from JSON text is one of the biggest cycle
+//! costs in the guest. The host can instead pre-parse the system data and
+//! pass the document tree in the zkVM's binary encoding, which the guest
+//! reads without tokenizing any text.
+//!
+//! The encoding is lossless: every field survives and numbers keep their
+//! JSON text, so `true` stays a boolean and nothing outside a checker's
+//! schema is dropped. The guest commits [`TypedSystemData::canonical_json`]
+//! as the `system_data` evidence. That is the supplied document
+//! re-serialized as compact JSON with sorted keys, so an auditor holding
+//! the original file recomputes it with [`canonical_json`].
+//!
+//! Mirrors the guest program's `typed` module; types and field order must match.
+
+use serde::{Deserialize, Serialize, Serializer};
+use serde_json::{Map, Number, Value};
+
+/// A JSON value in the zkVM's binary encoding
+///
+/// `Value` cannot be read from the zkVM's encoding, which is not
+/// self-describing. Numbers keep their JSON text so the round trip
+/// through [`Json::to_value`] is exact.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Number(String),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    /// The JSON value this encodes; `None` if a number's text is not a
+    /// JSON number
+    #[must_use]
+    pub fn to_value(&self) -> Option<Value> {
+        Some(match self {
+            Self::Null => Value::Null,
+            Self::Bool(b) => Value::Bool(*b),
+            Self::Number(text) => Value::Number(text.parse::<Number>().ok()?),
+            Self::String(s) => Value::String(s.clone()),
+            Self::Array(items) => Value::Array(items.iter().map(Self::to_value).collect::<Option<_>>()?),
+            Self::Object(fields) => Value::Object(
+                fields.iter().map(|(key, value)| Some((key.clone(), value.to_value()?))).collect::<Option<Map<_, _>>>()?,
+            ),
+        })
+    }
+}
+
+impl From<&Value> for Json {
+    fn from(value: &Value) -> Self {
+        match value {
+            Value::Null => Self::Null,
+            Value::Bool(b) => Self::Bool(*b),
+            Value::Number(n) => Self::Number(n.to_string()),
+            Value::String(s) => Self::String(s.clone()),
+            Value::Array(items) => Self::Array(items.iter().map(Self::from).collect()),
+            Value::Object(fields) => Self::Object(fields.iter().map(|(key, value)| (key.clone(), Self::from(value))).collect()),
+        }
+    }
+}
+
+/// Pre-parsed system data document
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TypedSystemData(Json);
+
+impl TypedSystemData {
+    /// Typed form of a parsed system data document
+    #[must_use]
+    pub fn from_json(system_data: &Value) -> Self {
+        Self(Json::from(system_data))
+    }
+
+    /// Canonical JSON form the guest commits as the `system_data` evidence
+    #[must_use]
+    pub fn canonical_json(&self) -> String {
+        self.0.to_value().map(|value| to_canonical(&value)).unwrap_or_default()
+    }
+}
+
+/// Canonical form of a system data file, as committed when it is passed
+/// as typed input; `None` if the file is not JSON
+#[must_use]
+pub fn canonical_json(system_data_json: &str) -> Option<String> {
+    serde_json::from_str::<Value>(system_data_json).ok().map(|value| to_canonical(&value))
+}
+
+fn to_canonical(value: &Value) -> String {
+    serde_json::to_string(&Sorted(value)).unwrap_or_default()
+}
+
+/// Serializes a `Value` with every object's keys in sorted order, whether
+/// or not `serde_json` keeps insertion order in this build
+struct Sorted<'a>(&'a Value);
+
+impl Serialize for Sorted<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.0 {
+            Value::Array(items) => serializer.collect_seq(items.iter().map(Sorted)),
+            Value::Object(fields) => {
+                let mut fields: Vec<_> = fields.iter().collect();
+                fields.sort_unstable_by(|a, b| a.0.cmp(b.0));
+                serializer.collect_map(fields.into_iter().map(|(key, value)| (key, Sorted(value))))
+            }
+            scalar => scalar.serialize(serializer),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_canonical_json_keeps_the_whole_document() {
+        let data = json!({"access_logs": [{"user": "alice", "mfa": true, "attempts": 2.5}], "unrelated": 1});
+        let typed = TypedSystemData::from_json(&data);
+        assert_eq!(
+            typed.canonical_json(),
+            r#"{"access_logs":[{"attempts":2.5,"mfa":true,"user":"alice"}],"unrelated":1}"#
+        );
+        assert_eq!(Some(typed.canonical_json()), canonical_json(&data.to_string()));
+    }
+
+    #[test]
+    fn test_binary_encoding_round_trips() {
+        let data = json!({"n": [0, -1, 1e300, 18446744073709551615u64], "s": "\u{1F600}", "null": null});
+        let typed = TypedSystemData::from_json(&data);
+        let bytes = risc0_zkvm::serde::to_vec(&typed).unwrap();
+        let decoded: TypedSystemData = risc0_zkvm::serde::from_slice(&bytes).unwrap();
+        assert_eq!(decoded.0.to_value().unwrap(), data);
+    }
+}
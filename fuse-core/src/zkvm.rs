@@ -1,20 +1,33 @@
 //! RISC Zero zkVM proof generation and verification (1.0+ API)
 
+use crate::aggregate::{AggregatedChild, Aggregation};
+use crate::cache::{CacheKey, ProofCache};
 use crate::error::{VceError, Result};
+use crate::evidence::{self, EvidenceFile};
+use crate::incremental::Incremental;
+use crate::progress::{CancellationToken, ProofObserver, ProofPhase, Progress};
 use crate::proof::JournalOutput;
+use crate::registry::{CheckerId, ImageRegistry};
+use crate::stream;
+use crate::typed::TypedSystemData;
 use risc0_zkvm::{
-    ExecutorEnv, ExecutorImpl, get_prover_server, ProverOpts, ProverServer, Receipt, VerifierContext,
+    ExecutorEnv, ExecutorImpl, get_prover_server, ProverOpts, ProverServer, Receipt, Segment, SegmentRef, Session,
+    VerifierContext,
 };
 use risc0_zkvm::serde::to_vec;
+use risc0_zkvm::sha::{Digest, Digestible};
 use risc0_binfmt::{MemoryImage, Program};
 use bincode;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 use std::rc::Rc;
+use std::sync::Arc;
 
 /// Prover type selection for proof generation
 /// 
 /// Determines which hardware backend to use for proof generation.
 /// GPU option provides significant performance improvements but requires additional setup.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ProverType {
     /// Local CPU prover (default, always available)
     Local,
@@ -22,28 +35,372 @@ pub enum ProverType {
     /// Requires GPU hardware and appropriate drivers
     /// Note: Requires --features gpu to be enabled at build time
     Gpu,
+    /// HTTP proving service at `url`, such as `fuse-prover-server`; the
+    /// receipt is verified locally before it is accepted (see [`crate::remote`]).
+    /// A receipt body over `max_receipt_bytes` is refused unread.
+    /// Note: Requires --features remote to be enabled at build time
+    Remote { url: String, max_receipt_bytes: u64 },
 }
 
-/// Get the guest program ELF binary
-/// Returns None if the guest program hasn't been built yet
-fn get_guest_elf() -> Option<&'static [u8]> {
-    // Point directly to the workspace target directory (most reliable path)
-    // This is where cargo build --target riscv32im-risc0-zkvm-elf places the binary
-    #[cfg(guest_program_built)]
-    {
-        Some(include_bytes!("../../target/riscv32im-risc0-zkvm-elf/release/fuse-guest"))
+/// Default for `ProverType::Remote::max_receipt_bytes`, well above a
+/// succinct receipt (a few hundred KiB) and a composite one of a long session
+pub const DEFAULT_MAX_RECEIPT_BYTES: u64 = 64 << 20;
+
+/// Bounds on one guest execution
+///
+/// Execution is split into segments of at most `2^segment_po2` cycles, each
+/// written to disk as soon as it is complete rather than held in memory.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SessionLimits {
+    /// Stop with [`VceError::SessionLimitExceeded`] once the guest has run
+    /// past this many total cycles (as in [`ExecutionStats::total_cycles`]:
+    /// segment sizes, so paging and padding included); `None` for no limit.
+    /// It is checked as each segment completes, so execution may overrun
+    /// it by up to one segment.
+    pub max_cycles: Option<u64>,
+    /// Segment size as a power of two of cycles, from 13 to 21; smaller
+    /// segments take less memory to prove and make more units of work for
+    /// parallel workers. `None` for the default (20).
+    pub segment_po2: Option<u32>,
+    /// Directory to write segments under, in a fresh subdirectory removed
+    /// once proving is done; `None` for a system temporary directory
+    pub segment_dir: Option<PathBuf>,
+}
+
+/// Smallest and largest segment sizes the prover accepts
+const SEGMENT_PO2_RANGE: std::ops::RangeInclusive<u32> = 13..=21;
+
+/// How to prove a session
+#[derive(Debug, Clone)]
+pub struct ProofOptions {
+    /// Hardware backend for proving
+    pub prover_type: ProverType,
+    /// Worker threads proving segments in parallel (see [`crate::parallel`]);
+    /// 1 proves them one after another
+    pub workers: usize,
+    /// Bounds on the guest execution being proved
+    pub limits: SessionLimits,
+    /// Reuse proofs of identical inputs from this cache and store new ones
+    /// there (see [`crate::cache`]); `None` to always prove
+    pub cache: Option<ProofCache>,
+    /// Told each phase of the proof as it happens (see [`crate::progress`])
+    pub observer: Option<Arc<dyn ProofObserver>>,
+    /// Stops the proof at the next segment boundary once cancelled
+    pub cancellation: Option<CancellationToken>,
+}
+
+impl ProofOptions {
+    /// Serial proving on the given backend with default segments and no
+    /// cycle limit
+    #[must_use]
+    pub fn new(prover_type: ProverType) -> Self {
+        Self { prover_type, workers: 1, limits: SessionLimits::default(), cache: None, observer: None, cancellation: None }
     }
-    
-    #[cfg(not(guest_program_built))]
-    {
-        None
+}
+
+/// Segment subdirectory of [`SessionLimits::segment_dir`], removed when
+/// dropped
+struct SegmentDir(PathBuf);
+
+impl Drop for SegmentDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+/// A segment written to a [`SegmentDir`] as execution produces it
+struct DiskSegment(PathBuf);
+
+impl SegmentRef for DiskSegment {
+    fn resolve(&self) -> anyhow::Result<Segment> {
+        Ok(bincode::deserialize(&std::fs::read(&self.0)?)?)
+    }
+}
+
+/// Total cycles of the segments executed so far, against
+/// [`SessionLimits::max_cycles`]
+#[derive(Debug, Default)]
+struct CycleBudget {
+    max_cycles: Option<u64>,
+    used: u64,
+}
+
+/// Raised from the segment callback to stop a session past its limit, and
+/// recognised by type when execution fails
+#[derive(Debug)]
+struct CycleLimitReached;
+
+impl std::fmt::Display for CycleLimitReached {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "cycle limit reached")
+    }
+}
+
+impl std::error::Error for CycleLimitReached {}
+
+impl CycleBudget {
+    /// Count a completed segment of `2^po2` cycles
+    fn spend(&mut self, po2: usize) -> anyhow::Result<()> {
+        self.used = self.used.saturating_add(1 << po2);
+        match self.max_cycles {
+            Some(max_cycles) if self.used > max_cycles => Err(CycleLimitReached.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl From<ProverType> for ProofOptions {
+    fn from(prover_type: ProverType) -> Self {
+        Self::new(prover_type)
+    }
+}
+
+/// Inputs passed to the guest program
+#[derive(Debug, Clone)]
+pub struct ProofInputs<'a> {
+    /// What to prove, and the system data to prove it over
+    pub mode: ProofMode<'a>,
+    /// Private seed for per-field commitment salts. Never committed; keep it
+    /// to reveal fields against the committed root later.
+    pub salt_seed: [u8; 32],
+    /// JSON object of private spec parameters (`PrivateParams`); empty when
+    /// the spec has no `private_constraints`
+    pub private_params_json: &'a str,
+    /// Extra evidence files committed by digest alongside the spec and
+    /// system data; not read by the checkers
+    pub evidence_files: &'a [EvidenceFile],
+}
+
+impl<'a> ProofInputs<'a> {
+    /// Inputs checking one spec against JSON system data, with a freshly
+    /// generated random salt seed
+    #[must_use]
+    pub fn new(spec_json: &'a str, system_data_json: &'a str) -> Self {
+        Self::with_mode(ProofMode::Single { spec_json, system_data_json, parse_full_json: false })
+    }
+
+    /// Inputs proving `mode`, with a freshly generated random salt seed
+    #[must_use]
+    pub fn with_mode(mode: ProofMode<'a>) -> Self {
+        Self { mode, salt_seed: rand::random(), private_params_json: "", evidence_files: &[] }
+    }
+}
+
+/// What a proof checks, and the system data it checks it over
+///
+/// Each spec is a JSON string; the guest commits it byte for byte, so pass
+/// [`ComplianceSpec::to_proof_json`](crate::ComplianceSpec::to_proof_json)
+/// for the proof to verify in an envelope.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub enum ProofMode<'a> {
+    /// One spec against JSON system data
+    Single {
+        spec_json: &'a str,
+        system_data_json: &'a str,
+        /// Build the full `serde_json::Value` tree of the system data even
+        /// when the checker can read its fields directly; for benchmarking
+        parse_full_json: bool,
+    },
+    /// One spec against named JSON documents that together form the system
+    /// data, each committed under its name (see [`evidence::bundle_document`])
+    Bundle { spec_json: &'a str, documents: &'a [EvidenceFile] },
+    /// One spec against system data pre-parsed on the host, passed in
+    /// binary form to skip JSON parsing in the guest
+    Typed { spec_json: &'a str, system_data: &'a TypedSystemData },
+    /// Several specs against the same system data in one proof; the journal
+    /// holds a result per spec in this order
+    Batch { spec_jsons: &'a [String], system_data: BatchData<'a> },
+    /// One spec against JSON Lines of the checker's records, streamed to
+    /// the guest in chunks (see [`stream`])
+    Stream { spec_json: &'a str, records: &'a [u8] },
+    /// A record stream that commits a cumulative log state, so later
+    /// periods can continue from this proof (see [`crate::incremental`])
+    Incremental {
+        spec_json: &'a str,
+        records: &'a [u8],
+        /// Serialized receipt of the previous period's incremental proof,
+        /// whose state these records continue; `None` for the first period
+        previous_receipt: Option<&'a [u8]>,
+    },
+    /// Serialized receipts of child proofs (an envelope's `proof_data`)
+    /// aggregated under the spec; each is added as an assumption the guest
+    /// verifies (see [`crate::aggregate`])
+    Aggregate { spec_json: &'a str, child_receipts: &'a [Vec<u8>] },
+}
+
+impl<'a> ProofMode<'a> {
+    /// The spec being proved; `None` for a batch
+    #[must_use]
+    pub fn spec_json(&self) -> Option<&'a str> {
+        match *self {
+            Self::Single { spec_json, .. }
+            | Self::Bundle { spec_json, .. }
+            | Self::Typed { spec_json, .. }
+            | Self::Stream { spec_json, .. }
+            | Self::Incremental { spec_json, .. }
+            | Self::Aggregate { spec_json, .. } => Some(spec_json),
+            Self::Batch { .. } => None,
+        }
+    }
+}
+
+/// System data a batch of specs is checked against
+#[derive(Debug, Clone, Copy, Serialize)]
+pub enum BatchData<'a> {
+    Json(&'a str),
+    Bundle(&'a [EvidenceFile]),
+}
+
+/// [`ProofInputs`] owning their data, to outlive the caller while queued
+/// in a [`crate::pool::ProverPool`] or to send to a remote prover
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OwnedProofInputs {
+    pub mode: OwnedProofMode,
+    pub salt_seed: [u8; 32],
+    pub private_params_json: String,
+    pub evidence_files: Vec<EvidenceFile>,
+}
+
+/// [`ProofMode`] owning its data
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum OwnedProofMode {
+    Single { spec_json: String, system_data_json: String, parse_full_json: bool },
+    Bundle { spec_json: String, documents: Vec<EvidenceFile> },
+    Typed { spec_json: String, system_data: TypedSystemData },
+    Batch { spec_jsons: Vec<String>, system_data: OwnedBatchData },
+    Stream { spec_json: String, records: Vec<u8> },
+    Incremental { spec_json: String, records: Vec<u8>, previous_receipt: Option<Vec<u8>> },
+    Aggregate { spec_json: String, child_receipts: Vec<Vec<u8>> },
+}
+
+/// [`BatchData`] owning its data
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum OwnedBatchData {
+    Json(String),
+    Bundle(Vec<EvidenceFile>),
+}
+
+impl OwnedProofInputs {
+    #[must_use]
+    pub fn as_inputs(&self) -> ProofInputs<'_> {
+        let mode = match &self.mode {
+            OwnedProofMode::Single { spec_json, system_data_json, parse_full_json } => {
+                ProofMode::Single { spec_json, system_data_json, parse_full_json: *parse_full_json }
+            }
+            OwnedProofMode::Bundle { spec_json, documents } => ProofMode::Bundle { spec_json, documents },
+            OwnedProofMode::Typed { spec_json, system_data } => ProofMode::Typed { spec_json, system_data },
+            OwnedProofMode::Batch { spec_jsons, system_data } => ProofMode::Batch {
+                spec_jsons,
+                system_data: match system_data {
+                    OwnedBatchData::Json(json) => BatchData::Json(json),
+                    OwnedBatchData::Bundle(documents) => BatchData::Bundle(documents),
+                },
+            },
+            OwnedProofMode::Stream { spec_json, records } => ProofMode::Stream { spec_json, records },
+            OwnedProofMode::Incremental { spec_json, records, previous_receipt } => {
+                ProofMode::Incremental { spec_json, records, previous_receipt: previous_receipt.as_deref() }
+            }
+            OwnedProofMode::Aggregate { spec_json, child_receipts } => ProofMode::Aggregate { spec_json, child_receipts },
+        };
+        ProofInputs {
+            mode,
+            salt_seed: self.salt_seed,
+            private_params_json: &self.private_params_json,
+            evidence_files: &self.evidence_files,
+        }
+    }
+}
+
+impl From<&ProofInputs<'_>> for OwnedProofInputs {
+    fn from(inputs: &ProofInputs<'_>) -> Self {
+        let mode = match inputs.mode {
+            ProofMode::Single { spec_json, system_data_json, parse_full_json } => OwnedProofMode::Single {
+                spec_json: spec_json.to_string(),
+                system_data_json: system_data_json.to_string(),
+                parse_full_json,
+            },
+            ProofMode::Bundle { spec_json, documents } => {
+                OwnedProofMode::Bundle { spec_json: spec_json.to_string(), documents: documents.to_vec() }
+            }
+            ProofMode::Typed { spec_json, system_data } => {
+                OwnedProofMode::Typed { spec_json: spec_json.to_string(), system_data: system_data.clone() }
+            }
+            ProofMode::Batch { spec_jsons, system_data } => OwnedProofMode::Batch {
+                spec_jsons: spec_jsons.to_vec(),
+                system_data: match system_data {
+                    BatchData::Json(json) => OwnedBatchData::Json(json.to_string()),
+                    BatchData::Bundle(documents) => OwnedBatchData::Bundle(documents.to_vec()),
+                },
+            },
+            ProofMode::Stream { spec_json, records } => {
+                OwnedProofMode::Stream { spec_json: spec_json.to_string(), records: records.to_vec() }
+            }
+            ProofMode::Incremental { spec_json, records, previous_receipt } => OwnedProofMode::Incremental {
+                spec_json: spec_json.to_string(),
+                records: records.to_vec(),
+                previous_receipt: previous_receipt.map(<[u8]>::to_vec),
+            },
+            ProofMode::Aggregate { spec_json, child_receipts } => {
+                OwnedProofMode::Aggregate { spec_json: spec_json.to_string(), child_receipts: child_receipts.to_vec() }
+            }
+        };
+        Self {
+            mode,
+            salt_seed: inputs.salt_seed,
+            private_params_json: inputs.private_params_json.to_string(),
+            evidence_files: inputs.evidence_files.to_vec(),
+        }
+    }
+}
+
+/// Image ID of `checker`'s guest program, which its proofs are verified
+/// against
+pub fn image_id(checker: CheckerId) -> Result<Digest> {
+    match ImageRegistry::built().image_id(checker) {
+        Some(image_id) => Ok(image_id),
+        // Not built, or not loadable; either way the error says why
+        None => compute_image_id(guest_elf(checker)?),
+    }
+}
+
+/// `checker`'s guest program ELF binary, or an error explaining how to
+/// build it
+fn guest_elf(checker: CheckerId) -> Result<&'static [u8]> {
+    get_guest_elf(checker).ok_or_else(|| {
+        VceError::ProofGenerationFailed(format!(
+            "Guest program {} not found. To build the guest programs, run: scripts/build-guests.sh",
+            checker.guest()
+        ))
+    })
+}
+
+/// Get `checker`'s guest program ELF binary
+/// Returns None if that guest program hasn't been built yet
+pub(crate) fn get_guest_elf(checker: CheckerId) -> Option<&'static [u8]> {
+    // Point directly to the workspace target directory (most reliable path)
+    // This is where cargo build --target riscv32im-risc0-zkvm-elf places the binaries
+    match checker {
+        #[cfg(guest_built = "general")]
+        CheckerId::General => Some(include_bytes!("../../target/riscv32im-risc0-zkvm-elf/release/fuse-guest")),
+        #[cfg(guest_built = "soc2")]
+        CheckerId::Soc2 => Some(include_bytes!("../../target/riscv32im-risc0-zkvm-elf/release/fuse-guest-soc2")),
+        #[cfg(guest_built = "gdpr")]
+        CheckerId::Gdpr => Some(include_bytes!("../../target/riscv32im-risc0-zkvm-elf/release/fuse-guest-gdpr")),
+        #[cfg(guest_built = "supply-chain")]
+        CheckerId::SupplyChain => Some(include_bytes!("../../target/riscv32im-risc0-zkvm-elf/release/fuse-guest-supply-chain")),
+        #[cfg(guest_built = "ml-model")]
+        CheckerId::MlModel => Some(include_bytes!("../../target/riscv32im-risc0-zkvm-elf/release/fuse-guest-ml-model")),
+        #[cfg(guest_built = "c2pa")]
+        CheckerId::C2pa => Some(include_bytes!("../../target/riscv32im-risc0-zkvm-elf/release/fuse-guest-c2pa")),
+        #[allow(unreachable_patterns)]
+        _ => None,
     }
 }
 
 /// Compute image ID from ELF binary for verification
 /// The image ID is a cryptographic hash of the guest program and is used to verify
 /// that proofs were generated by the expected code.
-fn compute_image_id(elf: &[u8]) -> Result<risc0_zkvm::sha::Digest> {
+pub(crate) fn compute_image_id(elf: &[u8]) -> Result<Digest> {
     use risc0_zkvm_platform::{PAGE_SIZE, memory::GUEST_MAX_MEM};
     let program = Program::load_elf(elf, GUEST_MAX_MEM as u32)
         .map_err(|e| VceError::RiscZero(format!("Failed to load ELF binary: {e}. The ELF may be corrupted or not a valid RISC Zero guest program.")))?;
@@ -53,7 +410,7 @@ fn compute_image_id(elf: &[u8]) -> Result<risc0_zkvm::sha::Digest> {
 }
 
 /// Get prover server based on prover type
-fn get_prover_for_type(prover_type: ProverType) -> Result<Rc<dyn ProverServer>> {
+pub(crate) fn get_prover_for_type(prover_type: &ProverType) -> Result<Rc<dyn ProverServer>> {
     let opts = match prover_type {
         ProverType::Local => {
             ProverOpts::default()
@@ -72,6 +429,9 @@ fn get_prover_for_type(prover_type: ProverType) -> Result<Rc<dyn ProverServer>>
                 ));
             }
         }
+        ProverType::Remote { url, .. } => {
+            return Err(VceError::ProofGenerationFailed(format!("No local prover for the remote prover at {url}")));
+        }
     };
     
     get_prover_server(&opts)
@@ -80,41 +440,153 @@ fn get_prover_for_type(prover_type: ProverType) -> Result<Rc<dyn ProverServer>>
 
 /// Generate a RISC Zero proof for a compliance check
 /// 
+/// The proof commits to `spec_json` byte for byte. For the proof to verify
+/// in a [`VerifiableComplianceEnvelope`](crate::VerifiableComplianceEnvelope),
+/// pass [`ComplianceSpec::to_proof_json`](crate::ComplianceSpec::to_proof_json)
+/// rather than the spec file as written.
+///
 /// # Arguments
 /// * `spec_json` - JSON string of the compliance specification
 /// * `system_data_json` - JSON string of the system data to verify
-/// * `prover_type` - Type of prover to use (Local or Gpu)
+/// * `options` - Type of prover to use (Local or Gpu), or full [`ProofOptions`]
 /// 
 /// # Returns
 /// A tuple of (serialized receipt, `journal_output`, `journal_bytes`)
 pub fn generate_proof(
     spec_json: &str,
     system_data_json: &str,
-    prover_type: ProverType,
+    options: impl Into<ProofOptions>,
 ) -> Result<(Vec<u8>, JournalOutput, Vec<u8>)> {
-    // Get guest program ELF binary
-    let guest_elf = get_guest_elf().ok_or_else(|| {
-        VceError::ProofGenerationFailed(
-            "Guest program ELF binary not found. To build it, run: RUSTC=\"$HOME/.risc0/toolchains/v1.91.1-rust-aarch64-apple-darwin/bin/rustc\" cargo build -p fuse-guest --release --target riscv32im-risc0-zkvm-elf".to_string()
-        )
-    })?;
+    generate_with_fresh_seed(ProofInputs::new(spec_json, system_data_json), options.into())
+}
+
+/// Generate one RISC Zero proof checking several specs against the same
+/// system data
+///
+/// The guest parses the system data once and runs each spec's checker over
+/// it. The journal's `batch` holds a result per spec, in order; narrow the
+/// proof to one with [`ComplianceProof::with_batch_entry`](crate::ComplianceProof::with_batch_entry).
+/// As with [`generate_proof`], pass each spec's
+/// [`to_proof_json`](crate::ComplianceSpec::to_proof_json) form.
+pub fn generate_batch_proof(
+    spec_jsons: &[String],
+    system_data_json: &str,
+    options: impl Into<ProofOptions>,
+) -> Result<(Vec<u8>, JournalOutput, Vec<u8>)> {
+    let inputs = ProofInputs::with_mode(ProofMode::Batch { spec_jsons, system_data: BatchData::Json(system_data_json) });
+    generate_with_fresh_seed(inputs, options.into())
+}
+
+/// Prove with a salt seed the caller never sees, so a cached proof of the
+/// same inputs serves whatever seed it was proved with
+fn generate_with_fresh_seed(mut inputs: ProofInputs<'_>, options: ProofOptions) -> Result<(Vec<u8>, JournalOutput, Vec<u8>)> {
+    if let Some(cache) = &options.cache {
+        cache.reuse_seed(&mut inputs, &options);
+    }
+    generate_proof_with_inputs(&inputs, options)
+}
+
+/// Cycle counts of one guest execution
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExecutionStats {
+    /// Cycles spent executing guest instructions
+    pub user_cycles: u64,
+    /// All cycles, including paging and padding; what proving time scales with
+    pub total_cycles: u64,
+    /// Number of segments the execution was split into
+    pub segments: usize,
+}
+
+/// Execute the guest program without proving, to measure its cycle counts
+pub fn execute(inputs: &ProofInputs<'_>) -> Result<(JournalOutput, ExecutionStats)> {
+    execute_with_limits(inputs, &SessionLimits::default())
+}
+
+/// Execute the guest program without proving within the given limits
+pub fn execute_with_limits(inputs: &ProofInputs<'_>, limits: &SessionLimits) -> Result<(JournalOutput, ExecutionStats)> {
+    let (session, _segments) = execute_session(inputs, limits)?;
+    let stats = ExecutionStats {
+        user_cycles: session.user_cycles,
+        total_cycles: session.total_cycles,
+        segments: session.segments.len(),
+    };
+    let journal_output: JournalOutput = session.journal
+        .ok_or_else(|| VceError::GuestProgramExecution("Guest program did not commit a journal".to_string()))?
+        .decode()
+        .map_err(|e| VceError::RiscZero(format!("Failed to decode JournalOutput from journal: {e}")))?;
+    Ok((journal_output, stats))
+}
+
+/// Generate a RISC Zero proof from explicit guest inputs
+///
+/// Use this instead of [`generate_proof`] to choose the salt seed, e.g. to
+/// keep it for revealing committed fields later. Pass [`ProofOptions`] in
+/// place of the prover type to prove segments in parallel or through a
+/// cache, to observe its progress or to be able to cancel it; call
+/// [`ProofCache::reuse_seed`] first for a cached proof to match.
+pub fn generate_proof_with_inputs(
+    inputs: &ProofInputs<'_>,
+    options: impl Into<ProofOptions>,
+) -> Result<(Vec<u8>, JournalOutput, Vec<u8>)> {
+    let options = options.into();
+    let progress = Progress::new(options.observer.clone(), options.cancellation.clone());
+    progress.check()?;
+    // Dev-mode receipts are not real proofs, so they are neither reused nor kept
+    let cached = match &options.cache {
+        Some(cache) if !risc0_zkvm::is_dev_mode() => Some((cache, CacheKey::new(inputs, &options, image_id(CheckerId::for_inputs(inputs))?)?)),
+        _ => None,
+    };
+    if let Some((cache, key)) = &cached {
+        if let Some(proof) = cache.get(key, inputs) {
+            tracing::info!(key = crate::cache::short(&key.entry), "Reusing cached proof");
+            progress.report(ProofPhase::Done);
+            return Ok(proof);
+        }
+    }
+    let receipt = match &options.prover_type {
+        #[cfg(feature = "remote")]
+        ProverType::Remote { url, max_receipt_bytes } => crate::remote::prove(url, *max_receipt_bytes, inputs, &options, &progress)?,
+        #[cfg(not(feature = "remote"))]
+        ProverType::Remote { .. } => {
+            return Err(VceError::ProofGenerationFailed(
+                "Remote proving requested but 'remote' feature is not enabled. Build with --features remote".to_string()
+            ));
+        }
+        _ => prove_locally(inputs, &options, &progress)?,
+    };
     
-    // Prepare environment with inputs (1.0+ API: use .write_slice())
-    let env = ExecutorEnv::builder()
-        .write_slice(&to_vec(spec_json)
-            .map_err(|e| VceError::InputSerialization(format!("Failed to serialize compliance spec to JSON: {e}")))?)
-        .write_slice(&to_vec(system_data_json)
-            .map_err(|e| VceError::InputSerialization(format!("Failed to serialize system data to JSON: {e}")))?)
-        .build()
-        .map_err(|e| VceError::GuestProgramExecution(format!("Failed to build executor environment: {e}. This may indicate an issue with input data format.")))?;
+    // Extract journal bytes (public outputs)
+    let journal_bytes = receipt.journal.bytes.clone();
     
-    // Execute the guest program
-    let mut exec = ExecutorImpl::from_elf(env, guest_elf)
-        .map_err(|e| VceError::GuestProgramExecution(format!("Failed to create executor from guest ELF: {e}. The guest program may be corrupted or incompatible.")))?;
+    // Extract output from journal using decode (1.0+ API)
+    let journal_output: JournalOutput = receipt.journal.decode()
+        .map_err(|e| VceError::RiscZero(format!("Failed to decode JournalOutput from journal: {e}. The guest program may not have committed the result correctly.")))?;
     
-    let session = exec.run()
-        .map_err(|e| VceError::GuestProgramExecution(format!("Guest program execution failed: {e}. Check that inputs are valid JSON and guest program logic is correct.")))?;
+    // Serialize receipt for storage
+    let receipt_bytes = bincode::serialize(&receipt)
+        .map_err(|e| VceError::RiscZero(format!("Failed to serialize receipt for storage: {e}")))?;
+
+    if let Some((cache, key)) = &cached {
+        // A proof is still good without its cache entry
+        if let Err(e) = cache.put(key, &inputs.salt_seed, &receipt_bytes, journal_output.result) {
+            tracing::warn!("Could not cache proof: {e}");
+        }
+    }
+    progress.report(ProofPhase::Done);
     
+    Ok((receipt_bytes, journal_output, journal_bytes))
+}
+
+/// Execute and prove on this machine
+fn prove_locally(inputs: &ProofInputs<'_>, options: &ProofOptions, progress: &Progress) -> Result<Receipt> {
+    let ProofOptions { prover_type, workers, limits, .. } = options;
+    let workers = *workers;
+    // Segments stay on disk until proving is done
+    progress.report(ProofPhase::Executing);
+    let (mut session, _segments) = execute_session(inputs, limits)?;
+    progress.report(ProofPhase::Executed { segments: session.segments.len(), total_cycles: session.total_cycles });
+    progress.check()?;
+
     // Get prover server based on requested type
     // Note: Real proof generation can take 10-20+ minutes. Use RISC0_DEV_MODE=1 for faster testing.
     // GPU proving can reduce this significantly (5-10x faster).
@@ -123,36 +595,204 @@ pub fn generate_proof(
     // Log prover type being used
     match prover_type {
         ProverType::Local => {
-            println!("   Using local CPU prover");
+            tracing::info!("Using local CPU prover");
         }
         ProverType::Gpu => {
             #[cfg(feature = "gpu")]
-            println!("   Using local GPU prover (CUDA/Metal)");
+            tracing::info!("Using local GPU prover (CUDA/Metal)");
             #[cfg(not(feature = "gpu"))]
-            println!("   GPU prover requested but feature not enabled");
+            tracing::warn!("GPU prover requested but feature not enabled");
         }
+        ProverType::Remote { .. } => {}
     }
     
-    // Generate proof (this is the computationally expensive step)
-    let ctx = VerifierContext::default();
-    let receipt = prover.prove_session(&ctx, &session)
-        .map_err(|e| VceError::ProofGenerationFailed(format!("RISC Zero proof generation failed: {e}. This step can take 10-20+ minutes for real proofs. For testing, use RISC0_DEV_MODE=1.")))?;
-    
-    // Extract journal bytes (public outputs)
-    let journal_bytes = receipt.receipt.journal.bytes.clone();
+    // Generate proof (this is the computationally expensive step). Dev mode
+    // fakes the whole session at once, so there is nothing to parallelize.
+    let receipt = if risc0_zkvm::is_dev_mode() {
+        let ctx = VerifierContext::default();
+        prover.prove_session(&ctx, &session)
+            .map_err(|e| VceError::ProofGenerationFailed(format!("RISC Zero proof generation failed: {e}. This step can take 10-20+ minutes for real proofs. For testing, use RISC0_DEV_MODE=1.")))?
+            .receipt
+    } else {
+        tracing::info!(segments = session.segments.len(), workers, "Proving segments");
+        crate::parallel::prove_session(&mut session, prover_type, workers, progress)?
+    };
+
+    // Resolve the child assumptions so the aggregate verifies on its own
+    // (and can itself be aggregated or continued); a receipt joined in
+    // parallel is already succinct and resolved
+    if matches!(inputs.mode, ProofMode::Aggregate { .. } | ProofMode::Incremental { previous_receipt: Some(_), .. }) {
+        progress.report(ProofPhase::Joining);
+        return prover.compress(&ProverOpts::succinct(), &receipt)
+            .map_err(|e| VceError::ProofGenerationFailed(format!("Failed to resolve aggregated child proofs: {e}")));
+    }
+    Ok(receipt)
+}
+
+/// Write records in chunks: a `u32` length, then the bytes padded to a
+/// whole word; a zero length ends the stream
+fn write_stream(builder: &mut risc0_zkvm::ExecutorEnvBuilder<'_>, records: &[u8]) -> Result<()> {
+    let length = |len: usize| {
+        let len = u32::try_from(len)
+            .map_err(|_| VceError::InputSerialization("Stream chunk too large".to_string()))?;
+        to_vec(&len).map_err(|e| VceError::InputSerialization(format!("Failed to serialize stream chunk length: {e}")))
+    };
+    for chunk in records.chunks(stream::CHUNK_BYTES) {
+        let mut padded = chunk.to_vec();
+        padded.resize(chunk.len().next_multiple_of(4), 0);
+        builder.write_slice(&length(chunk.len())?).write_slice(&padded);
+    }
+    builder.write_slice(&length(0)?);
+    Ok(())
+}
+
+/// [`ProofMode`] as the guest program reads it, with child and previous
+/// period receipts reduced to the journals it verifies; streamed records
+/// follow the other inputs
+///
+/// Mirrors the guest program's `ProofMode`; variants and field order must
+/// match.
+#[derive(Debug, Serialize)]
+enum GuestMode<'a> {
+    Single { spec_json: &'a str, system_data_json: &'a str, parse_full_json: bool },
+    Bundle { spec_json: &'a str, documents: &'a [EvidenceFile] },
+    Typed { spec_json: &'a str, system_data: &'a TypedSystemData },
+    Batch { spec_jsons: &'a [String], system_data: BatchData<'a> },
+    Stream { spec_json: &'a str },
+    Incremental { spec_json: &'a str, previous: Incremental },
+    Aggregate { spec_json: &'a str, aggregation: Aggregation },
+}
+
+/// Run the guest program over the inputs
+fn execute_session(inputs: &ProofInputs<'_>, limits: &SessionLimits) -> Result<(Session, SegmentDir)> {
+    // Get the guest program ELF binary for the spec's checker
+    let checker = CheckerId::for_inputs(inputs);
+    let guest_elf = guest_elf(checker)?;
     
-    // Extract output from journal using decode (1.0+ API)
-    let journal_output: JournalOutput = receipt.receipt.journal.decode()
-        .map_err(|e| VceError::RiscZero(format!("Failed to decode JournalOutput from journal: {e}. The guest program may not have committed the result correctly.")))?;
+    let documents = match inputs.mode {
+        ProofMode::Bundle { documents, .. } | ProofMode::Batch { system_data: BatchData::Bundle(documents), .. } => {
+            // The bundle stands in for the system data commitment
+            if documents.is_empty() {
+                return Err(VceError::InputSerialization("An evidence bundle needs at least one document".to_string()));
+            }
+            documents
+        }
+        _ => &[],
+    };
+    evidence::validate_files(documents.iter().chain(inputs.evidence_files))?;
+
+    // Prepare environment with inputs (1.0+ API: use .write_slice())
+    let mut builder = ExecutorEnv::builder();
+    let mode = match inputs.mode {
+        ProofMode::Single { spec_json, system_data_json, parse_full_json } => GuestMode::Single { spec_json, system_data_json, parse_full_json },
+        ProofMode::Bundle { spec_json, documents } => GuestMode::Bundle { spec_json, documents },
+        ProofMode::Typed { spec_json, system_data } => GuestMode::Typed { spec_json, system_data },
+        ProofMode::Batch { spec_jsons, system_data } => {
+            if spec_jsons.is_empty() {
+                return Err(VceError::InputSerialization("A batch needs at least one spec".to_string()));
+            }
+            GuestMode::Batch { spec_jsons, system_data }
+        }
+        ProofMode::Stream { spec_json, .. } => GuestMode::Stream { spec_json },
+        ProofMode::Incremental { spec_json, previous_receipt, .. } => {
+            let mut previous = Incremental { image_id: [0; 8], previous_journal: Vec::new() };
+            if let Some(receipt_bytes) = previous_receipt {
+                let receipt: Receipt = bincode::deserialize(receipt_bytes)
+                    .map_err(|e| VceError::ReceiptDeserialization(format!("Failed to deserialize previous period receipt: {e}")))?;
+                // Previous periods must come from this same guest program
+                previous.image_id.copy_from_slice(compute_image_id(guest_elf)?.as_words());
+                previous.previous_journal = receipt.journal.bytes.clone();
+                builder.add_assumption(receipt);
+            }
+            GuestMode::Incremental { spec_json, previous }
+        }
+        ProofMode::Aggregate { spec_json, child_receipts } => {
+            if child_receipts.is_empty() {
+                return Err(VceError::InputSerialization("An aggregate needs at least one child proof".to_string()));
+            }
+            let mut aggregation = Aggregation { children: Vec::with_capacity(child_receipts.len()) };
+            for receipt_bytes in child_receipts {
+                let receipt: Receipt = bincode::deserialize(receipt_bytes)
+                    .map_err(|e| VceError::ReceiptDeserialization(format!("Failed to deserialize child receipt: {e}")))?;
+                // Each child is verified against the guest program that proved it,
+                // which verifiers will only accept if it is a known one
+                let image_id = receipt_image_id(&receipt)?;
+                if ImageRegistry::built().checker(&image_id).is_none() {
+                    return Err(VceError::ProofGenerationFailed(format!(
+                        "Child proof is from an unknown guest program (image ID {image_id})"
+                    )));
+                }
+                let mut child = AggregatedChild { image_id: [0; 8], journal: receipt.journal.bytes.clone() };
+                child.image_id.copy_from_slice(image_id.as_words());
+                aggregation.children.push(child);
+                builder.add_assumption(receipt);
+            }
+            GuestMode::Aggregate { spec_json, aggregation }
+        }
+    };
+    builder
+        .write_slice(&to_vec(&mode)
+            .map_err(|e| VceError::InputSerialization(format!("Failed to serialize proof mode: {e}")))?)
+        .write_slice(&to_vec(&inputs.salt_seed)
+            .map_err(|e| VceError::InputSerialization(format!("Failed to serialize salt seed: {e}")))?)
+        .write_slice(&to_vec(inputs.private_params_json)
+            .map_err(|e| VceError::InputSerialization(format!("Failed to serialize private parameters: {e}")))?)
+        .write_slice(&to_vec(inputs.evidence_files)
+            .map_err(|e| VceError::InputSerialization(format!("Failed to serialize evidence files: {e}")))?);
+    if let ProofMode::Stream { records, .. } | ProofMode::Incremental { records, .. } = inputs.mode {
+        write_stream(&mut builder, records)?;
+    }
+    if let Some(po2) = limits.segment_po2 {
+        if !SEGMENT_PO2_RANGE.contains(&po2) {
+            return Err(VceError::InputSerialization(format!(
+                "Segment size 2^{po2} is outside 2^{} to 2^{} cycles",
+                SEGMENT_PO2_RANGE.start(),
+                SEGMENT_PO2_RANGE.end()
+            )));
+        }
+        builder.segment_limit_po2(po2);
+    }
+    let parent = limits.segment_dir.clone().unwrap_or_else(std::env::temp_dir);
+    let segment_dir = SegmentDir(parent.join(format!("fuse-segments-{:016x}", rand::random::<u64>())));
+    std::fs::create_dir_all(&segment_dir.0)?;
+    let env = builder
+        .build()
+        .map_err(|e| VceError::GuestProgramExecution(format!("Failed to build executor environment: {e}. This may indicate an issue with input data format.")))?;
     
-    // Serialize receipt for storage
-    let receipt_bytes = bincode::serialize(&receipt.receipt)
-        .map_err(|e| VceError::RiscZero(format!("Failed to serialize receipt for storage: {e}")))?;
+    // Execute the guest program
+    let mut exec = ExecutorImpl::from_elf(env, guest_elf)
+        .map_err(|e| VceError::GuestProgramExecution(format!("Failed to create executor from guest ELF: {e}. The guest program may be corrupted or incompatible.")))?;
     
-    Ok((receipt_bytes, journal_output, journal_bytes))
+    // Segments go to disk as they complete, and the cycle limit is
+    // enforced here rather than by the executor so that hitting it is an
+    // error of our own type
+    let mut budget = CycleBudget { max_cycles: limits.max_cycles, used: 0 };
+    let session = exec
+        .run_with_callback(|segment| {
+            budget.spend(segment.po2())?;
+            let path = segment_dir.0.join(format!("{}.bincode", segment.index));
+            std::fs::write(&path, bincode::serialize(&segment)?)?;
+            Ok(Box::new(DiskSegment(path)))
+        })
+        .map_err(|e| match limits.max_cycles {
+            Some(max_cycles) if e.is::<CycleLimitReached>() => VceError::SessionLimitExceeded(format!(
+                "guest ran past {max_cycles} cycles; raise the limit or check the system data is what you meant to prove"
+            )),
+            _ => VceError::GuestProgramExecution(format!("Guest program execution failed: {e}. Check that inputs are valid JSON and guest program logic is correct.")),
+        })?;
+    Ok((session, segment_dir))
+}
+
+/// Image ID of the guest program a receipt claims to have run
+fn receipt_image_id(receipt: &Receipt) -> Result<Digest> {
+    let claim = receipt.claim()
+        .map_err(|e| VceError::ProofVerificationFailed(format!("Receipt has no readable claim: {e}")))?;
+    let claim = claim.as_value()
+        .map_err(|e| VceError::ProofVerificationFailed(format!("Receipt claim is pruned: {e}")))?;
+    Ok(claim.pre.digest())
 }
 
-/// Verify a RISC Zero proof
+/// Verify a RISC Zero proof against the guest programs built into this binary
 /// 
 /// # Arguments
 /// * `receipt_bytes` - Serialized RISC Zero receipt
@@ -160,24 +800,31 @@ pub fn generate_proof(
 /// # Returns
 /// A tuple of (`journal_output`, `journal_bytes`) if verification succeeds
 pub fn verify_proof(receipt_bytes: &[u8]) -> Result<(JournalOutput, Vec<u8>)> {
+    verify_receipt(receipt_bytes, ImageRegistry::built()).map(|(_, journal_output, journal_bytes)| (journal_output, journal_bytes))
+}
+
+/// Verify a RISC Zero proof against whichever registered guest program it
+/// claims to come from
+///
+/// # Returns
+/// A tuple of (checker, `journal_output`, `journal_bytes`) if verification
+/// succeeds; the caller checks the checker is the one its spec names
+pub fn verify_receipt(receipt_bytes: &[u8], registry: &ImageRegistry) -> Result<(CheckerId, JournalOutput, Vec<u8>)> {
     // Deserialize receipt
     let receipt: Receipt = bincode::deserialize(receipt_bytes)
         .map_err(|e| VceError::ReceiptDeserialization(format!("Failed to deserialize receipt from bytes: {e}. The receipt data may be corrupted.")))?;
     
-    // Get guest program ELF for image ID computation
-    let guest_elf = get_guest_elf().ok_or_else(|| {
-        VceError::ProofVerificationFailed(
-            "Guest program ELF binary not found. Cannot verify proof without computing image ID. Build the guest program first.".to_string()
-        )
+    // Find the guest program the receipt claims to come from
+    let image_id = receipt_image_id(&receipt)?;
+    let checker = registry.checker(&image_id).ok_or_else(|| {
+        VceError::ProofVerificationFailed(format!(
+            "Receipt is from an unknown guest program (image ID {image_id}). Build the guest programs first, or it was generated by a different guest program version."
+        ))
     })?;
     
-    // Compute image ID from ELF
-    let image_id = compute_image_id(guest_elf)
-        .map_err(|e| VceError::ProofVerificationFailed(format!("Failed to compute image ID from guest ELF: {e}. The ELF binary may be corrupted.")))?;
-    
     // Verify the receipt (1.0+ API: verify takes image_id as Digest)
     if std::env::var("RISC0_DEV_MODE").unwrap_or_default() == "1" {
-        println!("   ⚠ Skipping strict cryptographic verification in DEV_MODE");
+        tracing::warn!("Skipping strict cryptographic verification in DEV_MODE");
     } else {
         receipt.verify(image_id)
             .map_err(|e| VceError::ProofVerificationFailed(format!("RISC Zero cryptographic proof verification failed: {e}. The proof may be invalid, tampered with, or generated by a different guest program version.")))?;
@@ -189,6 +836,101 @@ pub fn verify_proof(receipt_bytes: &[u8]) -> Result<(JournalOutput, Vec<u8>)> {
     // Extract output from journal using decode (1.0+ API)
     let journal_output: JournalOutput = receipt.journal.decode()
         .map_err(|e| VceError::RiscZero(format!("Failed to decode JournalOutput from verified journal: {e}. The journal format may be incorrect.")))?;
+
+    // Aggregated children and previous periods are only as trustworthy as
+    // the programs that proved them: a previous period must come from this
+    // same program, children from any registered one
+    let incremental = journal_output.log_state.is_incremental();
+    for child in &journal_output.children {
+        let known = if incremental {
+            child.image_id == image_id.as_words()
+        } else {
+            <[u32; 8]>::try_from(child.image_id.as_slice()).is_ok_and(|id| registry.checker(&Digest::from(id)).is_some())
+        };
+        if !known {
+            return Err(VceError::ProofVerificationFailed(if incremental {
+                "The previous period was verified against a different guest program".to_string()
+            } else {
+                "Aggregated child proofs were verified against an unknown guest program".to_string()
+            }));
+        }
+    }
     
-    Ok((journal_output, journal_bytes))
+    Ok((checker, journal_output, journal_bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cycle_budget_stops_past_the_limit() {
+        let mut budget = CycleBudget { max_cycles: Some(3 << 20), used: 0 };
+        for _ in 0..3 {
+            budget.spend(20).unwrap();
+        }
+        let e = budget.spend(20).unwrap_err();
+        // Recognised by type, not by message
+        assert!(e.is::<CycleLimitReached>());
+        assert!(!anyhow::anyhow!("Session limit exceeded").is::<CycleLimitReached>());
+
+        let mut unlimited = CycleBudget::default();
+        assert!((0..64).all(|_| unlimited.spend(21).is_ok()));
+    }
+
+    #[test]
+    fn test_guest_reads_every_mode_as_sent() {
+        use fuse_guest::checker::{BatchData as GuestBatchData, ProofMode as Guest};
+
+        let documents = [EvidenceFile { name: "iam".to_string(), bytes: b"{}".to_vec() }];
+        let typed = TypedSystemData::from_json(&serde_json::json!({"a": [1, true]}));
+        let specs = ["{}".to_string(), "[]".to_string()];
+        let previous = Incremental { image_id: [7; 8], previous_journal: vec![1, 2, 3] };
+        let aggregation = Aggregation { children: vec![AggregatedChild { image_id: [9; 8], journal: vec![4] }] };
+        let modes = [
+            GuestMode::Single { spec_json: "s", system_data_json: "{}", parse_full_json: true },
+            GuestMode::Bundle { spec_json: "s", documents: &documents },
+            GuestMode::Typed { spec_json: "s", system_data: &typed },
+            GuestMode::Batch { spec_jsons: &specs, system_data: BatchData::Bundle(&documents) },
+            GuestMode::Stream { spec_json: "s" },
+            GuestMode::Incremental { spec_json: "s", previous },
+            GuestMode::Aggregate { spec_json: "s", aggregation },
+        ];
+        for mode in &modes {
+            let guest: Guest = risc0_zkvm::serde::from_slice(&to_vec(mode).unwrap()).unwrap();
+            let same = match (mode, guest) {
+                (GuestMode::Single { .. }, Guest::Single { spec_json, system_data_json, parse_full_json }) => {
+                    spec_json == "s" && system_data_json == "{}" && parse_full_json
+                }
+                (GuestMode::Bundle { .. }, Guest::Bundle { documents, .. }) => documents[0].name == "iam",
+                (GuestMode::Typed { .. }, Guest::Typed { system_data, .. }) => {
+                    system_data.into_document().unwrap() == serde_json::json!({"a": [1, true]})
+                }
+                (GuestMode::Batch { .. }, Guest::Batch { spec_jsons, system_data: GuestBatchData::Bundle(documents) }) => {
+                    spec_jsons == specs && documents[0].bytes == b"{}"
+                }
+                (GuestMode::Stream { .. }, Guest::Stream { spec_json }) => spec_json == "s",
+                (GuestMode::Incremental { .. }, Guest::Incremental { previous, .. }) => {
+                    previous.image_id == [7; 8] && previous.previous_journal == [1, 2, 3]
+                }
+                (GuestMode::Aggregate { .. }, Guest::Aggregate { aggregation, .. }) => aggregation.children[0].journal == [4],
+                _ => false,
+            };
+            assert!(same, "guest read {mode:?} differently");
+        }
+    }
+
+    #[test]
+    fn test_owned_inputs_keep_their_mode() {
+        let records = b"{}\n".to_vec();
+        let receipt = vec![1, 2];
+        let inputs = ProofInputs::with_mode(ProofMode::Incremental { spec_json: "{}", records: &records, previous_receipt: Some(&receipt) });
+        // Remote provers are sent the owned inputs as JSON
+        let owned: OwnedProofInputs = serde_json::from_str(&serde_json::to_string(&OwnedProofInputs::from(&inputs)).unwrap()).unwrap();
+        let ProofMode::Incremental { spec_json, records: sent, previous_receipt } = owned.as_inputs().mode else {
+            panic!("mode changed in transit");
+        };
+        assert_eq!((spec_json, sent, previous_receipt), ("{}", records.as_slice(), Some(receipt.as_slice())));
+        assert_eq!(owned.salt_seed, inputs.salt_seed);
+    }
 }
@@ -1,6 +1,6 @@
 //! Integration tests for C2PA signature verification and selective disclosure
 
-use fuse_core::{ComplianceSpec, VerifiableComplianceEnvelope, Result, ProverType};
+use fuse_core::{ComplianceSpec, VerifiableComplianceEnvelope, ProverType};
 use fuse_cli::c2pa::parse_c2pa_manifest;
 use std::path::Path;
 
@@ -119,7 +119,7 @@ fn test_selective_disclosure_with_real_manifest() {
             let verify_result = fuse_core::zkvm::verify_proof(&receipt_bytes);
             assert!(verify_result.is_ok(), "Proof verification should succeed");
             
-            let (verified_journal_output, verified_journal) = verify_result.unwrap();
+            let (verified_journal_output, _verified_journal) = verify_result.unwrap();
             assert_eq!(result, verified_journal_output.result, "Verified result should match");
             
             // Parse journal to verify selective disclosure worked
@@ -1,9 +1,8 @@
 //! Tests for detecting tampered C2PA assets and signatures
 
-use fuse_core::{Result, ProverType};
+use fuse_core::ProverType;
 use fuse_cli::c2pa::parse_c2pa_manifest;
 use std::env;
-use std::path::Path;
 
 #[path = "tamper_helpers.rs"]
 mod helpers;
@@ -4,6 +4,7 @@
 // Real integration tests are in checkers_integration.rs
 
 #[test]
+#[allow(clippy::assertions_on_constants)]
 fn test_checker_modules_exist() {
     // Verify checker modules can be imported
     assert!(true);
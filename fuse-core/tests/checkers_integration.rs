@@ -1,6 +1,6 @@
 //! Integration tests for compliance checkers in zkVM
 
-use fuse_core::{ComplianceSpec, Result, ProverType};
+use fuse_core::ProverType;
 use std::env;
 
 /// Test SOC2 checker with valid data
@@ -192,11 +192,12 @@ fn test_supply_chain_checker() {
     
     let system_data_json = r#"{"components": []}"#;
     
-    match fuse_core::zkvm::generate_proof(spec_json, &system_data_json, ProverType::Local) {
+    match fuse_core::zkvm::generate_proof(spec_json, system_data_json, ProverType::Local) {
         Ok((_receipt_bytes, journal_output, _journal_bytes)) => {
             // Supply chain checker should at least not panic
             assert!(journal_output.result == fuse_core::ComplianceResult::Pass || 
-                    journal_output.result == fuse_core::ComplianceResult::Fail,
+                    journal_output.result == fuse_core::ComplianceResult::Fail ||
+                    journal_output.result == fuse_core::ComplianceResult::Error,
                 "Supply chain checker should return a valid result");
         }
         Err(e) => {
@@ -226,11 +227,12 @@ fn test_ml_model_checker() {
     
     let system_data_json = r#"{"usage": []}"#;
     
-    match fuse_core::zkvm::generate_proof(spec_json, &system_data_json, ProverType::Local) {
+    match fuse_core::zkvm::generate_proof(spec_json, system_data_json, ProverType::Local) {
         Ok((_receipt_bytes, journal_output, _journal_bytes)) => {
             // ML model checker should at least not panic
             assert!(journal_output.result == fuse_core::ComplianceResult::Pass || 
-                    journal_output.result == fuse_core::ComplianceResult::Fail,
+                    journal_output.result == fuse_core::ComplianceResult::Fail ||
+                    journal_output.result == fuse_core::ComplianceResult::Error,
                 "ML model checker should return a valid result");
         }
         Err(e) => {
@@ -243,3 +245,40 @@ fn test_ml_model_checker() {
         }
     }
 }
+
+#[test]
+fn test_session_limits_stop_execution() {
+    env::set_var("RISC0_DEV_MODE", "1");
+
+    let spec_json = r#"{"claim": "GDPR data residency", "system_hash": "test", "constraints": {"data_region": "EU"}, "jurisdiction": "EU", "version": "1.0", "expiry": "2099-12-31T23:59:59Z"}"#;
+    let system_data = r#"{"storage_locations": [{"region": "EU"}]}"#;
+    let inputs = fuse_core::zkvm::ProofInputs::new(spec_json, system_data);
+
+    let segment_dir = env::temp_dir().join(format!("fuse-limits-{}", std::process::id()));
+    let tight = fuse_core::zkvm::SessionLimits {
+        max_cycles: Some(1_000),
+        segment_po2: Some(16),
+        segment_dir: Some(segment_dir.clone()),
+    };
+    match fuse_core::zkvm::execute_with_limits(&inputs, &tight) {
+        Err(fuse_core::VceError::SessionLimitExceeded(_)) => {}
+        Err(e) if e.to_string().contains("not built") || e.to_string().contains("Guest program") => {
+            println!("Skipping test: Guest program not built");
+            return;
+        }
+        other => panic!("Expected the session limit to stop execution, got {other:?}"),
+    }
+    // The segment subdirectory is removed with the session
+    assert!(std::fs::read_dir(&segment_dir).map(|entries| entries.count()).unwrap_or(0) == 0);
+
+    let oversized = fuse_core::zkvm::SessionLimits { segment_po2: Some(24), ..Default::default() };
+    assert!(fuse_core::zkvm::execute_with_limits(&inputs, &oversized).is_err());
+
+    let (output, stats) = fuse_core::zkvm::execute_with_limits(&inputs, &fuse_core::zkvm::SessionLimits {
+        segment_po2: Some(16),
+        ..Default::default()
+    })
+    .expect("execution within limits");
+    assert_eq!(output.result, fuse_core::ComplianceResult::Pass);
+    assert!(stats.segments >= 1);
+}
@@ -116,10 +116,9 @@ mod tests {
 
     #[test]
     fn test_list_fixtures() {
-        let fixtures = list_available_c2pa_fixtures().unwrap();
         // Fixtures may not be downloaded - this is okay, test passes if function works
         // The actual fixture loading is tested in integration tests
-        assert!(true, "Fixture listing function works (fixtures may not be downloaded)");
+        assert!(list_available_c2pa_fixtures().is_ok(), "Fixture listing function works (fixtures may not be downloaded)");
     }
 
     #[test]
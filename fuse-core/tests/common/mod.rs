@@ -1,5 +1,8 @@
 //! Common test utilities
 
+// Each test binary only uses a subset of these helpers
+#![allow(dead_code, unused_imports)]
+
 pub mod fixtures;
 
 pub use fixtures::{
@@ -92,6 +95,7 @@ pub fn create_system_data(fields: &[(&str, serde_json::Value)]) -> String {
 }
 
 /// Helper to assert proof result
+#[allow(clippy::type_complexity)]
 pub fn assert_proof_result(
     result: Result<(Vec<u8>, JournalOutput, Vec<u8>), Box<dyn std::error::Error>>,
     expected: ComplianceResult,
@@ -0,0 +1,58 @@
+//! The host composite evaluator must record the same node results as the
+//! guest program, including the error codes of inconclusive leaves
+
+use fuse_checkers::CheckerRegistry;
+use fuse_core::composite::ClaimNode;
+use fuse_core::{ComplianceResult, ErrorCode};
+use serde_json::{json, Value};
+
+#[path = "common/mod.rs"]
+mod common;
+
+use common::SpecBuilder;
+
+/// Node results from the host checkers and from the guest program's
+/// evaluator, as JSON so the two crates' types compare
+fn host_and_guest(node: &Value, data: &Value) -> (Value, Value) {
+    let tree: ClaimNode = serde_json::from_value(node.clone()).unwrap();
+    let spec = SpecBuilder::new("Composite control set").build();
+    let host = CheckerRegistry::new().check_composite(&spec, &tree, &data.to_string()).unwrap();
+    let guest = fuse_guest::composite::evaluate(node, data);
+    (serde_json::to_value(host).unwrap(), serde_json::to_value(guest.node_results).unwrap())
+}
+
+#[test]
+fn test_erroring_leaf_keeps_its_error_code() {
+    let node = json!({ "any": [
+        { "check": { "id": "gdpr", "claim": "GDPR data residency" } },
+        { "check": { "id": "soc2", "claim": "SOC2 control", "constraints": { "sampling": "1" } } },
+        { "check": { "id": "supply", "claim": "Supply chain provenance", "constraints": { "provenance_chain": "vendor" }, "data_key": "sbom" } }
+    ] });
+    let data = json!({ "access_logs": ["not a record"] });
+
+    let (host, guest) = host_and_guest(&node, &data);
+    assert_eq!(host, guest);
+
+    let codes: Vec<ErrorCode> = serde_json::from_value::<Vec<fuse_core::composite::NodeResult>>(host)
+        .unwrap()
+        .into_iter()
+        .map(|node| node.error_code)
+        .collect();
+    assert_eq!(codes, [ErrorCode::SubClaimError, ErrorCode::MissingConstraint, ErrorCode::MalformedRecord, ErrorCode::MissingField]);
+}
+
+#[test]
+fn test_unknown_leaf_is_inconclusive() {
+    let tree: ClaimNode = serde_json::from_value(json!({ "all": [
+        { "check": { "id": "gdpr", "claim": "GDPR data residency", "constraints": { "data_region": "EU" } } },
+        { "check": { "id": "unknown", "claim": "Quarterly board minutes" } }
+    ] }))
+    .unwrap();
+    let data = json!({ "storage_locations": [{ "region": "EU" }] });
+    let spec = SpecBuilder::new("Composite control set").build();
+
+    let nodes = CheckerRegistry::new().check_composite(&spec, &tree, &data.to_string()).unwrap();
+    assert_eq!(nodes[0].result, ComplianceResult::Error);
+    assert_eq!(nodes[1].result, ComplianceResult::Pass);
+    assert_eq!((nodes[2].result, nodes[2].error_code), (ComplianceResult::Error, ErrorCode::CheckerNotInGuest));
+}
@@ -0,0 +1,70 @@
+//! Tests for detecting tampered envelopes around valid receipts
+
+use fuse_core::{ComplianceProof, ProverType, VerifiableComplianceEnvelope};
+
+#[path = "tamper_helpers.rs"]
+mod helpers;
+use helpers::*;
+
+#[path = "common/mod.rs"]
+mod common;
+
+use common::{setup_dev_mode, SpecBuilder};
+
+/// Test that editing the stored result of a real proof fails verification
+#[test]
+fn test_flipped_result_fails() {
+    setup_dev_mode();
+
+    let spec = SpecBuilder::new("SOC2 control X verified").with_constraint("sampling", "0").build();
+    let spec_json = serde_json::to_string(&spec).unwrap();
+    let system_data_json = r#"{"access_logs": []}"#;
+
+    let (receipt_bytes, journal_output, journal_bytes) =
+        match fuse_core::zkvm::generate_proof(&spec_json, system_data_json, ProverType::Local) {
+            Ok(proof) => proof,
+            Err(e) => {
+                let error_msg = e.to_string();
+                if error_msg.contains("not built") || error_msg.contains("Guest program") {
+                    println!("Skipping test: Guest program not built");
+                    return;
+                }
+                panic!("Unexpected error: {}", e);
+            }
+        };
+    let proof = ComplianceProof::from_risc_zero_receipt(spec.hash(), receipt_bytes, journal_output, journal_bytes);
+    let mut envelope = VerifiableComplianceEnvelope::new(spec, proof);
+    assert!(envelope.verify().is_ok(), "Untampered envelope should verify");
+
+    let tampered = tamper_result(&serde_json::to_string(&envelope).unwrap()).unwrap();
+    let mut tampered: VerifiableComplianceEnvelope = serde_json::from_str(&tampered).unwrap();
+    assert!(tampered.verify().is_err(), "Flipped result should fail verification");
+    assert!(tampered.is_compliant().is_err(), "Flipped result should not be reported compliant");
+}
+
+/// Test that a real proof re-wrapped under another spec fails verification
+#[test]
+fn test_rewrapped_spec_fails() {
+    setup_dev_mode();
+
+    let spec = SpecBuilder::new("SOC2 control X verified").with_constraint("sampling", "0").build();
+    let spec_json = serde_json::to_string(&spec).unwrap();
+    let (receipt_bytes, journal_output, journal_bytes) =
+        match fuse_core::zkvm::generate_proof(&spec_json, r#"{"access_logs": []}"#, ProverType::Local) {
+            Ok(proof) => proof,
+            Err(e) => {
+                let error_msg = e.to_string();
+                if error_msg.contains("not built") || error_msg.contains("Guest program") {
+                    println!("Skipping test: Guest program not built");
+                    return;
+                }
+                panic!("Unexpected error: {}", e);
+            }
+        };
+
+    // Same checker, other constraints, with a spec hash that matches them
+    let other = SpecBuilder::new("SOC2 control X verified").with_constraint("sampling", "100").build();
+    let proof = ComplianceProof::from_risc_zero_receipt(other.hash(), receipt_bytes, journal_output, journal_bytes);
+    let mut envelope = VerifiableComplianceEnvelope::new(other, proof);
+    assert!(envelope.verify().is_err(), "Receipt under another spec should fail verification");
+}
@@ -1,6 +1,6 @@
 //! Error path tests - ensure all failure modes are handled gracefully
 
-use fuse_core::{Result, ProverType};
+use fuse_core::ProverType;
 use std::env;
 
 /// Test malformed JSON inputs
@@ -19,9 +19,10 @@ fn test_malformed_json_inputs() {
     
     match fuse_core::zkvm::generate_proof(malformed_spec, system_data, ProverType::Local) {
         Ok((_receipt_bytes, journal_output, _journal_bytes)) => {
-            // Should fail gracefully
-            assert_eq!(journal_output.result, fuse_core::ComplianceResult::Fail,
-                "Malformed JSON should cause failure");
+            // Should be reported as inconclusive, not as a violation
+            assert_eq!(journal_output.result, fuse_core::ComplianceResult::Error,
+                "Malformed JSON should be an error");
+            assert_eq!(journal_output.error_code, fuse_core::ErrorCode::SpecParse);
         }
         Err(e) => {
             let error_msg = e.to_string();
@@ -48,8 +49,9 @@ fn test_malformed_json_inputs() {
     
     match fuse_core::zkvm::generate_proof(valid_spec, malformed_system, ProverType::Local) {
         Ok((_receipt_bytes, journal_output, _journal_bytes)) => {
-            assert_eq!(journal_output.result, fuse_core::ComplianceResult::Fail,
-                "Malformed system data should cause failure");
+            assert_eq!(journal_output.result, fuse_core::ComplianceResult::Error,
+                "Malformed system data should be an error");
+            assert_eq!(journal_output.error_code, fuse_core::ErrorCode::SystemDataParse);
         }
         Err(e) => {
             let error_msg = e.to_string();
@@ -82,7 +84,8 @@ fn test_missing_spec_fields() {
         Ok((_receipt_bytes, journal_output, _journal_bytes)) => {
             // Should handle missing fields gracefully
             assert!(journal_output.result == fuse_core::ComplianceResult::Pass || 
-                    journal_output.result == fuse_core::ComplianceResult::Fail,
+                    journal_output.result == fuse_core::ComplianceResult::Fail ||
+                    journal_output.result == fuse_core::ComplianceResult::Error,
                 "Should return valid result even with missing fields");
         }
         Err(e) => {
@@ -119,9 +122,10 @@ fn test_missing_system_data_fields() {
     
     match fuse_core::zkvm::generate_proof(spec_json, system_data_no_key, ProverType::Local) {
         Ok((_receipt_bytes, journal_output, _journal_bytes)) => {
-            // Should fail when required fields are missing
-            assert_eq!(journal_output.result, fuse_core::ComplianceResult::Fail,
-                "Missing required fields should cause failure");
+            // Missing evidence is inconclusive, not a violation
+            assert_eq!(journal_output.result, fuse_core::ComplianceResult::Error,
+                "Missing required fields should be an error");
+            assert_eq!(journal_output.error_code, fuse_core::ErrorCode::MissingField);
         }
         Err(e) => {
             let error_msg = e.to_string();
@@ -158,8 +162,9 @@ fn test_invalid_hex_encoding() {
     
     match fuse_core::zkvm::generate_proof(spec_json, system_data_invalid_hex, ProverType::Local) {
         Ok((_receipt_bytes, journal_output, _journal_bytes)) => {
-            assert_eq!(journal_output.result, fuse_core::ComplianceResult::Fail,
-                "Invalid hex encoding should cause failure");
+            assert_eq!(journal_output.result, fuse_core::ComplianceResult::Error,
+                "Invalid hex encoding should be an error");
+            assert_eq!(journal_output.error_code, fuse_core::ErrorCode::InvalidEncoding);
         }
         Err(e) => {
             let error_msg = e.to_string();
@@ -196,8 +201,9 @@ fn test_wrong_key_signature_lengths() {
     
     match fuse_core::zkvm::generate_proof(spec_json, system_data_wrong_length, ProverType::Local) {
         Ok((_receipt_bytes, journal_output, _journal_bytes)) => {
-            assert_eq!(journal_output.result, fuse_core::ComplianceResult::Fail,
-                "Wrong key/signature lengths should cause failure");
+            assert_eq!(journal_output.result, fuse_core::ComplianceResult::Error,
+                "Wrong key/signature lengths should be an error");
+            assert_eq!(journal_output.error_code, fuse_core::ErrorCode::InvalidLength);
         }
         Err(e) => {
             let error_msg = e.to_string();
@@ -234,9 +240,10 @@ fn test_invalid_ed25519_keys() {
     
     match fuse_core::zkvm::generate_proof(spec_json, system_data_invalid_key, ProverType::Local) {
         Ok((_receipt_bytes, journal_output, _journal_bytes)) => {
-            // May pass or fail depending on validation - important is no panic
+            // May pass, fail or error depending on validation - important is no panic
             assert!(journal_output.result == fuse_core::ComplianceResult::Pass || 
-                    journal_output.result == fuse_core::ComplianceResult::Fail,
+                    journal_output.result == fuse_core::ComplianceResult::Fail ||
+                    journal_output.result == fuse_core::ComplianceResult::Error,
                 "Should handle invalid keys without panicking");
         }
         Err(e) => {
@@ -272,7 +279,8 @@ fn test_empty_inputs() {
         Ok((_receipt_bytes, journal_output, _journal_bytes)) => {
             // Should handle empty data gracefully
             assert!(journal_output.result == fuse_core::ComplianceResult::Pass || 
-                    journal_output.result == fuse_core::ComplianceResult::Fail,
+                    journal_output.result == fuse_core::ComplianceResult::Fail ||
+                    journal_output.result == fuse_core::ComplianceResult::Error,
                 "Should handle empty inputs without panicking");
         }
         Err(e) => {
@@ -323,3 +331,13 @@ fn test_no_panics_on_invalid_input() {
         }
     }
 }
+
+/// Test that journal outputs saved before error codes existed still load
+#[test]
+fn test_journal_output_without_error_code() {
+    let legacy = r#"{"result": "Fail", "claim_hash": [], "redacted_json": ""}"#;
+
+    let output: fuse_core::JournalOutput = serde_json::from_str(legacy).unwrap();
+    assert_eq!(output.result, fuse_core::ComplianceResult::Fail);
+    assert_eq!(output.error_code, fuse_core::ErrorCode::None);
+}
@@ -0,0 +1,396 @@
+//! The guest program, run on the host for each way of passing it inputs,
+//! must check the system data like the host checkers and commit a journal
+//! the host decodes, with the evidence digests the host expects
+
+use fuse_checkers::CheckerRegistry;
+use fuse_core::aggregate::{AggregatedChild, Aggregation};
+use fuse_core::evidence::{self, EvidenceFile};
+use fuse_core::incremental::{self, Incremental};
+use fuse_core::proof::JournalOutput;
+use fuse_core::{ComplianceResult, ComplianceSpec, ErrorCode};
+use fuse_guest::checker::{self, BatchData, ProofMode};
+use fuse_guest::stream::Stream;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+const SALT_SEED: [u8; 32] = [42; 32];
+
+/// Image ID of the guest that proved child and previous period journals
+const IMAGE_ID: [u32; 8] = [7; 8];
+
+/// `value` as the other crate's mirror type reads it through the zkVM's serde
+fn convert<T: Serialize + ?Sized, U: DeserializeOwned>(value: &T) -> U {
+    risc0_zkvm::serde::from_slice(&risc0_zkvm::serde::to_vec(value).unwrap()).unwrap()
+}
+
+/// Run the guest program for `mode` on the host; returns its journal as
+/// the host decodes it, and the journal bytes
+fn execute(mode: ProofMode, private_params_json: &str, evidence_files: &[EvidenceFile], stream: Stream) -> (JournalOutput, Vec<u8>) {
+    let evidence_files: Vec<fuse_guest::evidence::EvidenceFile> = convert(evidence_files);
+    let output = checker::execute(mode, &SALT_SEED, private_params_json, &evidence_files, stream);
+    let journal: Vec<u8> = risc0_zkvm::serde::to_vec(&output).unwrap().iter().flat_map(|word| word.to_le_bytes()).collect();
+    (risc0_zkvm::serde::from_slice(&journal).unwrap(), journal)
+}
+
+/// Run a mode that takes no streamed records or other inputs
+fn run(mode: ProofMode) -> JournalOutput {
+    execute(mode, "", &[], records(b"")).0
+}
+
+/// Streamed records, sent in chunks like the host sends them
+fn records(json_lines: &[u8]) -> Stream {
+    Stream::from_bytes(json_lines.to_vec(), fuse_core::stream::CHUNK_BYTES)
+}
+
+/// A spec for `claim` with the given extra fields
+fn spec(claim: &str, fields: Value) -> ComplianceSpec {
+    let mut spec = json!({
+        "claim": claim,
+        "system_hash": "test",
+        "constraints": {},
+        "jurisdiction": "EU",
+        "version": "1.0",
+        "expiry": "2099-12-31T23:59:59Z"
+    });
+    spec.as_object_mut().unwrap().extend(fields.as_object().unwrap().clone());
+    serde_json::from_value(spec).unwrap()
+}
+
+/// Single JSON document input, as `ProofInputs::new` passes it
+fn single(spec: &ComplianceSpec, system_data: &Value) -> ProofMode {
+    ProofMode::Single { spec_json: spec.to_proof_json().unwrap(), system_data_json: system_data.to_string(), parse_full_json: false }
+}
+
+/// Assert the journal has the host checker's result and score
+fn assert_host_agrees(journal: &JournalOutput, spec: &ComplianceSpec, system_data: &Value) {
+    let checker = CheckerRegistry::new();
+    let (result, score) = checker.checker_for(spec).unwrap().check_scored(spec, &system_data.to_string()).unwrap();
+    assert_eq!(journal.result, result);
+    if let Some(score) = score {
+        assert_eq!(journal.score, score);
+    }
+}
+
+fn score(journal: &JournalOutput) -> (u64, u64) {
+    (journal.score.numerator, journal.score.denominator)
+}
+
+#[test]
+fn test_gdpr_graded_threshold() {
+    let spec = spec("GDPR data residency verified", json!({ "constraints": { "data_region": "EU" }, "pass_threshold": "75%" }));
+    // 3 of 4 locations in the EU meets a 75% threshold
+    let data = json!({ "storage_locations": [
+        { "region": "EU", "location": "Frankfurt" },
+        { "region": "EU", "location": "Dublin" },
+        { "region": "EU", "location": "Paris" },
+        { "region": "US", "location": "Virginia" }
+    ] });
+
+    let journal = run(single(&spec, &data));
+    assert_eq!(journal.result, ComplianceResult::Pass);
+    assert_eq!(score(&journal), (3, 4));
+    assert_host_agrees(&journal, &spec, &data);
+}
+
+#[test]
+fn test_rule_expression() {
+    let spec = spec("EU storage policy", json!({
+        "rule": "all(storage_locations[*].region in [\"eu-west-1\", \"eu-central-1\"]) && count(usage_logs) <= 1000"
+    }));
+    let data = json!({ "storage_locations": [{ "region": "eu-west-1" }, { "region": "us-east-1" }], "usage_logs": [] });
+
+    let journal = run(single(&spec, &data));
+    assert_eq!(journal.result, ComplianceResult::Fail, "a location is outside the allowed regions");
+    assert_host_agrees(&journal, &spec, &data);
+}
+
+#[test]
+fn test_composite_any_passes_with_one_sub_claim() {
+    let spec = spec("EU data controls", json!({ "composite": { "any": [
+        { "check": { "id": "residency", "claim": "GDPR data residency", "constraints": { "data_region": "EU" }, "data_key": "storage" } },
+        { "check": { "id": "usage", "claim": "ML model usage constraint", "constraints": { "max_usage": "10" }, "data_key": "model" } }
+    ] } }));
+    let data = json!({
+        "storage": { "storage_locations": [{ "region": "US", "location": "Virginia" }] },
+        "model": { "usage_logs": [{ "domain": "healthcare" }] }
+    });
+
+    let journal = run(single(&spec, &data));
+    assert_eq!(journal.result, ComplianceResult::Pass);
+    let nodes: Vec<_> = journal.node_results.iter().map(|node| (node.path.as_str(), node.result)).collect();
+    assert_eq!(nodes, [("/", ComplianceResult::Pass), ("/0", ComplianceResult::Fail), ("/1", ComplianceResult::Pass)]);
+
+    let host = CheckerRegistry::new().check_composite(&spec, spec.composite.as_ref().unwrap(), &data.to_string()).unwrap();
+    assert_eq!(journal.node_results, host);
+}
+
+#[test]
+fn test_nested_disclosure_matches_host() {
+    let fields = ["/storage_locations/*/region".to_string(), "/operator/name".to_string()];
+    let spec = spec("GDPR data residency verified", json!({ "constraints": { "data_region": "EU" }, "disclosed_fields": fields }));
+    let data = json!({ "storage_locations": [
+        { "region": "EU", "location": "Frankfurt" },
+        { "region": "EU", "location": "Dublin" }
+    ] });
+
+    let journal = run(single(&spec, &data));
+    let redacted: Value = serde_json::from_str(&journal.redacted_json).unwrap();
+    assert_eq!(redacted, json!({ "storage_locations": [{ "region": "EU" }, { "region": "EU" }] }));
+    assert_eq!(journal.missing_fields, ["/operator/name"]);
+
+    let host = fuse_core::disclosure::disclose(&fields, &data, &data);
+    assert_eq!((redacted, journal.missing_fields), (host.redacted, host.missing));
+}
+
+#[test]
+fn test_disclosure_predicates_commit_only_outcomes() {
+    let spec = spec("C2PA JSON parsing only", json!({
+        "disclosed_fields": ["claim_generator"],
+        "disclosure_predicates": [
+            { "name": "captured_before_june", "expr": "date(claim.capture_time) < date(\"2025-06-01\")" },
+            { "name": "trusted_issuer", "expr": "claim.issuer in [\"Acme Root CA\", \"Example CA\"]" },
+            { "name": "has_location", "expr": "claim.location matches \"^[A-Z]\"" }
+        ]
+    }));
+    let data = json!({ "claim": { "claim_generator": "FUSE Test Camera", "capture_time": "2025-05-20T08:00:00Z", "issuer": "Example CA" } });
+
+    let journal = run(single(&spec, &data));
+    let outcomes: Vec<_> = journal.predicates.iter().map(|p| (p.name.as_str(), p.holds, p.error_code)).collect();
+    assert_eq!(outcomes, [
+        ("captured_before_june", true, ErrorCode::None),
+        ("trusted_issuer", true, ErrorCode::None),
+        ("has_location", false, ErrorCode::MissingField),
+    ]);
+    assert!(!journal.redacted_json.contains("capture_time"), "predicate inputs must not be disclosed");
+}
+
+#[test]
+fn test_field_commitment_root_matches_host() {
+    let spec = spec("GDPR data residency verified", json!({ "constraints": { "data_region": "EU" }, "commit_fields": true }));
+    let data = json!({ "storage_locations": [
+        { "region": "EU", "location": "Frankfurt" },
+        { "region": "EU", "location": "Dublin" }
+    ] });
+
+    let journal = run(single(&spec, &data));
+    let tree = fuse_core::merkle::FieldTree::new(&data, &SALT_SEED);
+    assert_eq!(journal.fields_root, tree.root());
+    let opening = tree.open("/storage_locations/1/location").unwrap().remove(0);
+    assert!(opening.verify(&journal.fields_root).is_ok());
+}
+
+#[test]
+fn test_private_params_applied_and_committed() {
+    let values = std::collections::BTreeMap::from([("max_usage".to_string(), "1".to_string())]);
+    let (commitments, params) = fuse_core::private_params::seal(&values);
+    let spec = spec("ML model usage constraint", json!({ "private_constraints": commitments }));
+    // Two logs exceed the private max_usage of 1
+    let data = json!({ "usage_logs": [{ "domain": "a" }, { "domain": "b" }] });
+
+    let (journal, _) = execute(single(&spec, &data), &serde_json::to_string(&params).unwrap(), &[], records(b""));
+    assert_eq!(journal.result, ComplianceResult::Fail, "the private max_usage must be applied");
+    assert_eq!(journal.private_params, fuse_core::private_params::verify(&spec, &params).unwrap());
+    assert_host_agrees(&journal, &fuse_core::private_params::apply(&spec, &params).unwrap(), &data);
+}
+
+#[test]
+fn test_direct_reads_match_full_parse() {
+    let spec = spec("SOC2 control X", json!({ "constraints": { "sampling": "random sample 2", "required_fields": "user,mfa" } }));
+    let data = json!({
+        "access_logs": [{ "user": "alice", "mfa": true, "ip": "10.0.0.1" }, { "user": "bob", "mfa": false }],
+        "unrelated": { "large": [1, 2, 3] }
+    });
+
+    let direct = run(single(&spec, &data));
+    let full = run(ProofMode::Single { spec_json: spec.to_proof_json().unwrap(), system_data_json: data.to_string(), parse_full_json: true });
+    assert_eq!(score(&direct), (1, 2), "bob's log sets mfa to false, so it does not conform");
+    assert_eq!((direct.result, direct.score, direct.error_code, &direct.claim_hash), (full.result, full.score, full.error_code, &full.claim_hash));
+    assert_host_agrees(&direct, &spec, &data);
+}
+
+#[test]
+fn test_single_commits_spec_system_data_and_files() {
+    let spec = spec("JSON parsing only", json!({}));
+    let spec_json = spec.to_proof_json().unwrap();
+    let data = json!({ "claim": { "signature": "x" } });
+    let attachment = EvidenceFile { name: "photo.jpg".to_string(), bytes: vec![0xff, 0xd8, 0xff] };
+
+    let (journal, _) = execute(single(&spec, &data), "", std::slice::from_ref(&attachment), records(b""));
+    let names: Vec<&str> = journal.evidence.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, [evidence::SPEC, evidence::SYSTEM_DATA, "photo.jpg"]);
+    assert!(journal.evidence[0].matches(spec_json.as_bytes()));
+    assert!(journal.evidence[1].matches(data.to_string().as_bytes()));
+    assert!(!journal.evidence[1].matches(b"{}"), "other system data must not match");
+    assert!(journal.evidence[2].matches(&attachment.bytes));
+}
+
+#[test]
+fn test_bundle_forms_system_data_and_commits_each_document() {
+    let spec = spec("Bundle rule", json!({ "rule": "all(iam.users[*].mfa == true) && count(access_logs) <= 2" }));
+    let bundle = [
+        EvidenceFile { name: "iam".to_string(), bytes: br#"{"users": [{"name": "a", "mfa": true}]}"#.to_vec() },
+        EvidenceFile { name: "access_logs".to_string(), bytes: br#"[{"user": "a"}]"#.to_vec() },
+    ];
+
+    let journal = run(ProofMode::Bundle { spec_json: spec.to_proof_json().unwrap(), documents: convert(&bundle[..]) });
+    assert_eq!(journal.result, ComplianceResult::Pass);
+    assert_host_agrees(&journal, &spec, &evidence::bundle_document(&bundle).unwrap());
+    let names: Vec<&str> = journal.evidence.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, [evidence::SPEC, "iam", "access_logs"]);
+    assert!(journal.evidence[1].matches(&bundle[0].bytes));
+    assert!(journal.evidence[2].matches(&bundle[1].bytes));
+
+    // A name used twice is ambiguous
+    let twice = [bundle[0].clone(), bundle[0].clone()];
+    let journal = run(ProofMode::Bundle { spec_json: spec.to_proof_json().unwrap(), documents: convert(&twice[..]) });
+    assert_eq!(journal.error_code, ErrorCode::MalformedRecord);
+}
+
+#[test]
+fn test_typed_system_data_matches_json_input() {
+    let spec = spec("GDPR data residency", json!({ "constraints": { "data_region": "EU" } }));
+    let data = json!({ "storage_locations": [
+        { "region": "EU", "location": "Frankfurt" },
+        { "region": "US", "location": "Virginia" }
+    ] });
+    let typed = fuse_core::typed::TypedSystemData::from_json(&data);
+
+    let journal = run(ProofMode::Typed { spec_json: spec.to_proof_json().unwrap(), system_data: convert(&typed) });
+    let json = run(single(&spec, &data));
+    assert_eq!((journal.result, journal.score), (json.result, json.score));
+    assert_eq!(score(&journal), (1, 2));
+    assert_host_agrees(&journal, &spec, &data);
+    assert!(journal.evidence[1].matches(typed.canonical_json().as_bytes()), "the system_data digest must cover the canonical JSON");
+}
+
+#[test]
+fn test_batch_specs_share_system_data() {
+    let gdpr = spec("GDPR data residency", json!({ "constraints": { "data_region": "EU" } }));
+    let ml = spec("ML model usage constraint", json!({ "constraints": { "max_usage": "1" } }));
+    let data = json!({ "storage_locations": [{ "region": "EU" }], "usage_logs": [{ "domain": "health" }, { "domain": "finance" }] });
+    let spec_jsons = vec![gdpr.to_proof_json().unwrap(), ml.to_proof_json().unwrap()];
+
+    let journal = run(ProofMode::Batch { spec_jsons: spec_jsons.clone(), system_data: BatchData::Json(data.to_string()) });
+    assert_eq!(journal.result, ComplianceResult::Fail, "one failing spec fails the batch");
+    assert_eq!(journal.batch.len(), 2);
+    assert_host_agrees(&journal.batch[0], &gdpr, &data);
+    assert_host_agrees(&journal.batch[1], &ml, &data);
+    assert!(journal.batch[1].evidence[0].matches(spec_jsons[1].as_bytes()));
+    assert_eq!(journal.evidence.len(), 1);
+    assert!(journal.evidence[0].matches(data.to_string().as_bytes()));
+}
+
+#[test]
+fn test_streamed_records_match_json_input() {
+    let spec = spec("SOC2 control X", json!({
+        "constraints": { "sampling": "random sample 15000", "required_fields": "user,mfa" },
+        "pass_threshold": "0.9"
+    }));
+    let logs: Vec<Value> = (0..25_000)
+        .map(|i| if i % 20 == 0 {
+            json!({ "user": format!("user-{i}"), "ip": "10.0.0.1" })
+        } else {
+            json!({ "user": format!("user-{i}"), "mfa": true, "ip": "10.0.0.1" })
+        })
+        .collect();
+    let json_lines: String = logs.iter().map(|log| format!("{log}\n")).collect();
+    // Over one chunk of records, so some line spans two chunks
+    assert!(json_lines.len() > fuse_core::stream::CHUNK_BYTES);
+    let data = fuse_core::stream::document(&spec, json_lines.as_bytes()).unwrap();
+
+    let (journal, _) = execute(ProofMode::Stream { spec_json: spec.to_proof_json().unwrap() }, "", &[], records(json_lines.as_bytes()));
+    assert_eq!(journal.result, ComplianceResult::Pass);
+    assert_eq!(score(&journal), (14_250, 15_000));
+    assert_eq!(journal.score, run(single(&spec, &data)).score);
+    assert_host_agrees(&journal, &spec, &data);
+    assert!(journal.evidence[1].matches(json_lines.as_bytes()), "the system_data digest must cover the streamed bytes");
+}
+
+#[test]
+fn test_stream_reassembles_lines_across_chunks() {
+    let spec = spec("GDPR data residency", json!({ "constraints": { "data_region": "EU" } }));
+    let json_lines = b"{\"region\": \"EU\"}\n\n{\"region\": \"US\"}\n  \n{\"region\": \"EU\"}";
+    let expected = execute(ProofMode::Stream { spec_json: spec.to_proof_json().unwrap() }, "", &[], records(json_lines)).0;
+    assert_eq!(score(&expected), (2, 3), "blank lines are not records and the last line needs no newline");
+
+    for chunk_bytes in [1, 3, 7, 16] {
+        let stream = Stream::from_bytes(json_lines.to_vec(), chunk_bytes);
+        let (journal, _) = execute(ProofMode::Stream { spec_json: spec.to_proof_json().unwrap() }, "", &[], stream);
+        assert_eq!((journal.result, journal.score), (expected.result, expected.score), "chunks of {chunk_bytes} bytes");
+        assert!(journal.evidence[1].matches(json_lines));
+    }
+}
+
+#[test]
+fn test_stream_rejects_specs_that_need_the_document() {
+    let spec = spec("GDPR data residency", json!({ "constraints": { "data_region": "EU" }, "commit_fields": true }));
+    let json_lines = b"{\"region\": \"EU\"}\n";
+    assert!(fuse_core::stream::document(&spec, json_lines).is_err());
+
+    let (journal, _) = execute(ProofMode::Stream { spec_json: spec.to_proof_json().unwrap() }, "", &[], records(json_lines));
+    assert_eq!(journal.error_code, ErrorCode::StreamInput);
+    assert!(journal.evidence[1].matches(json_lines), "the stream is committed whatever the outcome");
+}
+
+#[test]
+fn test_incremental_periods_carry_log_state() {
+    let spec = spec("GDPR data residency", json!({ "constraints": { "data_region": "EU" }, "pass_threshold": "0.75" }));
+    let spec_json = spec.to_proof_json().unwrap();
+    let first: &[u8] = b"{\"region\": \"EU\"}\n{\"region\": \"EU\"}\n{\"region\": \"EU\"}\n";
+    let second: &[u8] = b"{\"region\": \"US\"}\n";
+    let period = |spec_json: &str, previous_journal: &[u8], json_lines: &[u8]| {
+        let previous = Incremental { image_id: IMAGE_ID, previous_journal: previous_journal.to_vec() };
+        execute(ProofMode::Incremental { spec_json: spec_json.to_string(), previous: convert(&previous) }, "", &[], records(json_lines))
+    };
+
+    let (first_output, first_journal) = period(&spec_json, &[], first);
+    assert_eq!(first_output.result, ComplianceResult::Pass);
+    assert_eq!(first_output.log_state.periods, 1);
+    assert_eq!(first_output.log_state.root, incremental::log_root([first]));
+    assert!(first_output.children.is_empty());
+
+    // Only the new record is streamed; the counters cover both periods
+    let (output, _) = period(&spec_json, &first_journal, second);
+    assert_eq!(output.result, ComplianceResult::Pass);
+    assert_eq!(score(&output), (3, 4));
+    let both = [first, second].concat();
+    assert_host_agrees(&output, &spec, &fuse_core::stream::document(&spec, &both).unwrap());
+    assert_eq!(output.log_state.periods, 2);
+    assert_eq!(output.log_state.root, incremental::log_root([first, second]));
+    assert_eq!(output.children[0].image_id, IMAGE_ID);
+    assert!(output.children[0].matches(&first_journal));
+    assert!(output.evidence[1].matches(second));
+
+    // Continuing under another spec is rejected
+    let other_spec = spec_json.replace("0.75", "0.5");
+    assert_eq!(period(&other_spec, &first_journal, second).0.error_code, ErrorCode::IncrementalState);
+    assert_eq!(period(&spec_json, &[0xff; 4], second).0.error_code, ErrorCode::ChildJournal);
+}
+
+#[test]
+fn test_aggregate_scores_children() {
+    let child_spec = spec("GDPR data residency", json!({ "constraints": { "data_region": "EU" } }));
+    let group_spec = spec("Group GDPR data residency", json!({ "pass_threshold": "0.5" }));
+    let journals: Vec<Vec<u8>> = [json!({ "storage_locations": [{ "region": "EU" }] }), json!({ "storage_locations": [{ "region": "US" }] })]
+        .iter()
+        .map(|data| execute(single(&child_spec, data), "", &[], records(b"")).1)
+        .collect();
+    let aggregate = |journals: &[Vec<u8>]| {
+        let children = journals.iter().map(|journal| AggregatedChild { image_id: IMAGE_ID, journal: journal.clone() }).collect();
+        run(ProofMode::Aggregate { spec_json: group_spec.to_proof_json().unwrap(), aggregation: convert(&Aggregation { children }) })
+    };
+
+    let journal = aggregate(&journals);
+    assert_eq!(journal.result, ComplianceResult::Pass);
+    assert_eq!(score(&journal), (1, 2));
+    let results: Vec<_> = journal.children.iter().map(|child| child.result).collect();
+    assert_eq!(results, [ComplianceResult::Pass, ComplianceResult::Fail]);
+    assert!(journal.children.iter().zip(&journals).all(|(child, bytes)| child.matches(bytes) && child.image_id == IMAGE_ID));
+    assert!(journal.evidence[0].matches(group_spec.to_proof_json().unwrap().as_bytes()));
+
+    // A journal that does not decode is still committed
+    let journal = aggregate(&[journals[0].clone(), vec![0xff; 4]]);
+    assert_eq!(journal.error_code, ErrorCode::ChildJournal);
+    assert_eq!(journal.children.len(), 2);
+}
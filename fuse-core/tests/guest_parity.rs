@@ -0,0 +1,78 @@
+//! The guest program's checkers, called directly on the host, must score
+//! system data the same way as the host checkers
+
+use fuse_checkers::soc2::Soc2ControlXChecker;
+use fuse_checkers::ComplianceChecker;
+use fuse_guest::checkers::soc2;
+use fuse_guest::stream::{RecordTally, TallyState};
+use serde_json::{json, Value};
+
+#[path = "common/mod.rs"]
+mod common;
+
+use common::SpecBuilder;
+
+/// (numerator, denominator) of a guest score
+fn guest_score(output: &fuse_guest::checker::JournalOutput) -> (u64, u64) {
+    (output.score.numerator, output.score.denominator)
+}
+
+#[test]
+fn test_soc2_required_fields_must_be_set() {
+    let spec = SpecBuilder::new("SOC2 control X")
+        .with_constraint("sampling", "4")
+        .with_constraint("required_fields", "user,mfa")
+        .build();
+    let data = json!({ "access_logs": [
+        { "user": "alice", "mfa": true },
+        { "user": "bob", "mfa": false },
+        { "user": "carol", "mfa": null },
+        { "user": "", "mfa": true }
+    ] });
+    let spec_value = serde_json::to_value(&spec).unwrap();
+
+    let (result, score) = Soc2ControlXChecker.check_scored(&spec, &data.to_string()).unwrap();
+    let score = score.unwrap();
+    assert_eq!((score.numerator, score.denominator), (1, 4));
+    assert_eq!(result, fuse_core::ComplianceResult::Fail);
+
+    assert_eq!(guest_score(&soc2::check(&spec_value, &data).unwrap()), (1, 4));
+    assert_eq!(guest_score(&soc2::check_json(&spec_value, &data.to_string()).unwrap().unwrap()), (1, 4));
+
+    let mut tally = soc2::Tally::new(&spec_value, TallyState::default()).unwrap();
+    for log in data["access_logs"].as_array().unwrap() {
+        tally.record(log.to_string().as_bytes());
+    }
+    assert_eq!(guest_score(&tally.finish().unwrap()), (1, 4));
+}
+
+#[test]
+fn test_soc2_mfa_false_lowers_the_score() {
+    let spec = SpecBuilder::new("SOC2 control X")
+        .with_constraint("sampling", "2")
+        .with_constraint("required_fields", "user,mfa")
+        .build();
+    let spec_value = serde_json::to_value(&spec).unwrap();
+    let score = |logs: Value| {
+        let data = json!({ "access_logs": logs });
+        let host = Soc2ControlXChecker.score(&spec, &data.to_string()).unwrap().unwrap();
+        let guest = soc2::check(&spec_value, &data).unwrap();
+        assert_eq!((host.numerator, host.denominator), guest_score(&guest));
+        guest_score(&guest)
+    };
+
+    assert_eq!(score(json!([{ "user": "a", "mfa": true }, { "user": "b", "mfa": true }])), (2, 2));
+    assert_eq!(score(json!([{ "user": "a", "mfa": true }, { "user": "b", "mfa": false }])), (1, 2));
+}
+
+#[test]
+fn test_typed_input_commits_the_host_canonical_json() {
+    let data = json!({ "access_logs": [{ "user": "alice", "mfa": true, "ip": "10.0.0.1" }], "retention": { "days": 30 }, "ratio": 0.25 });
+    let typed = fuse_core::typed::TypedSystemData::from_json(&data);
+    let bytes = risc0_zkvm::serde::to_vec(&typed).unwrap();
+
+    let guest: fuse_guest::typed::TypedSystemData = risc0_zkvm::serde::from_slice(&bytes).unwrap();
+    let document = guest.into_document().unwrap();
+    assert_eq!(document, data, "typed input must rebuild the whole document");
+    assert_eq!(fuse_guest::typed::canonical_json(&document), typed.canonical_json());
+}
@@ -1,5 +1,7 @@
 //! Helper functions for tampering with C2PA assets and test data
 
+#![allow(dead_code)]
+
 use std::path::Path;
 use std::fs;
 use anyhow::Result;
@@ -110,3 +112,17 @@ pub fn create_invalid_signature_length() -> String {
     // Return a signature that's not 64 bytes when decoded
     hex::encode(vec![0u8; 63]) // 63 bytes instead of 64
 }
+
+/// Flip an envelope's stored result between Pass and Fail
+///
+/// The receipt is untouched, so only the unauthenticated copy of the
+/// result changes.
+pub fn tamper_result(envelope_json: &str) -> Result<String> {
+    let mut value: serde_json::Value = serde_json::from_str(envelope_json)?;
+    let flipped = match value["proof"]["result"].as_str() {
+        Some("Pass") => "Fail",
+        _ => "Pass",
+    };
+    value["proof"]["result"] = serde_json::json!(flipped);
+    Ok(serde_json::to_string(&value)?)
+}
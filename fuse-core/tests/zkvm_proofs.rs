@@ -1,6 +1,6 @@
 //! Integration tests for zkVM proof generation and verification
 
-use fuse_core::{ComplianceSpec, VerifiableComplianceEnvelope, Result, ProverType};
+use fuse_core::{ComplianceSpec, VerifiableComplianceEnvelope, ProverType};
 use std::collections::BTreeMap;
 use chrono::Utc;
 
@@ -129,3 +129,20 @@ fn test_envelope_with_real_zkvm_proof() {
         println!("Skipping test: Guest program not built");
     }
 }
+
+#[test]
+fn test_cancelled_proof_stops_before_execution() {
+    let token = fuse_core::progress::CancellationToken::new();
+    let phases = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let seen = std::sync::Arc::clone(&phases);
+    let options = fuse_core::zkvm::ProofOptions {
+        observer: Some(std::sync::Arc::new(move |phase| seen.lock().unwrap().push(phase))),
+        cancellation: Some(token.clone()),
+        ..fuse_core::zkvm::ProofOptions::new(ProverType::Local)
+    };
+    token.cancel();
+
+    let result = fuse_core::zkvm::generate_proof(r#"{"claim": "SOC2 control X verified"}"#, "{}", options);
+    assert!(matches!(result, Err(fuse_core::VceError::Cancelled)));
+    assert!(phases.lock().unwrap().is_empty(), "A cancelled proof should not start executing");
+}
@@ -0,0 +1,86 @@
+//! Proof aggregation in zkVM
+//!
+//! Rolls many envelopes up into one proof, e.g. one per subsidiary into a
+//! group-level attestation. The host adds each child receipt as an
+//! assumption and passes its journal; the guest's entry point calls
+//! `env::verify` on each, making this proof conditional on every child
+//! being a valid proof of the given image, and the receipt only verifies
+//! once those assumptions are resolved.
+//!
+//! Children may come from different checkers' guest programs, or be
+//! aggregates themselves. Each child is committed by its image ID, so
+//! verifiers can check it is a known guest program, its journal digest,
+//! the SHA-256 RISC Zero uses, and its result. The aggregate is scored as
+//! the fraction of children that pass against the spec's `pass_threshold`
+//! (all of them by default).
+//!
+//! Mirrors `fuse_core::aggregate`; types and field order must match.
+
+use alloc::vec::Vec;
+use risc0_zkvm::sha::rust_crypto::{Digest, Sha256};
+use serde::{Serialize, Deserialize};
+use serde_json::Value;
+use crate::checker::{ComplianceResult, ErrorCode, JournalOutput};
+use crate::score::{self, Score};
+
+/// Child proofs to aggregate; none when not aggregating
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Aggregation {
+    pub children: Vec<AggregatedChild>,
+}
+
+/// A child proof's journal and the image ID of the guest that proved it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AggregatedChild {
+    pub image_id: [u32; 8],
+    pub journal: Vec<u8>,
+}
+
+/// A child proof, committed to the journal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChildCommitment {
+    pub image_id: Vec<u32>,
+    pub journal_digest: Vec<u8>,
+    pub result: ComplianceResult,
+}
+
+impl Aggregation {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.children.is_empty()
+    }
+}
+
+/// Score the aggregate of children already verified for the spec
+///
+/// The child commitments are set even when the spec is malformed, so the
+/// output always shows what was aggregated.
+#[must_use]
+pub fn check(spec: &Value, aggregation: &Aggregation) -> JournalOutput {
+    let mut children = Vec::with_capacity(aggregation.children.len());
+    let mut decoded = true;
+    for AggregatedChild { image_id, journal } in &aggregation.children {
+        let result = match risc0_zkvm::serde::from_slice::<JournalOutput, u8>(journal) {
+            Ok(child) => child.result,
+            Err(_) => {
+                decoded = false;
+                ComplianceResult::Error
+            }
+        };
+        children.push(ChildCommitment { image_id: image_id.to_vec(), journal_digest: Sha256::digest(journal).to_vec(), result });
+    }
+
+    let mut output = if decoded {
+        score(spec, &children).unwrap_or_else(JournalOutput::error)
+    } else {
+        JournalOutput::error(ErrorCode::ChildJournal)
+    };
+    output.children = children;
+    output
+}
+
+fn score(spec: &Value, children: &[ChildCommitment]) -> Result<JournalOutput, ErrorCode> {
+    let threshold = score::pass_threshold(spec)?;
+    let passed = children.iter().filter(|child| child.result == ComplianceResult::Pass).count();
+    Ok(JournalOutput::from_score(Score::new(passed as u64, children.len() as u64, threshold)))
+}
@@ -0,0 +1,19 @@
+//! Guest program with only the C2PA, Ed25519 and JSON parsing checkers
+//!
+//! Leaves the other checker families out, so changing them does not change
+//! this guest's image ID.
+
+#![no_main]
+#![no_std]
+
+use risc0_zkvm::guest::env;
+use fuse_guest::checker;
+
+#[cfg(any(feature = "soc2", feature = "gdpr", feature = "supply-chain", feature = "ml-model"))]
+compile_error!("fuse-guest-c2pa must be built with --no-default-features --features c2pa-guest");
+
+risc0_zkvm::guest::entry!(main);
+
+fn main() {
+    env::commit(&checker::execute_checker());
+}
@@ -0,0 +1,19 @@
+//! Guest program with only the ML model usage checker
+//!
+//! Leaves the other checker families out, so changing them does not change
+//! this guest's image ID.
+
+#![no_main]
+#![no_std]
+
+use risc0_zkvm::guest::env;
+use fuse_guest::checker;
+
+#[cfg(any(feature = "soc2", feature = "gdpr", feature = "supply-chain", feature = "c2pa"))]
+compile_error!("fuse-guest-ml-model must be built with --no-default-features --features ml-model-guest");
+
+risc0_zkvm::guest::entry!(main);
+
+fn main() {
+    env::commit(&checker::execute_checker());
+}
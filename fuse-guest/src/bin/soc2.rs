@@ -0,0 +1,19 @@
+//! Guest program with only the SOC2 checker
+//!
+//! Leaves the other checker families out, so changing them does not change
+//! this guest's image ID.
+
+#![no_main]
+#![no_std]
+
+use risc0_zkvm::guest::env;
+use fuse_guest::checker;
+
+#[cfg(any(feature = "gdpr", feature = "supply-chain", feature = "ml-model", feature = "c2pa"))]
+compile_error!("fuse-guest-soc2 must be built with --no-default-features --features soc2-guest");
+
+risc0_zkvm::guest::entry!(main);
+
+fn main() {
+    env::commit(&checker::execute_checker());
+}
@@ -0,0 +1,19 @@
+//! Guest program with only the supply chain provenance checker
+//!
+//! Leaves the other checker families out, so changing them does not change
+//! this guest's image ID.
+
+#![no_main]
+#![no_std]
+
+use risc0_zkvm::guest::env;
+use fuse_guest::checker;
+
+#[cfg(any(feature = "soc2", feature = "gdpr", feature = "ml-model", feature = "c2pa"))]
+compile_error!("fuse-guest-supply-chain must be built with --no-default-features --features supply-chain-guest");
+
+risc0_zkvm::guest::entry!(main);
+
+fn main() {
+    env::commit(&checker::execute_checker());
+}
@@ -8,12 +8,79 @@ use alloc::string::String;
 use alloc::vec::Vec;
 use serde_json::Value;
 use serde::{Serialize, Deserialize};
+use serde::de::IgnoredAny;
+use crate::aggregate::{self, Aggregation, ChildCommitment};
+use crate::composite::{self, NodeResult};
+use crate::disclosure::{self, PredicateResult};
+use crate::evidence::{self, EvidenceCommitment, EvidenceFile};
+use crate::incremental::{self, Incremental, LogState};
+use crate::merkle;
+use crate::private_params::{self, ParamCommitment};
+use crate::score::Score;
+use crate::stream::{self, Stream};
+use crate::typed::TypedSystemData;
 
 /// Result of a compliance check
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ComplianceResult {
     Pass = 0,
     Fail = 1,
+    /// The check could not be evaluated because the inputs were malformed.
+    /// The reason is committed in `JournalOutput::error_code`.
+    Error = 2,
+}
+
+/// Reason a check was inconclusive
+///
+/// Committed to the journal so verifiers can tell "non-compliant" apart
+/// from "evidence was malformed".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ErrorCode {
+    /// No error (result is Pass or Fail)
+    None = 0,
+    /// The compliance spec is not valid JSON
+    SpecParse = 1,
+    /// The system data is not valid JSON
+    SystemDataParse = 2,
+    /// A constraint required by the checker is missing from the spec
+    MissingConstraint = 3,
+    /// A field required by the checker is missing from the system data
+    MissingField = 4,
+    /// A record in the system data has an unexpected shape
+    MalformedRecord = 5,
+    /// A hex-encoded value could not be decoded
+    InvalidEncoding = 6,
+    /// A key or signature has the wrong length
+    InvalidLength = 7,
+    /// A public key could not be parsed
+    InvalidKey = 8,
+    /// The spec's `pass_threshold` is not a fraction between 0 and 1
+    InvalidThreshold = 9,
+    /// The spec's `composite` tree is malformed
+    InvalidComposite = 10,
+    /// A composite node is inconclusive because some of its sub-claims errored
+    SubClaimError = 11,
+    /// The spec's `rule` is not a valid expression
+    InvalidRule = 12,
+    /// The spec's `rule` applies an operator to values of the wrong type
+    RuleType = 13,
+    /// A private parameter is missing or does not match its commitment
+    PrivateParam = 14,
+    /// No longer produced: a batch of specs cannot be given typed system
+    /// data. Kept so that later codes keep their numbers.
+    TypedInput = 15,
+    /// The spec's checker cannot read streamed records, or the spec needs
+    /// the full JSON document
+    StreamInput = 16,
+    /// A child journal of an aggregation, or the previous period's journal
+    /// of an incremental proof, could not be decoded
+    ChildJournal = 17,
+    /// The previous period of an incremental proof errored, is not
+    /// incremental, or is for a different spec or private parameters
+    IncrementalState = 18,
+    /// The claim's checker is not compiled into this guest program; prove
+    /// it with the checker's own guest or the general one
+    CheckerNotInGuest = 19,
 }
 
 /// The complete output committed to the journal
@@ -24,6 +91,73 @@ pub struct JournalOutput {
     pub claim_hash: Vec<u8>,
     // Serialize JSON as string to avoid RISC Zero journal format issues
     pub redacted_json: String,
+    // ErrorCode::None unless result is Error
+    pub error_code: ErrorCode,
+    // Zero denominator unless the checker produced a graded score
+    pub score: Score,
+    // Per-node results of a composite spec in pre-order; empty otherwise
+    pub node_results: Vec<NodeResult>,
+    // Disclosed field paths that did not resolve in the system data
+    pub missing_fields: Vec<String>,
+    // Salted Merkle root over all system data fields; empty unless the spec sets commit_fields
+    pub fields_root: Vec<u8>,
+    // Outcomes of the spec's disclosure predicates, in spec order
+    pub predicates: Vec<PredicateResult>,
+    // Commitments of the private parameters applied, in name order
+    pub private_params: Vec<ParamCommitment>,
+    // Digests of the spec, the system data and any extra evidence files
+    pub evidence: Vec<EvidenceCommitment>,
+    // One output per spec of a batch, in input order, each committing its
+    // spec's digest; empty for a single spec
+    pub batch: Vec<JournalOutput>,
+    // Image ID, journal digest and result of each aggregated child proof,
+    // in input order, or of the previous period of an incremental proof
+    pub children: Vec<ChildCommitment>,
+    // Cumulative log commitment and counters; empty root unless incremental
+    pub log_state: LogState,
+}
+
+impl JournalOutput {
+    /// Output for a checker that only produces a Pass/Fail result
+    #[must_use]
+    pub fn from_result(result: ComplianceResult) -> Self {
+        Self { result, claim_hash: Vec::new(), redacted_json: String::new(), error_code: ErrorCode::None, score: Score::default(), node_results: Vec::new(), missing_fields: Vec::new(), fields_root: Vec::new(), predicates: Vec::new(), private_params: Vec::new(), evidence: Vec::new(), batch: Vec::new(), children: Vec::new(), log_state: LogState::default() }
+    }
+
+    /// Output for a graded checker; Pass/Fail is derived from the score
+    #[must_use]
+    pub fn from_score(score: Score) -> Self {
+        Self { score, ..Self::from_result(score.result()) }
+    }
+
+    /// Output for a check that could not be evaluated
+    #[must_use]
+    pub fn error(code: ErrorCode) -> Self {
+        Self { error_code: code, ..Self::from_result(ComplianceResult::Error) }
+    }
+}
+
+/// What to prove, and the system data to prove it over
+///
+/// Mirrors the host's `ProofMode` as it passes it, with child and previous
+/// period receipts reduced to their journals; variants and field order
+/// must match. Streamed records follow the other inputs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ProofMode {
+    Single { spec_json: String, system_data_json: String, parse_full_json: bool },
+    Bundle { spec_json: String, documents: Vec<EvidenceFile> },
+    Typed { spec_json: String, system_data: TypedSystemData },
+    Batch { spec_jsons: Vec<String>, system_data: BatchData },
+    Stream { spec_json: String },
+    Incremental { spec_json: String, previous: Incremental },
+    Aggregate { spec_json: String, aggregation: Aggregation },
+}
+
+/// System data a batch of specs is checked against
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum BatchData {
+    Json(String),
+    Bundle(Vec<EvidenceFile>),
 }
 
 /// Execute a compliance check
@@ -33,51 +167,383 @@ pub struct JournalOutput {
 #[must_use] 
 pub fn execute_checker() -> JournalOutput {
     // Read inputs from host
-    let spec_json: String = env::read();
-    let system_data_json: String = env::read();
-    
-    // Parse JSON inputs
-    let spec: Value = match serde_json::from_str(&spec_json) {
-        Ok(v) => v,
-        Err(_) => return JournalOutput { result: ComplianceResult::Fail, claim_hash: Vec::new(), redacted_json: String::new() },
+    let mode: ProofMode = env::read();
+    // Private seed for field commitment salts; never committed
+    let salt_seed: [u8; 32] = env::read();
+    // Private spec parameters with their salts; only commitments are public
+    let private_params_json: String = env::read();
+    // Extra evidence files; committed by digest only
+    let evidence_files: Vec<EvidenceFile> = env::read();
+
+    verify_journals(&mode);
+    execute(mode, &salt_seed, &private_params_json, &evidence_files, Stream::new())
+}
+
+/// Add an assumption for each child or previous period journal, which only
+/// a receipt for that journal resolves
+fn verify_journals(mode: &ProofMode) {
+    let verify = |image_id: [u32; 8], journal: &[u8]| env::verify(image_id, journal).unwrap_or_else(|never| match never {});
+    match mode {
+        ProofMode::Aggregate { aggregation, .. } => {
+            for child in &aggregation.children {
+                verify(child.image_id, &child.journal);
+            }
+        }
+        ProofMode::Incremental { previous, .. } if !previous.previous_journal.is_empty() => {
+            verify(previous.image_id, &previous.previous_journal);
+        }
+        _ => {}
+    }
+}
+
+/// Run the checks `mode` asks for over inputs already read from the host
+///
+/// `stream` holds the records of the streamed modes. The journals of child
+/// and previous period proofs must already be verified.
+#[must_use]
+pub fn execute(mode: ProofMode, salt_seed: &[u8; 32], private_params_json: &str, evidence_files: &[EvidenceFile], mut stream: Stream) -> JournalOutput {
+    // Each mode commits to the exact evidence bytes, whatever the outcome of the check
+    match mode {
+        ProofMode::Single { spec_json, system_data_json, parse_full_json } => {
+            let source = Source::Json { text: &system_data_json, parse_full_json };
+            let mut output = check_inputs(&spec_json, source, salt_seed, private_params_json);
+            output.evidence = evidence::commit(&spec_json, &system_data_json, &[], evidence_files);
+            output
+        }
+        ProofMode::Bundle { spec_json, documents } => {
+            let source = Source::Document(evidence::bundle_document(&documents));
+            let mut output = check_inputs(&spec_json, source, salt_seed, private_params_json);
+            output.evidence = evidence::commit(&spec_json, "", &documents, evidence_files);
+            output
+        }
+        ProofMode::Typed { spec_json, system_data } => {
+            // Typed input is committed as its canonical JSON form
+            let document = system_data.into_document();
+            let system_data_json = document.as_ref().map(crate::typed::canonical_json).unwrap_or_default();
+            let mut output = check_inputs(&spec_json, Source::Document(document), salt_seed, private_params_json);
+            output.evidence = evidence::commit(&spec_json, &system_data_json, &[], evidence_files);
+            output
+        }
+        ProofMode::Batch { spec_jsons, system_data } => {
+            let (system_data_json, documents) = match system_data {
+                BatchData::Json(json) => (json, Vec::new()),
+                BatchData::Bundle(documents) => (String::new(), documents),
+            };
+            let system_data = parse_system_data(&system_data_json, &documents);
+            let mut output = check_batch(&spec_jsons, system_data, salt_seed, private_params_json);
+            output.evidence = evidence::commit_system_data(&system_data_json, &documents, evidence_files);
+            output
+        }
+        ProofMode::Stream { spec_json } => {
+            let mut output = check_stream(&spec_json, private_params_json, &mut stream);
+            output.evidence = evidence::commit_streamed(&spec_json, stream.finish(), evidence_files);
+            output
+        }
+        ProofMode::Incremental { spec_json, previous } => {
+            let mut output = incremental::check(&spec_json, private_params_json, &previous, &mut stream);
+            let records = stream.finish();
+            incremental::advance(&mut output, &records);
+            output.evidence = evidence::commit_streamed(&spec_json, records, evidence_files);
+            output
+        }
+        ProofMode::Aggregate { spec_json, aggregation } => {
+            let mut output = match parse_spec(&spec_json, private_params_json) {
+                Ok((spec, param_commitments)) => {
+                    let mut output = aggregate::check(&spec, &aggregation);
+                    output.private_params = param_commitments;
+                    output
+                }
+                Err(code) => JournalOutput::error(code),
+            };
+            output.evidence = evidence::commit_spec(&spec_json);
+            output
+        }
+    }
+}
+
+/// System data of a single spec
+enum Source<'a> {
+    /// JSON text, read field by field when the checker is all that looks
+    /// at it unless `parse_full_json` is set
+    Json { text: &'a str, parse_full_json: bool },
+    /// A document already built from bundled or typed input
+    Document(Result<Value, ErrorCode>),
+}
+
+/// Parse the inputs and run the spec's checks
+fn check_inputs(spec_json: &str, source: Source<'_>, salt_seed: &[u8; 32], private_params_json: &str) -> JournalOutput {
+    let (spec, param_commitments) = match parse_spec(spec_json, private_params_json) {
+        Ok(parsed) => parsed,
+        Err(code) => return JournalOutput::error(code),
+    };
+
+    let system_data = match source {
+        Source::Json { text, parse_full_json } => {
+            if !parse_full_json {
+                if let Some(mut output) = check_json(&spec, text) {
+                    output.private_params = param_commitments;
+                    return output;
+                }
+            }
+            serde_json::from_str(text).map_err(|_| ErrorCode::SystemDataParse)
+        }
+        Source::Document(document) => document,
     };
-    
-    let system_data: Value = match serde_json::from_str(&system_data_json) {
+    let system_data = match system_data {
         Ok(v) => v,
-        Err(_) => return JournalOutput { result: ComplianceResult::Fail, claim_hash: Vec::new(), redacted_json: String::new() },
+        Err(code) => return JournalOutput::error(code),
     };
-    
-    // Extract claim type from spec
-    let claim = spec.get("claim")
-        .and_then(|v| v.as_str())
-        .unwrap_or("");
-    
-    // Route to appropriate checker based on claim
-    if claim.contains("JSON parsing only") {
-        crate::checkers::json_only::check(&spec, &system_data)
-    } else if claim.contains("C2PA") || claim.contains("C2PA signature") {
-        crate::checkers::c2pa::check(&spec, &system_data)
-    } else if claim.contains("SOC2") {
-        let result = crate::checkers::soc2::check(&spec, &system_data);
-        JournalOutput { result, claim_hash: Vec::new(), redacted_json: String::new() }
-    } else if claim.contains("GDPR") {
-        let result = crate::checkers::gdpr::check(&spec, &system_data);
-        JournalOutput { result, claim_hash: Vec::new(), redacted_json: String::new() }
-    } else if claim.contains("Supply chain") || claim.contains("provenance") {
-        let result = crate::checkers::supply_chain::check(&spec, &system_data);
-        JournalOutput { result, claim_hash: Vec::new(), redacted_json: String::new() }
-    } else if claim.contains("ML model") || claim.contains("usage constraint") {
-        let result = crate::checkers::ml_model::check(&spec, &system_data);
-        JournalOutput { result, claim_hash: Vec::new(), redacted_json: String::new() }
-    } else if claim.contains("Ed25519") || claim.contains("signature verification") {
-        let result = crate::checkers::ed25519::check(&spec, &system_data);
-        JournalOutput { result, claim_hash: Vec::new(), redacted_json: String::new() }
+
+    let mut output = check_document(&spec, &system_data, salt_seed);
+    output.private_params = param_commitments;
+    output
+}
+
+/// Check every spec of a batch against the system data, parsed once
+///
+/// The batch passes when every spec passes; any failing spec fails it,
+/// and otherwise an errored spec leaves it inconclusive.
+fn check_batch(
+    spec_jsons: &[String],
+    system_data: Result<Value, ErrorCode>,
+    salt_seed: &[u8; 32],
+    private_params_json: &str,
+) -> JournalOutput {
+    let batch: Vec<JournalOutput> = spec_jsons
+        .iter()
+        .map(|spec_json| {
+            let mut entry = match (parse_spec(spec_json, private_params_json), system_data.as_ref()) {
+                (Ok((spec, param_commitments)), Ok(system_data)) => {
+                    let mut entry = check_document(&spec, system_data, salt_seed);
+                    entry.private_params = param_commitments;
+                    entry
+                }
+                (Err(code), _) | (_, Err(&code)) => JournalOutput::error(code),
+            };
+            entry.evidence = evidence::commit_spec(spec_json);
+            entry
+        })
+        .collect();
+
+    let mut output = if batch.iter().any(|entry| entry.result == ComplianceResult::Fail) {
+        JournalOutput::from_result(ComplianceResult::Fail)
+    } else if batch.iter().any(|entry| entry.result == ComplianceResult::Error) {
+        JournalOutput::error(ErrorCode::SubClaimError)
     } else {
-        // Default: basic validation
-        if !spec_json.is_empty() && !system_data_json.is_empty() {
-            JournalOutput { result: ComplianceResult::Pass, claim_hash: Vec::new(), redacted_json: String::new() }
+        JournalOutput::from_result(ComplianceResult::Pass)
+    };
+    output.batch = batch;
+    output
+}
+
+/// The system data as one JSON document
+fn parse_system_data(system_data_json: &str, evidence_bundle: &[EvidenceFile]) -> Result<Value, ErrorCode> {
+    if evidence_bundle.is_empty() {
+        serde_json::from_str(system_data_json).map_err(|_| ErrorCode::SystemDataParse)
+    } else {
+        evidence::bundle_document(evidence_bundle)
+    }
+}
+
+/// Run the spec's checks over the parsed system data
+fn check_document(spec: &Value, system_data: &Value, salt_seed: &[u8; 32]) -> JournalOutput {
+    // Composite specs combine several sub-claims; everything else is a single claim
+    let mut output = match spec.get("composite") {
+        Some(node) => composite::evaluate(node, system_data),
+        None => run_claim(spec, system_data),
+    };
+
+    // Selective disclosure applies to every checker's system data
+    if output.result != ComplianceResult::Error {
+        if let Some(fields) = spec.get("disclosed_fields").and_then(Value::as_array) {
+            let disclosure = disclosure::disclose(fields, system_data, legacy_disclosure_root(spec, system_data));
+            output.redacted_json = serde_json::to_string(&disclosure.redacted).unwrap_or_default();
+            output.missing_fields = disclosure.missing;
+        }
+        if let Some(predicates) = spec.get("disclosure_predicates").and_then(Value::as_array) {
+            output.predicates = disclosure::evaluate_predicates(predicates, system_data);
+        }
+    }
+
+    // Commit to every field so more can be revealed later without re-proving
+    if spec.get("commit_fields").and_then(Value::as_bool).unwrap_or(false) {
+        output.fields_root = merkle::fields_root(system_data, salt_seed).to_vec();
+    }
+
+    output
+}
+
+/// Parse the spec and fill in its private parameters
+pub fn parse_spec(spec_json: &str, private_params_json: &str) -> Result<(Value, Vec<ParamCommitment>), ErrorCode> {
+    // Parse JSON inputs
+    let mut spec: Value = serde_json::from_str(spec_json).map_err(|_| ErrorCode::SpecParse)?;
+
+    let private_params: Value = if private_params_json.is_empty() {
+        Value::Object(serde_json::Map::new())
+    } else {
+        serde_json::from_str(private_params_json).map_err(|_| ErrorCode::PrivateParam)?
+    };
+    let param_commitments = private_params::apply(&mut spec, &private_params)?;
+    Ok((spec, param_commitments))
+}
+
+/// Run the spec's checker over streamed records
+fn check_stream(spec_json: &str, private_params_json: &str, stream: &mut Stream) -> JournalOutput {
+    let (spec, param_commitments) = match parse_spec(spec_json, private_params_json) {
+        Ok(parsed) => parsed,
+        Err(code) => return JournalOutput::error(code),
+    };
+    let mut output = stream::check(&spec, stream).unwrap_or_else(JournalOutput::error);
+    output.private_params = param_commitments;
+    output
+}
+
+/// Spec keys whose evaluation reads the whole system data document
+const FULL_DOCUMENT_KEYS: [&str; 5] = ["rule", "composite", "disclosed_fields", "disclosure_predicates", "commit_fields"];
+
+/// Whether the spec reads more of the system data than its checker does
+#[must_use]
+pub fn needs_full_document(spec: &Value) -> bool {
+    FULL_DOCUMENT_KEYS
+        .iter()
+        .any(|key| spec.get(key).is_some_and(|v| !v.is_null() && v != &Value::Bool(false)))
+}
+
+/// Run the spec's checker straight from the system data JSON
+///
+/// Each checker deserializes only the fields it reads, borrowing strings
+/// from the input instead of building a `Value` tree. Returns `None` when
+/// the spec needs the full document or the JSON does not have the shape
+/// the checker expects; the `Value` path then reports the exact error.
+fn check_json(spec: &Value, system_data_json: &str) -> Option<JournalOutput> {
+    if needs_full_document(spec) {
+        return None;
+    }
+    let output = match Route::for_spec(spec) {
+        #[cfg(feature = "c2pa")]
+        Route::JsonOnly => crate::checkers::json_only::check_json(system_data_json)?,
+        #[cfg(feature = "c2pa")]
+        Route::C2pa => crate::checkers::c2pa::check_json(system_data_json)?,
+        #[cfg(feature = "soc2")]
+        Route::Soc2 => crate::checkers::soc2::check_json(spec, system_data_json)?,
+        #[cfg(feature = "gdpr")]
+        Route::Gdpr => crate::checkers::gdpr::check_json(spec, system_data_json)?,
+        #[cfg(feature = "supply-chain")]
+        Route::SupplyChain => crate::checkers::supply_chain::check_json(spec, system_data_json)?,
+        #[cfg(feature = "ml-model")]
+        Route::MlModel => crate::checkers::ml_model::check_json(spec, system_data_json)?,
+        #[cfg(feature = "c2pa")]
+        Route::Ed25519 => crate::checkers::ed25519::check_json(system_data_json)?.map(JournalOutput::from_result),
+        // Default: the system data only has to be valid JSON
+        Route::Default => {
+            serde_json::from_str::<IgnoredAny>(system_data_json).ok()?;
+            Ok(JournalOutput::from_result(ComplianceResult::Pass))
+        }
+        #[allow(unreachable_patterns)]
+        _ => Err(ErrorCode::CheckerNotInGuest),
+    };
+    Some(output.unwrap_or_else(JournalOutput::error))
+}
+
+/// Root that bare `disclosed_fields` names are looked up in
+///
+/// The C2PA checkers have always disclosed top-level keys of the `claim`
+/// object; everything else discloses top-level keys of the system data.
+fn legacy_disclosure_root<'a>(spec: &Value, system_data: &'a Value) -> &'a Value {
+    let claim = spec.get("claim").and_then(Value::as_str).unwrap_or("");
+    let single_claim = spec.get("composite").is_none() && spec.get("rule").is_none();
+    if single_claim && (claim.contains("JSON parsing only") || claim.contains("C2PA")) {
+        system_data.get("claim").unwrap_or(system_data)
+    } else {
+        system_data
+    }
+}
+
+/// Route a single claim to its checker
+///
+/// Used for top-level specs and for the leaves of a composite spec.
+/// A spec with a `rule` is evaluated by the rule engine instead.
+#[must_use]
+pub fn run_claim(spec: &Value, system_data: &Value) -> JournalOutput {
+    if let Some(rule) = spec.get("rule") {
+        return crate::checkers::rule::check(rule, system_data).unwrap_or_else(JournalOutput::error);
+    }
+
+    // Route to appropriate checker based on claim
+    let output = match Route::for_spec(spec) {
+        #[cfg(feature = "c2pa")]
+        Route::JsonOnly => crate::checkers::json_only::check(spec, system_data),
+        #[cfg(feature = "c2pa")]
+        Route::C2pa => crate::checkers::c2pa::check(spec, system_data),
+        #[cfg(feature = "soc2")]
+        Route::Soc2 => crate::checkers::soc2::check(spec, system_data),
+        #[cfg(feature = "gdpr")]
+        Route::Gdpr => crate::checkers::gdpr::check(spec, system_data),
+        #[cfg(feature = "supply-chain")]
+        Route::SupplyChain => crate::checkers::supply_chain::check(spec, system_data),
+        #[cfg(feature = "ml-model")]
+        Route::MlModel => crate::checkers::ml_model::check(spec, system_data),
+        #[cfg(feature = "c2pa")]
+        Route::Ed25519 => crate::checkers::ed25519::check(spec, system_data).map(JournalOutput::from_result),
+        // Default: basic validation (both inputs parsed as JSON)
+        Route::Default => Ok(JournalOutput::from_result(ComplianceResult::Pass)),
+        #[allow(unreachable_patterns)]
+        _ => Err(ErrorCode::CheckerNotInGuest),
+    };
+
+    output.unwrap_or_else(JournalOutput::error)
+}
+
+/// Checker a claim is routed to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Route {
+    JsonOnly,
+    C2pa,
+    Soc2,
+    Gdpr,
+    SupplyChain,
+    MlModel,
+    Ed25519,
+    Default,
+}
+
+impl Route {
+    /// Route for the spec's `claim`; earlier patterns take precedence
+    #[must_use]
+    pub fn for_spec(spec: &Value) -> Self {
+        let claim = spec.get("claim")
+            .and_then(|v| v.as_str())
+            .unwrap_or("");
+
+        if claim.contains("JSON parsing only") {
+            Self::JsonOnly
+        } else if claim.contains("C2PA") {
+            Self::C2pa
+        } else if claim.contains("SOC2") {
+            Self::Soc2
+        } else if claim.contains("GDPR") {
+            Self::Gdpr
+        } else if claim.contains("Supply chain") || claim.contains("provenance") {
+            Self::SupplyChain
+        } else if claim.contains("ML model") || claim.contains("usage constraint") {
+            Self::MlModel
+        } else if claim.contains("Ed25519") || claim.contains("signature verification") {
+            Self::Ed25519
         } else {
-            JournalOutput { result: ComplianceResult::Fail, claim_hash: Vec::new(), redacted_json: String::new() }
+            Self::Default
+        }
+    }
+
+    /// Whether this guest program was built with the route's checker
+    #[must_use]
+    pub fn is_built(self) -> bool {
+        match self {
+            Self::JsonOnly | Self::C2pa | Self::Ed25519 => cfg!(feature = "c2pa"),
+            Self::Soc2 => cfg!(feature = "soc2"),
+            Self::Gdpr => cfg!(feature = "gdpr"),
+            Self::SupplyChain => cfg!(feature = "supply-chain"),
+            Self::MlModel => cfg!(feature = "ml-model"),
+            Self::Default => true,
         }
     }
 }
@@ -3,10 +3,23 @@
 //! This is a minimal Ed25519 signature verification implementation
 //! for benchmarking performance impact in zkVM.
 
+use serde::Deserialize;
 use serde_json::Value;
-use crate::checker::ComplianceResult;
+use alloc::borrow::Cow;
+use crate::checker::{ComplianceResult, ErrorCode};
 use ed25519_dalek::{VerifyingKey, Signature};
 
+/// The fields of the system data this checker reads, borrowed from the JSON
+#[derive(Deserialize)]
+struct SignedMessageJson<'a> {
+    #[serde(borrow)]
+    public_key: Cow<'a, str>,
+    #[serde(borrow)]
+    message: Cow<'a, str>,
+    #[serde(borrow)]
+    signature: Cow<'a, str>,
+}
+
 /// Check Ed25519 signature verification
 ///
 /// Expected `system_data` format:
@@ -15,78 +28,58 @@ use ed25519_dalek::{VerifyingKey, Signature};
 ///   "message": "<hex-encoded message bytes>",
 ///   "signature": "<hex-encoded 64-byte signature>"
 /// }
-#[must_use] 
-pub fn check(_spec: &Value, system_data: &Value) -> ComplianceResult {
+///
+/// Missing or undecodable fields are reported as `Err`; only a signature
+/// that does not verify is a `Fail`.
+pub fn check(_spec: &Value, system_data: &Value) -> Result<ComplianceResult, ErrorCode> {
     // Extract public key, message, and signature from system_data
-    let public_key_hex = match system_data.get("public_key")
+    let public_key_hex = system_data.get("public_key")
         .and_then(|v| v.as_str())
-    {
-        Some(key) => key,
-        None => return ComplianceResult::Fail,
-    };
+        .ok_or(ErrorCode::MissingField)?;
 
-    let message_hex = match system_data.get("message")
+    let message_hex = system_data.get("message")
         .and_then(|v| v.as_str())
-    {
-        Some(msg) => msg,
-        None => return ComplianceResult::Fail,
-    };
+        .ok_or(ErrorCode::MissingField)?;
 
-    let signature_hex = match system_data.get("signature")
+    let signature_hex = system_data.get("signature")
         .and_then(|v| v.as_str())
-    {
-        Some(sig) => sig,
-        None => return ComplianceResult::Fail,
-    };
+        .ok_or(ErrorCode::MissingField)?;
 
-    // Decode hex strings to bytes
-    let public_key_bytes = match hex::decode(public_key_hex) {
-        Ok(bytes) => bytes,
-        Err(_) => return ComplianceResult::Fail,
-    };
-
-    let message_bytes = match hex::decode(message_hex) {
-        Ok(bytes) => bytes,
-        Err(_) => return ComplianceResult::Fail,
-    };
+    verify(public_key_hex, message_hex, signature_hex)
+}
 
-    let signature_bytes = match hex::decode(signature_hex) {
-        Ok(bytes) => bytes,
-        Err(_) => return ComplianceResult::Fail,
-    };
+/// Check Ed25519 signature verification straight from the system data JSON
+///
+/// Returns `None` when the JSON does not have the expected shape; the
+/// `Value` path then reports the exact error.
+pub fn check_json(system_data_json: &str) -> Option<Result<ComplianceResult, ErrorCode>> {
+    let data: SignedMessageJson<'_> = serde_json::from_str(system_data_json).ok()?;
+    Some(verify(&data.public_key, &data.message, &data.signature))
+}
 
-    // Validate lengths
-    if public_key_bytes.len() != 32 {
-        return ComplianceResult::Fail;
-    }
-    if signature_bytes.len() != 64 {
-        return ComplianceResult::Fail;
-    }
+/// Verify a hex-encoded signature over a hex-encoded message
+fn verify(public_key_hex: &str, message_hex: &str, signature_hex: &str) -> Result<ComplianceResult, ErrorCode> {
+    // Decode hex strings to bytes
+    let public_key_bytes = hex::decode(public_key_hex).map_err(|_| ErrorCode::InvalidEncoding)?;
+    let message_bytes = hex::decode(message_hex).map_err(|_| ErrorCode::InvalidEncoding)?;
+    let signature_bytes = hex::decode(signature_hex).map_err(|_| ErrorCode::InvalidEncoding)?;
 
     // Parse public key and signature (ed25519-dalek API)
-    // Convert slices to fixed-size arrays
-    let public_key_array: [u8; 32] = match public_key_bytes.try_into() {
-        Ok(arr) => arr,
-        Err(_) => return ComplianceResult::Fail,
-    };
-    
-    let signature_array: [u8; 64] = match signature_bytes.try_into() {
-        Ok(arr) => arr,
-        Err(_) => return ComplianceResult::Fail,
-    };
+    // Convert slices to fixed-size arrays, which also validates lengths
+    let public_key_array: [u8; 32] = public_key_bytes.try_into()
+        .map_err(|_| ErrorCode::InvalidLength)?;
+    let signature_array: [u8; 64] = signature_bytes.try_into()
+        .map_err(|_| ErrorCode::InvalidLength)?;
 
-    let public_key = match VerifyingKey::from_bytes(&public_key_array) {
-        Ok(key) => key,
-        Err(_) => return ComplianceResult::Fail,
-    };
+    let public_key = VerifyingKey::from_bytes(&public_key_array)
+        .map_err(|_| ErrorCode::InvalidKey)?;
 
     // ed25519-dalek Signature::from_bytes doesn't return Result
     let signature = Signature::from_bytes(&signature_array);
 
     // Verify signature (ed25519-dalek uses verify_strict for better security)
     match public_key.verify_strict(&message_bytes, &signature) {
-        Ok(()) => ComplianceResult::Pass,
-        Err(_) => ComplianceResult::Fail,
+        Ok(()) => Ok(ComplianceResult::Pass),
+        Err(_) => Ok(ComplianceResult::Fail),
     }
 }
-
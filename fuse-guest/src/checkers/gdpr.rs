@@ -1,42 +1,114 @@
 //! GDPR compliance checker for guest program
 
+use serde::Deserialize;
 use serde_json::Value;
-use crate::checker::ComplianceResult;
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+use crate::checker::{ErrorCode, JournalOutput};
+use crate::score::{self, Score};
+use crate::stream::{FirstError, RecordTally, TallyState};
+
+/// The fields of the system data this checker reads, borrowed from the JSON
+#[derive(Deserialize)]
+struct GdprJson<'a> {
+    #[serde(borrow)]
+    storage_locations: Vec<LocationJson<'a>>,
+}
+
+#[derive(Deserialize)]
+struct LocationJson<'a> {
+    #[serde(borrow)]
+    region: Cow<'a, str>,
+}
 
 /// Check GDPR data residency compliance
-#[must_use] 
-pub fn check(spec: &Value, system_data: &Value) -> ComplianceResult {
-    // Extract required region from constraints
-    let required_region = match spec.get("constraints")
-        .and_then(|c| c.get("data_region"))
-        .and_then(|v| v.as_str())
-    {
-        Some(region) => region,
-        None => return ComplianceResult::Fail,
-    };
+///
+/// Scores the fraction of storage locations in the required region.
+pub fn check(spec: &Value, system_data: &Value) -> Result<JournalOutput, ErrorCode> {
+    let (threshold, required_region) = policy(spec)?;
 
     // Check data storage locations
-    let storage_locations = match system_data.get("storage_locations")
+    let storage_locations = system_data.get("storage_locations")
         .and_then(|v| v.as_array())
-    {
-        Some(locations) => locations,
-        None => return ComplianceResult::Fail,
-    };
+        .ok_or(ErrorCode::MissingField)?;
 
-    // Verify all storage is in the required region
+    // Count storage locations in the required region
+    let mut in_region: u64 = 0;
     for location in storage_locations {
-        let region = match location.get("region")
+        let region = location.get("region")
             .and_then(|v| v.as_str())
-        {
-            Some(region) => region,
-            None => return ComplianceResult::Fail,
-        };
+            .ok_or(ErrorCode::MissingField)?;
+
+        if region == required_region {
+            in_region += 1;
+        }
+    }
+
+    Ok(JournalOutput::from_score(Score::new(in_region, storage_locations.len() as u64, threshold)))
+}
+
+/// Check GDPR data residency compliance straight from the system data JSON
+///
+/// Returns `None` when the JSON does not have the expected shape; the
+/// `Value` path then reports the exact error.
+pub fn check_json(spec: &Value, system_data_json: &str) -> Option<Result<JournalOutput, ErrorCode>> {
+    let data: GdprJson<'_> = serde_json::from_str(system_data_json).ok()?;
+    Some(check_regions(spec, data.storage_locations.iter().map(|location| location.region.as_ref())))
+}
+
+/// Score the regions of well-formed storage locations
+fn check_regions<'a>(spec: &Value, regions: impl ExactSizeIterator<Item = &'a str>) -> Result<JournalOutput, ErrorCode> {
+    let (threshold, required_region) = policy(spec)?;
+    let total = regions.len() as u64;
+    let in_region = regions.filter(|region| *region == required_region).count();
+    Ok(JournalOutput::from_score(Score::new(in_region as u64, total, threshold)))
+}
+
+/// Streamed storage locations, one per line
+pub struct Tally<'a> {
+    threshold: (u64, u64),
+    required_region: &'a str,
+    total: u64,
+    in_region: u64,
+    errors: FirstError,
+}
+
+impl<'a> Tally<'a> {
+    pub fn new(spec: &'a Value, state: TallyState) -> Result<Self, ErrorCode> {
+        let (threshold, required_region) = policy(spec)?;
+        Ok(Self { threshold, required_region, total: state.records, in_region: state.conforming, errors: FirstError::default() })
+    }
+}
 
-        if region != required_region {
-            return ComplianceResult::Fail;
+impl RecordTally for Tally<'_> {
+    fn record(&mut self, line: &[u8]) {
+        self.total += 1;
+        match serde_json::from_slice::<LocationJson<'_>>(line) {
+            Ok(location) if location.region == self.required_region => self.in_region += 1,
+            Ok(_) => {}
+            Err(_) => self.errors.record(line, ErrorCode::MissingField),
         }
     }
 
-    ComplianceResult::Pass
+    fn finish(&self) -> Result<JournalOutput, ErrorCode> {
+        self.errors.check()?;
+        Ok(JournalOutput::from_score(Score::new(self.in_region, self.total, self.threshold)))
+    }
+
+    fn state(&self) -> TallyState {
+        TallyState { records: self.total, conforming: self.in_region }
+    }
 }
 
+/// Pass threshold and required region from the spec
+fn policy(spec: &Value) -> Result<((u64, u64), &str), ErrorCode> {
+    let threshold = score::pass_threshold(spec)?;
+
+    // Extract required region from constraints
+    let required_region = spec.get("constraints")
+        .and_then(|c| c.get("data_region"))
+        .and_then(|v| v.as_str())
+        .ok_or(ErrorCode::MissingConstraint)?;
+
+    Ok((threshold, required_region))
+}
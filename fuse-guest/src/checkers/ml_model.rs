@@ -1,54 +1,146 @@
 //! ML model usage constraint checker for guest program
 
+use serde::Deserialize;
 use serde_json::Value;
+use alloc::borrow::Cow;
 use alloc::vec::Vec;
-use crate::checker::ComplianceResult;
+use crate::checker::{ComplianceResult, ErrorCode, JournalOutput};
+use crate::score::{self, Score};
+use crate::stream::{FirstError, RecordTally, TallyState};
+
+/// The fields of the system data this checker reads, borrowed from the JSON
+#[derive(Deserialize)]
+struct MlModelJson<'a> {
+    #[serde(borrow)]
+    usage_logs: Vec<UsageLogJson<'a>>,
+}
+
+#[derive(Deserialize)]
+struct UsageLogJson<'a> {
+    #[serde(borrow, default)]
+    domain: Option<Cow<'a, str>>,
+}
+
+/// Constraints read from the spec
+struct Policy<'a> {
+    threshold: (u64, u64),
+    max_usage: u64,
+    allowed_domains: Vec<&'a str>,
+}
 
 /// Check ML model usage constraint compliance
-#[must_use] 
-pub fn check(spec: &Value, system_data: &Value) -> ComplianceResult {
-    // Extract usage constraints
-    let max_usage = spec.get("constraints")
-        .and_then(|c| c.get("max_usage"))
-        .and_then(|v| v.as_str())
-        .and_then(|s| s.parse::<u64>().ok())
-        .unwrap_or(u64::MAX);
-
-    let allowed_domains: Vec<&str> = spec.get("constraints")
-        .and_then(|c| c.get("allowed_domains"))
-        .and_then(|v| v.as_str())
-        .map(|s| s.split(',').map(str::trim).collect())
-        .unwrap_or_default();
+///
+/// Exceeding `max_usage` always fails. When domains are restricted, the
+/// fraction of usage logs in an allowed domain is scored.
+pub fn check(spec: &Value, system_data: &Value) -> Result<JournalOutput, ErrorCode> {
+    let policy = Policy::from_spec(spec)?;
 
     // Check model usage
-    let usage_logs = match system_data.get("usage_logs")
+    let usage_logs = system_data.get("usage_logs")
         .and_then(|v| v.as_array())
-    {
-        Some(logs) => logs,
-        None => return ComplianceResult::Fail,
-    };
-
-    // Check total usage count
-    if usage_logs.len() as u64 > max_usage {
-        return ComplianceResult::Fail;
+        .ok_or(ErrorCode::MissingField)?;
+
+    policy.score(usage_logs, |log| log.get("domain").and_then(|v| v.as_str()))
+}
+
+/// Check ML model usage constraint compliance straight from the system data JSON
+///
+/// Returns `None` when the JSON does not have the expected shape; the
+/// `Value` path then reports the exact error.
+pub fn check_json(spec: &Value, system_data_json: &str) -> Option<Result<JournalOutput, ErrorCode>> {
+    let data: MlModelJson<'_> = serde_json::from_str(system_data_json).ok()?;
+    Some(Policy::from_spec(spec).and_then(|policy| policy.score(&data.usage_logs, |log| log.domain.as_deref())))
+}
+
+/// Streamed usage logs, one per line
+pub struct Tally<'a> {
+    policy: Policy<'a>,
+    total: u64,
+    in_domain: u64,
+    errors: FirstError,
+}
+
+impl<'a> Tally<'a> {
+    pub fn new(spec: &'a Value, state: TallyState) -> Result<Self, ErrorCode> {
+        Ok(Self { policy: Policy::from_spec(spec)?, total: state.records, in_domain: state.conforming, errors: FirstError::default() })
     }
+}
 
-    // Check domain restrictions if specified
-    if !allowed_domains.is_empty() {
-        for log in usage_logs {
-            let domain = match log.get("domain")
-                .and_then(|v| v.as_str())
-            {
-                Some(domain) => domain,
-                None => return ComplianceResult::Fail,
-            };
-
-            if !allowed_domains.contains(&domain) {
-                return ComplianceResult::Fail;
-            }
+impl RecordTally for Tally<'_> {
+    fn record(&mut self, line: &[u8]) {
+        self.total += 1;
+        if self.policy.allowed_domains.is_empty() {
+            self.errors.validate(line);
+            return;
+        }
+        // A log that is not an object or whose domain is not a string has no domain
+        let domain = serde_json::from_slice::<UsageLogJson<'_>>(line).ok().and_then(|log| log.domain);
+        match domain {
+            Some(domain) if self.policy.allowed_domains.contains(&domain.as_ref()) => self.in_domain += 1,
+            Some(_) => {}
+            None => self.errors.record(line, ErrorCode::MissingField),
+        }
+    }
+
+    /// Same order of checks as [`Policy::score`]
+    fn finish(&self) -> Result<JournalOutput, ErrorCode> {
+        self.errors.parse_error()?;
+        if self.total > self.policy.max_usage {
+            return Ok(JournalOutput::from_result(ComplianceResult::Fail));
+        }
+        if self.policy.allowed_domains.is_empty() {
+            return Ok(JournalOutput::from_result(ComplianceResult::Pass));
         }
+        self.errors.check()?;
+        Ok(JournalOutput::from_score(Score::new(self.in_domain, self.total, self.policy.threshold)))
     }
 
-    ComplianceResult::Pass
+    fn state(&self) -> TallyState {
+        TallyState { records: self.total, conforming: self.in_domain }
+    }
 }
 
+impl<'a> Policy<'a> {
+    fn from_spec(spec: &'a Value) -> Result<Self, ErrorCode> {
+        let threshold = score::pass_threshold(spec)?;
+
+        // Extract usage constraints
+        let max_usage = spec.get("constraints")
+            .and_then(|c| c.get("max_usage"))
+            .and_then(|v| v.as_str())
+            .and_then(|s| s.parse::<u64>().ok())
+            .unwrap_or(u64::MAX);
+
+        let allowed_domains: Vec<&str> = spec.get("constraints")
+            .and_then(|c| c.get("allowed_domains"))
+            .and_then(|v| v.as_str())
+            .map(|s| s.split(',').map(str::trim).collect())
+            .unwrap_or_default();
+
+        Ok(Self { threshold, max_usage, allowed_domains })
+    }
+
+    /// Score usage logs, reading each log's domain with `domain`
+    fn score<'l, L>(&self, usage_logs: &'l [L], domain: impl Fn(&'l L) -> Option<&'l str>) -> Result<JournalOutput, ErrorCode> {
+        // Check total usage count
+        if usage_logs.len() as u64 > self.max_usage {
+            return Ok(JournalOutput::from_result(ComplianceResult::Fail));
+        }
+
+        // Check domain restrictions if specified
+        if self.allowed_domains.is_empty() {
+            return Ok(JournalOutput::from_result(ComplianceResult::Pass));
+        }
+
+        let mut in_domain: u64 = 0;
+        for log in usage_logs {
+            let domain = domain(log).ok_or(ErrorCode::MissingField)?;
+
+            if self.allowed_domains.contains(&domain) {
+                in_domain += 1;
+            }
+        }
+
+        Ok(JournalOutput::from_score(Score::new(in_domain, usage_logs.len() as u64, self.threshold)))
+    }
+}
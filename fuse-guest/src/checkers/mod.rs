@@ -1,10 +1,21 @@
 //! Individual checker implementations for guest program
+//!
+//! Each family is behind a feature of the same name, so a per-checker
+//! guest binary compiles in only its own (see `checker::Route::is_built`).
+//! The rule engine is in every guest.
 
+#[cfg(feature = "soc2")]
 pub mod soc2;
+#[cfg(feature = "gdpr")]
 pub mod gdpr;
+#[cfg(feature = "supply-chain")]
 pub mod supply_chain;
+#[cfg(feature = "ml-model")]
 pub mod ml_model;
+#[cfg(feature = "c2pa")]
 pub mod ed25519;
+#[cfg(feature = "c2pa")]
 pub mod c2pa;
+#[cfg(feature = "c2pa")]
 pub mod json_only;
-
+pub mod rule;
@@ -0,0 +1,26 @@
+//! Rule expression checker for guest program
+//!
+//! Evaluates the spec's `rule` against the system data with the shared
+//! `fuse-rules` engine, so customer-defined policies run on a stable guest image.
+
+use serde_json::Value;
+use fuse_rules::RuleError;
+use crate::checker::{ComplianceResult, ErrorCode, JournalOutput};
+
+pub fn check(rule: &Value, system_data: &Value) -> Result<JournalOutput, ErrorCode> {
+    let rule = rule.as_str().ok_or(ErrorCode::InvalidRule)?;
+
+    let passed = fuse_rules::evaluate(rule, system_data).map_err(error_code)?;
+
+    Ok(JournalOutput::from_result(if passed { ComplianceResult::Pass } else { ComplianceResult::Fail }))
+}
+
+/// Journal error code for a rule that could not be evaluated
+#[must_use]
+pub fn error_code(error: RuleError) -> ErrorCode {
+    match error {
+        RuleError::Parse { .. } => ErrorCode::InvalidRule,
+        RuleError::MissingPath(_) => ErrorCode::MissingField,
+        RuleError::Type(_) => ErrorCode::RuleType,
+    }
+}
@@ -1,47 +1,284 @@
 //! SOC2 compliance checker for guest program
 
+use core::fmt;
+use serde::{Deserialize, Deserializer};
+use serde::de::{DeserializeSeed, IgnoredAny, MapAccess, SeqAccess, Visitor};
 use serde_json::Value;
-use crate::checker::ComplianceResult;
+use alloc::borrow::Cow;
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use crate::checker::{ComplianceResult, ErrorCode, JournalOutput};
+use crate::score::{self, Score};
+use crate::stream::{FirstError, RecordTally, TallyState};
+
+/// The fields of the system data this checker reads, borrowed from the JSON
+#[derive(Deserialize)]
+struct Soc2Json<'a> {
+    #[serde(borrow)]
+    access_logs: Vec<BTreeMap<Cow<'a, str>, Set>>,
+}
+
+/// Constraints read from the spec
+struct Policy<'a> {
+    threshold: (u64, u64),
+    sample_size: usize,
+    required_fields: Vec<&'a str>,
+}
 
 /// Check SOC2 compliance
-#[must_use] 
-pub fn check(spec: &Value, system_data: &Value) -> ComplianceResult {
+///
+/// Scores the fraction of sampled access logs that set every required
+/// field (see [`is_set`]). Returns `Err` when the system data is malformed rather than
+/// non-compliant.
+pub fn check(spec: &Value, system_data: &Value) -> Result<JournalOutput, ErrorCode> {
+    let threshold = score::pass_threshold(spec)?;
+
     // Parse system data (expected to be JSON with access logs)
-    let logs = match system_data.get("access_logs")
+    let logs = system_data.get("access_logs")
         .and_then(|v| v.as_array())
-    {
-        Some(logs) => logs,
-        None => return ComplianceResult::Fail,
-    };
-
-    // Extract sampling constraint
-    let sample_size = spec.get("constraints")
-        .and_then(|c| c.get("sampling"))
-        .and_then(|s| s.as_str())
-        .and_then(|s| s.split_whitespace().last())
-        .and_then(|s| s.parse::<usize>().ok())
-        .unwrap_or(1000);
-
-    // Verify we have at least the required sample size
-    if logs.len() < sample_size {
-        return ComplianceResult::Fail;
-    }
-
-    // Check that all logs in the sample conform to control X
-    // For MVP, we check that each log has required fields
-    for log in logs.iter().take(sample_size) {
-        if !log.is_object() {
-            return ComplianceResult::Fail;
+        .ok_or(ErrorCode::MissingField)?;
+
+    let policy = Policy::from_spec(spec, threshold);
+    policy.score(logs, |log| {
+        let obj = log.as_object().ok_or(ErrorCode::MalformedRecord)?;
+        Ok(policy.required_fields.iter().all(|field| obj.get(*field).is_some_and(is_set)))
+    })
+}
+
+/// A log field counts as set unless it is `null`, `false` or `""`, so
+/// `"mfa": false` does not satisfy a required `mfa`
+#[must_use]
+pub fn is_set(value: &Value) -> bool {
+    !matches!(value, Value::Null | Value::Bool(false)) && value.as_str() != Some("")
+}
+
+/// Check SOC2 compliance straight from the system data JSON
+///
+/// Returns `None` when the JSON does not have the expected shape; the
+/// `Value` path then reports the exact error.
+pub fn check_json(spec: &Value, system_data_json: &str) -> Option<Result<JournalOutput, ErrorCode>> {
+    let data: Soc2Json<'_> = serde_json::from_str(system_data_json).ok()?;
+    Some(check_records(spec, &data.access_logs, |log, field| log.get(field).is_some_and(|set| set.0)))
+}
+
+/// Score well-formed logs, asking `field_set` whether a log sets a field
+fn check_records<L>(spec: &Value, logs: &[L], field_set: impl Fn(&L, &str) -> bool) -> Result<JournalOutput, ErrorCode> {
+    let policy = Policy::from_spec(spec, score::pass_threshold(spec)?);
+    policy.score(logs, |log| Ok(policy.required_fields.iter().all(|field| field_set(log, field))))
+}
+
+/// Streamed access logs, one per line
+pub struct Tally<'a> {
+    policy: Policy<'a>,
+    records: u64,
+    conforming: u64,
+    // Which required fields the current log sets; reused for every log
+    present: Vec<bool>,
+    errors: FirstError,
+}
+
+impl<'a> Tally<'a> {
+    pub fn new(spec: &'a Value, state: TallyState) -> Result<Self, ErrorCode> {
+        let policy = Policy::from_spec(spec, score::pass_threshold(spec)?);
+        let present = alloc::vec![false; policy.required_fields.len()];
+        Ok(Self { policy, records: state.records, conforming: state.conforming, present, errors: FirstError::default() })
+    }
+}
+
+impl RecordTally for Tally<'_> {
+    fn record(&mut self, line: &[u8]) {
+        self.records += 1;
+        if self.records > self.policy.sample_size as u64 {
+            self.errors.validate(line);
+            return;
         }
+        self.present.fill(false);
+        let fields = RequiredFields { required: &self.policy.required_fields, present: &mut self.present };
+        let mut deserializer = serde_json::Deserializer::from_slice(line);
+        match fields.deserialize(&mut deserializer).and_then(|()| deserializer.end()) {
+            Ok(()) => {
+                if self.present.iter().all(|&present| present) {
+                    self.conforming += 1;
+                }
+            }
+            Err(_) => self.errors.record(line, ErrorCode::MalformedRecord),
+        }
+    }
 
-        // Basic validation: ensure required fields exist
-        // In production, this would be more sophisticated
-        let obj = log.as_object().unwrap();
-        if !obj.contains_key("timestamp") || !obj.contains_key("user") {
-            return ComplianceResult::Fail;
+    /// Same order of checks as [`Policy::score`]
+    fn finish(&self) -> Result<JournalOutput, ErrorCode> {
+        self.errors.parse_error()?;
+        if self.records < self.policy.sample_size as u64 {
+            return Ok(JournalOutput::from_result(ComplianceResult::Fail));
         }
+        self.errors.check()?;
+        let sampled = self.records.min(self.policy.sample_size as u64);
+        Ok(JournalOutput::from_score(Score::new(self.conforming, sampled, self.policy.threshold)))
+    }
+
+    fn state(&self) -> TallyState {
+        TallyState { records: self.records, conforming: self.conforming }
+    }
+}
+
+/// Marks which required fields one access log object sets, comparing
+/// keys in place instead of allocating them
+struct RequiredFields<'p> {
+    required: &'p [&'p str],
+    present: &'p mut [bool],
+}
+
+impl<'de> DeserializeSeed<'de> for RequiredFields<'_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for RequiredFields<'_> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("an access log object")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        let Self { required, present } = self;
+        while let Some(key) = map.next_key_seed(FieldKey { required })? {
+            match key {
+                Some(key) => {
+                    let Set(set) = map.next_value()?;
+                    for (field, present) in required.iter().zip(present.iter_mut()) {
+                        if *field == key {
+                            *present = set;
+                        }
+                    }
+                }
+                None => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// One key of an access log: the required field it names, if any
+struct FieldKey<'p> {
+    required: &'p [&'p str],
+}
+
+impl<'de, 'p> DeserializeSeed<'de> for FieldKey<'p> {
+    type Value = Option<&'p str>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_str(self)
+    }
+}
+
+impl<'p> Visitor<'_> for FieldKey<'p> {
+    type Value = Option<&'p str>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a field name")
     }
 
-    ComplianceResult::Pass
+    fn visit_str<E>(self, key: &str) -> Result<Self::Value, E> {
+        Ok(self.required.iter().copied().find(|field| *field == key))
+    }
 }
 
+/// Whether one log value is set, in the sense of [`is_set`], read without
+/// building a `Value`
+struct Set(bool);
+
+impl<'de> Deserialize<'de> for Set {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(SetVisitor)
+    }
+}
+
+struct SetVisitor;
+
+impl<'de> Visitor<'de> for SetVisitor {
+    type Value = Set;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a JSON value")
+    }
+
+    fn visit_bool<E>(self, value: bool) -> Result<Set, E> {
+        Ok(Set(value))
+    }
+
+    fn visit_unit<E>(self) -> Result<Set, E> {
+        Ok(Set(false))
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Set, E> {
+        Ok(Set(!value.is_empty()))
+    }
+
+    fn visit_i64<E>(self, _: i64) -> Result<Set, E> {
+        Ok(Set(true))
+    }
+
+    fn visit_u64<E>(self, _: u64) -> Result<Set, E> {
+        Ok(Set(true))
+    }
+
+    fn visit_f64<E>(self, _: f64) -> Result<Set, E> {
+        Ok(Set(true))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Set, A::Error> {
+        while seq.next_element::<IgnoredAny>()?.is_some() {}
+        Ok(Set(true))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Set, A::Error> {
+        while map.next_entry::<IgnoredAny, IgnoredAny>()?.is_some() {}
+        Ok(Set(true))
+    }
+}
+
+impl<'a> Policy<'a> {
+    fn from_spec(spec: &'a Value, threshold: (u64, u64)) -> Self {
+        // Extract sampling constraint
+        let sample_size = spec.get("constraints")
+            .and_then(|c| c.get("sampling"))
+            .and_then(|s| s.as_str())
+            .and_then(|s| s.split_whitespace().last())
+            .and_then(|s| s.parse::<usize>().ok())
+            .unwrap_or(1000);
+
+        // Fields every log must carry (e.g. "timestamp,user,mfa")
+        let required_fields: Vec<&str> = spec.get("constraints")
+            .and_then(|c| c.get("required_fields"))
+            .and_then(|v| v.as_str())
+            .map_or_else(|| ["timestamp", "user"].into(), |s| s.split(',').map(str::trim).collect());
+
+        Self { threshold, sample_size, required_fields }
+    }
+
+    /// Score the sampled logs with `conforms`
+    fn score<L>(&self, logs: &[L], conforms: impl Fn(&L) -> Result<bool, ErrorCode>) -> Result<JournalOutput, ErrorCode> {
+        // Verify we have at least the required sample size
+        if logs.len() < self.sample_size {
+            return Ok(JournalOutput::from_result(ComplianceResult::Fail));
+        }
+
+        // Count the logs in the sample that conform to control X
+        let mut conforming: u64 = 0;
+        let mut sampled: u64 = 0;
+        for log in logs.iter().take(self.sample_size) {
+            if conforms(log)? {
+                conforming += 1;
+            }
+            sampled += 1;
+        }
+
+        Ok(JournalOutput::from_score(Score::new(conforming, sampled, self.threshold)))
+    }
+}
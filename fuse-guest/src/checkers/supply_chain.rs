@@ -1,44 +1,116 @@
 //! Supply chain provenance checker for guest program
 
+use serde::Deserialize;
 use serde_json::Value;
-use crate::checker::ComplianceResult;
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+use crate::checker::{ErrorCode, JournalOutput};
+use crate::score::{self, Score};
+use crate::stream::{FirstError, RecordTally, TallyState};
+
+/// The fields of the system data this checker reads, borrowed from the JSON
+#[derive(Deserialize)]
+struct SupplyChainJson<'a> {
+    #[serde(borrow)]
+    components: Vec<ComponentJson<'a>>,
+}
+
+#[derive(Deserialize)]
+struct ComponentJson<'a> {
+    #[serde(borrow)]
+    provenance: Cow<'a, str>,
+}
 
 /// Check supply chain provenance compliance
-#[must_use] 
-pub fn check(spec: &Value, system_data: &Value) -> ComplianceResult {
-    // Extract required provenance chain
-    let required_chain = match spec.get("constraints")
-        .and_then(|c| c.get("provenance_chain"))
-        .and_then(|v| v.as_str())
-    {
-        Some(chain) => chain,
-        None => return ComplianceResult::Fail,
-    };
+///
+/// Scores the fraction of components whose provenance matches the required chain.
+pub fn check(spec: &Value, system_data: &Value) -> Result<JournalOutput, ErrorCode> {
+    let (threshold, required_chain) = policy(spec)?;
 
     // Check components
-    let components = match system_data.get("components")
+    let components = system_data.get("components")
         .and_then(|v| v.as_array())
-    {
-        Some(components) => components,
-        None => return ComplianceResult::Fail,
-    };
+        .ok_or(ErrorCode::MissingField)?;
 
-    // Verify each component has valid provenance
+    // Count components with valid provenance
+    let mut verified: u64 = 0;
     for component in components {
-        let provenance = match component.get("provenance")
+        let provenance = component.get("provenance")
             .and_then(|v| v.as_str())
-        {
-            Some(provenance) => provenance,
-            None => return ComplianceResult::Fail,
-        };
+            .ok_or(ErrorCode::MissingField)?;
 
         // Basic validation: check that provenance matches required chain
         // In production, this would verify cryptographic signatures
-        if !provenance.contains(required_chain) {
-            return ComplianceResult::Fail;
+        if provenance.contains(required_chain) {
+            verified += 1;
+        }
+    }
+
+    Ok(JournalOutput::from_score(Score::new(verified, components.len() as u64, threshold)))
+}
+
+/// Check supply chain provenance compliance straight from the system data JSON
+///
+/// Returns `None` when the JSON does not have the expected shape; the
+/// `Value` path then reports the exact error.
+pub fn check_json(spec: &Value, system_data_json: &str) -> Option<Result<JournalOutput, ErrorCode>> {
+    let data: SupplyChainJson<'_> = serde_json::from_str(system_data_json).ok()?;
+    Some(check_provenance(spec, data.components.iter().map(|component| component.provenance.as_ref())))
+}
+
+/// Score the provenance of well-formed components
+fn check_provenance<'a>(spec: &Value, provenance: impl ExactSizeIterator<Item = &'a str>) -> Result<JournalOutput, ErrorCode> {
+    let (threshold, required_chain) = policy(spec)?;
+    let total = provenance.len() as u64;
+    let verified = provenance.filter(|chain| chain.contains(required_chain)).count();
+    Ok(JournalOutput::from_score(Score::new(verified as u64, total, threshold)))
+}
+
+/// Streamed components, one per line
+pub struct Tally<'a> {
+    threshold: (u64, u64),
+    required_chain: &'a str,
+    total: u64,
+    verified: u64,
+    errors: FirstError,
+}
+
+impl<'a> Tally<'a> {
+    pub fn new(spec: &'a Value, state: TallyState) -> Result<Self, ErrorCode> {
+        let (threshold, required_chain) = policy(spec)?;
+        Ok(Self { threshold, required_chain, total: state.records, verified: state.conforming, errors: FirstError::default() })
+    }
+}
+
+impl RecordTally for Tally<'_> {
+    fn record(&mut self, line: &[u8]) {
+        self.total += 1;
+        match serde_json::from_slice::<ComponentJson<'_>>(line) {
+            Ok(component) if component.provenance.contains(self.required_chain) => self.verified += 1,
+            Ok(_) => {}
+            Err(_) => self.errors.record(line, ErrorCode::MissingField),
         }
     }
 
-    ComplianceResult::Pass
+    fn finish(&self) -> Result<JournalOutput, ErrorCode> {
+        self.errors.check()?;
+        Ok(JournalOutput::from_score(Score::new(self.verified, self.total, self.threshold)))
+    }
+
+    fn state(&self) -> TallyState {
+        TallyState { records: self.total, conforming: self.verified }
+    }
 }
 
+/// Pass threshold and required provenance chain from the spec
+fn policy(spec: &Value) -> Result<((u64, u64), &str), ErrorCode> {
+    let threshold = score::pass_threshold(spec)?;
+
+    // Extract required provenance chain
+    let required_chain = spec.get("constraints")
+        .and_then(|c| c.get("provenance_chain"))
+        .and_then(|v| v.as_str())
+        .ok_or(ErrorCode::MissingConstraint)?;
+
+    Ok((threshold, required_chain))
+}
@@ -0,0 +1,176 @@
+//! Composite spec evaluation in zkVM
+//!
+//! A composite spec combines several sub-claims in a tree joined by
+//! `all`, `any` or `threshold` (k-of-n). Every leaf runs its checker in the
+//! same zkVM session, and the result of every node is committed alongside
+//! the aggregate so one envelope can attest to a whole control set.
+//!
+//! Node format (externally tagged, mirroring `fuse_core::composite::ClaimNode`):
+//! ```json
+//! { "all": [
+//!     { "check": { "id": "gdpr", "claim": "GDPR data residency", "constraints": { "data_region": "EU" } } },
+//!     { "threshold": { "k": 1, "of": [ ... ] } }
+//! ] }
+//! ```
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use serde::{Serialize, Deserialize};
+use serde_json::{Map, Value};
+use crate::checker::{self, ComplianceResult, ErrorCode, JournalOutput};
+use crate::score::Score;
+
+/// Maximum nesting depth of a composite tree
+const MAX_DEPTH: usize = 16;
+
+/// Result of one node in a composite tree, committed to the journal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeResult {
+    /// Location in the tree: "/" for the root, "/0/2" for the third child of the first child
+    pub path: String,
+    /// Leaf id, or the combinator ("all", "any", "2-of-3")
+    pub label: String,
+    pub result: ComplianceResult,
+    pub error_code: ErrorCode,
+    pub score: Score,
+}
+
+/// Evaluate a composite tree against the system data
+///
+/// Node results are recorded in pre-order. A malformed tree is an error
+/// for the whole spec, not a failed control.
+pub fn evaluate(node: &Value, system_data: &Value) -> JournalOutput {
+    let mut results = Vec::new();
+    match evaluate_node(node, system_data, String::from("/"), 0, &mut results) {
+        Ok(result) => {
+            let error_code = results.first().map_or(ErrorCode::None, |root| root.error_code);
+            JournalOutput { error_code, node_results: results, ..JournalOutput::from_result(result) }
+        }
+        Err(code) => JournalOutput::error(code),
+    }
+}
+
+fn evaluate_node(
+    node: &Value,
+    system_data: &Value,
+    path: String,
+    depth: usize,
+    results: &mut Vec<NodeResult>,
+) -> Result<ComplianceResult, ErrorCode> {
+    if depth > MAX_DEPTH {
+        return Err(ErrorCode::InvalidComposite);
+    }
+    let (kind, body) = single_entry(node)?;
+
+    if kind == "check" {
+        let label = body.get("id").and_then(Value::as_str).ok_or(ErrorCode::InvalidComposite)?;
+        let output = evaluate_leaf(body, system_data)?;
+        results.push(NodeResult {
+            path,
+            label: label.to_string(),
+            result: output.result,
+            error_code: output.error_code,
+            score: output.score,
+        });
+        return Ok(output.result);
+    }
+
+    let (children, required, label) = match kind {
+        "all" => {
+            let children = body.as_array().ok_or(ErrorCode::InvalidComposite)?;
+            (children, children.len(), String::from("all"))
+        }
+        "any" => {
+            let children = body.as_array().ok_or(ErrorCode::InvalidComposite)?;
+            (children, 1, String::from("any"))
+        }
+        "threshold" => {
+            let children = body.get("of").and_then(Value::as_array).ok_or(ErrorCode::InvalidComposite)?;
+            let k = body.get("k")
+                .and_then(Value::as_u64)
+                .and_then(|k| usize::try_from(k).ok())
+                .ok_or(ErrorCode::InvalidComposite)?;
+            (children, k, format!("{k}-of-{}", children.len()))
+        }
+        _ => return Err(ErrorCode::InvalidComposite),
+    };
+    if children.is_empty() || required == 0 || required > children.len() {
+        return Err(ErrorCode::InvalidComposite);
+    }
+
+    // Reserve this node's slot so results stay in pre-order
+    let slot = results.len();
+    results.push(NodeResult {
+        path: path.clone(),
+        label,
+        result: ComplianceResult::Error,
+        error_code: ErrorCode::None,
+        score: Score::default(),
+    });
+
+    let mut passed = 0;
+    let mut errored = 0;
+    for (i, child) in children.iter().enumerate() {
+        let child_path = if path == "/" { format!("/{i}") } else { format!("{path}/{i}") };
+        match evaluate_node(child, system_data, child_path, depth + 1, results)? {
+            ComplianceResult::Pass => passed += 1,
+            ComplianceResult::Error => errored += 1,
+            ComplianceResult::Fail => {}
+        }
+    }
+
+    // k-of-n with three-valued logic: enough passes is a Pass, too few
+    // possible passes is a Fail, otherwise the errors decide it
+    let result = if passed >= required {
+        ComplianceResult::Pass
+    } else if passed + errored < required {
+        ComplianceResult::Fail
+    } else {
+        ComplianceResult::Error
+    };
+    results[slot].result = result;
+    if result == ComplianceResult::Error {
+        results[slot].error_code = ErrorCode::SubClaimError;
+    }
+    Ok(result)
+}
+
+/// Run a single sub-claim's checker
+fn evaluate_leaf(body: &Value, system_data: &Value) -> Result<JournalOutput, ErrorCode> {
+    let claim = body.get("claim").and_then(Value::as_str).ok_or(ErrorCode::InvalidComposite)?;
+
+    // Build a standalone spec for the leaf so checkers see the usual shape
+    let mut spec = Map::new();
+    spec.insert("claim".into(), Value::String(claim.to_string()));
+    spec.insert("constraints".into(), body.get("constraints").cloned().unwrap_or_else(|| Value::Object(Map::new())));
+    for key in ["pass_threshold", "rule"] {
+        if let Some(value) = body.get(key) {
+            spec.insert(key.into(), value.clone());
+        }
+    }
+    let spec = Value::Object(spec);
+
+    // Optionally narrow the evidence to one top-level key of the system data
+    let data = match body.get("data_key").and_then(Value::as_str) {
+        Some(key) => match system_data.get(key) {
+            Some(data) => data,
+            None => return Ok(JournalOutput::error(ErrorCode::MissingField)),
+        },
+        None => system_data,
+    };
+
+    Ok(checker::run_claim(&spec, data))
+}
+
+/// Split a `{ "kind": body }` object into its single entry
+fn single_entry(node: &Value) -> Result<(&str, &Value), ErrorCode> {
+    let map = node.as_object().ok_or(ErrorCode::InvalidComposite)?;
+    if map.len() != 1 {
+        return Err(ErrorCode::InvalidComposite);
+    }
+    map.iter()
+        .next()
+        .map(|(kind, body)| (kind.as_str(), body))
+        .ok_or(ErrorCode::InvalidComposite)
+}
@@ -0,0 +1,157 @@
+//! Selective disclosure in zkVM
+//!
+//! Filters the system data down to the spec's `disclosed_fields` so only
+//! those values reach the journal. Entries starting with `/` are JSON
+//! Pointers (RFC 6901) into the system data, with `*` additionally
+//! matching every element of an array. Bare names are top-level keys of the
+//! checker's legacy disclosure root (the `claim` object for C2PA).
+//!
+//! Paths that do not resolve are reported rather than skipped, and nothing
+//! along a missing path is disclosed.
+//!
+//! Disclosure predicates reveal derived facts instead of values: each is a
+//! rule expression over the system data, and only its boolean outcome is
+//! committed.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use serde::{Serialize, Deserialize};
+use serde_json::{Map, Value};
+use crate::checker::ErrorCode;
+
+/// Outcome of one disclosure predicate, committed to the journal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PredicateResult {
+    pub name: String,
+    /// False whenever `error_code` is set
+    pub holds: bool,
+    pub error_code: ErrorCode,
+}
+
+/// Evaluate the spec's `disclosure_predicates` against the system data
+#[must_use]
+pub fn evaluate_predicates(predicates: &[Value], system_data: &Value) -> Vec<PredicateResult> {
+    predicates
+        .iter()
+        .map(|predicate| {
+            let name = predicate.get("name").and_then(Value::as_str).unwrap_or_default().to_string();
+            let outcome = predicate
+                .get("expr")
+                .and_then(Value::as_str)
+                .ok_or(ErrorCode::InvalidRule)
+                .and_then(|expr| fuse_rules::evaluate(expr, system_data).map_err(crate::checkers::rule::error_code));
+            match outcome {
+                Ok(holds) => PredicateResult { name, holds, error_code: ErrorCode::None },
+                Err(code) => PredicateResult { name, holds: false, error_code: code },
+            }
+        })
+        .collect()
+}
+
+/// Filtered document plus the paths that could not be resolved
+pub struct Disclosure {
+    pub redacted: Value,
+    pub missing: Vec<String>,
+}
+
+/// Apply `disclosed_fields` to the system data
+#[must_use]
+pub fn disclose(fields: &[Value], system_data: &Value, legacy_root: &Value) -> Disclosure {
+    let mut redacted = Value::Object(Map::new());
+    let mut missing = Vec::new();
+
+    for field in fields.iter().filter_map(Value::as_str) {
+        let found = match parse_pointer(field) {
+            Some(tokens) => {
+                let tokens: Vec<&str> = tokens.iter().map(String::as_str).collect();
+                resolves(system_data, &tokens) && {
+                    copy(system_data, &tokens, &mut redacted);
+                    true
+                }
+            }
+            // Legacy top-level name
+            None => match legacy_root.get(field) {
+                Some(value) => {
+                    if let Value::Object(map) = &mut redacted {
+                        map.insert(field.to_string(), value.clone());
+                    }
+                    true
+                }
+                None => false,
+            },
+        };
+        if !found {
+            missing.push(field.to_string());
+        }
+    }
+
+    Disclosure { redacted, missing }
+}
+
+/// Split a JSON Pointer into unescaped reference tokens.
+/// Returns `None` for bare names, which do not start with `/`.
+fn parse_pointer(path: &str) -> Option<Vec<String>> {
+    let rest = path.strip_prefix('/')?;
+    Some(rest.split('/').map(|token| token.replace("~1", "/").replace("~0", "~")).collect())
+}
+
+/// Array index per RFC 6901: decimal digits without leading zeros
+fn array_index(token: &str) -> Option<usize> {
+    if token.is_empty() || (token.len() > 1 && token.starts_with('0')) || !token.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    token.parse().ok()
+}
+
+/// Whether every value selected by the path exists
+fn resolves(source: &Value, tokens: &[&str]) -> bool {
+    let Some((token, rest)) = tokens.split_first() else {
+        return true;
+    };
+    match source {
+        Value::Object(map) => map.get(*token).is_some_and(|child| resolves(child, rest)),
+        Value::Array(items) if *token == "*" => items.iter().all(|child| resolves(child, rest)),
+        Value::Array(items) => array_index(token)
+            .and_then(|index| items.get(index))
+            .is_some_and(|child| resolves(child, rest)),
+        _ => false,
+    }
+}
+
+/// Copy the selected values into the redacted document, keeping their
+/// location. Undisclosed array elements before a disclosed index are null.
+/// The path must already resolve.
+fn copy(source: &Value, tokens: &[&str], out: &mut Value) {
+    let Some((token, rest)) = tokens.split_first() else {
+        *out = source.clone();
+        return;
+    };
+    match source {
+        Value::Object(map) => {
+            if !out.is_object() {
+                *out = Value::Object(Map::new());
+            }
+            if let (Some(child), Value::Object(out_map)) = (map.get(*token), out) {
+                copy(child, rest, out_map.entry(token.to_string()).or_insert(Value::Null));
+            }
+        }
+        Value::Array(items) => {
+            if !out.is_array() {
+                *out = Value::Array(Vec::new());
+            }
+            let Value::Array(out_items) = out else { return };
+            let selected: Vec<usize> = if *token == "*" {
+                (0..items.len()).collect()
+            } else {
+                array_index(token).into_iter().collect()
+            };
+            for index in selected {
+                if out_items.len() <= index {
+                    out_items.resize(index + 1, Value::Null);
+                }
+                copy(&items[index], rest, &mut out_items[index]);
+            }
+        }
+        _ => {}
+    }
+}
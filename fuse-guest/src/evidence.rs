@@ -0,0 +1,123 @@
+//! Evidence commitment in zkVM
+//!
+//! Every run commits a domain-separated SHA-256 of each input it was given:
+//! the spec JSON, the system data, and any extra evidence files the prover
+//! attached. In a dispute the prover hands over the evidence and an auditor
+//! recomputes the digests against the journal.
+//!
+//! With an evidence bundle the system data is an object of named JSON
+//! documents, each committed under its name in place of `system_data`.
+//!
+//! Mirrors `fuse_core::evidence`; the hashing scheme must match.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use serde::{Serialize, Deserialize};
+use serde_json::{Map, Value};
+use risc0_zkvm::sha::rust_crypto::{Digest, Sha256};
+use crate::checker::ErrorCode;
+
+const EVIDENCE_DOMAIN: &[u8] = b"fuse.evidence.v1";
+
+/// Evidence name of the spec JSON
+pub const SPEC: &str = "spec";
+/// Evidence name of the system data
+pub const SYSTEM_DATA: &str = "system_data";
+
+/// A named evidence file, attached or part of an evidence bundle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvidenceFile {
+    pub name: String,
+    pub bytes: Vec<u8>,
+}
+
+/// Digest of one piece of evidence, committed to the journal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvidenceCommitment {
+    pub name: String,
+    pub digest: Vec<u8>,
+}
+
+/// Commitments to the spec, the system data (or each bundle document) and
+/// every attached file, in that order
+#[must_use]
+pub fn commit(spec_json: &str, system_data_json: &str, bundle: &[EvidenceFile], files: &[EvidenceFile]) -> Vec<EvidenceCommitment> {
+    let mut commitments = commit_spec(spec_json);
+    commitments.extend(commit_system_data(system_data_json, bundle, files));
+    commitments
+}
+
+/// Commitment to the spec alone; each spec of a batch commits its own
+#[must_use]
+pub fn commit_spec(spec_json: &str) -> Vec<EvidenceCommitment> {
+    Vec::from([commitment(SPEC, spec_json.as_bytes())])
+}
+
+/// Commitments to the system data (or each bundle document) and every
+/// attached file, shared by all specs of a batch
+#[must_use]
+pub fn commit_system_data(system_data_json: &str, bundle: &[EvidenceFile], files: &[EvidenceFile]) -> Vec<EvidenceCommitment> {
+    let mut commitments = Vec::with_capacity(bundle.len() + files.len() + 1);
+    if bundle.is_empty() {
+        commitments.push(commitment(SYSTEM_DATA, system_data_json.as_bytes()));
+    }
+    commitments.extend(bundle.iter().chain(files).map(|file| commitment(&file.name, &file.bytes)));
+    commitments
+}
+
+/// System data formed by an evidence bundle: each document under its name
+///
+/// A name used twice would make the committed evidence ambiguous.
+pub fn bundle_document(bundle: &[EvidenceFile]) -> Result<Value, ErrorCode> {
+    let mut document = Map::new();
+    for file in bundle {
+        let value = serde_json::from_slice(&file.bytes).map_err(|_| ErrorCode::SystemDataParse)?;
+        if document.insert(file.name.clone(), value).is_some() {
+            return Err(ErrorCode::MalformedRecord);
+        }
+    }
+    Ok(Value::Object(document))
+}
+
+/// Commitments like [`commit`] for system data that was streamed in and
+/// hashed as it arrived
+#[must_use]
+pub fn commit_streamed(spec_json: &str, system_data: EvidenceCommitment, files: &[EvidenceFile]) -> Vec<EvidenceCommitment> {
+    let mut commitments = Vec::with_capacity(files.len() + 2);
+    commitments.push(commitment(SPEC, spec_json.as_bytes()));
+    commitments.push(system_data);
+    commitments.extend(files.iter().map(|file| commitment(&file.name, &file.bytes)));
+    commitments
+}
+
+/// Running evidence digest over bytes that arrive in pieces
+pub struct EvidenceHasher {
+    name: String,
+    hasher: Sha256,
+}
+
+impl EvidenceHasher {
+    #[must_use]
+    pub fn new(name: &str) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(EVIDENCE_DOMAIN);
+        hasher.update((name.len() as u64).to_be_bytes());
+        hasher.update(name.as_bytes());
+        Self { name: name.to_string(), hasher }
+    }
+
+    pub fn update(&mut self, bytes: &[u8]) {
+        self.hasher.update(bytes);
+    }
+
+    #[must_use]
+    pub fn finish(self) -> EvidenceCommitment {
+        EvidenceCommitment { name: self.name, digest: self.hasher.finalize().to_vec() }
+    }
+}
+
+fn commitment(name: &str, bytes: &[u8]) -> EvidenceCommitment {
+    let mut hasher = EvidenceHasher::new(name);
+    hasher.update(bytes);
+    hasher.finish()
+}
@@ -0,0 +1,108 @@
+//! Incremental proofs over append-only logs in zkVM
+//!
+//! Re-proving a whole year of access logs every week is wasteful. In
+//! incremental mode the host streams only the records appended since the
+//! previous period and passes the previous period's receipt as an
+//! assumption. The guest's entry point verifies it with `env::verify`; the
+//! guest then resumes the checker's counters from its journal, tallies only
+//! the new records and commits the updated state, so each envelope attests
+//! to the whole log.
+//!
+//! The cumulative log is committed as a hash chain over each period's
+//! `system_data` evidence digest; an auditor holding every period's JSON
+//! Lines file recomputes it. The previous period is committed like an
+//! aggregated child (image ID, journal digest and result), and it must be
+//! an incremental proof of the same spec with the same private parameters
+//! that did not error, so an error cannot be dropped by proving on top of it.
+//!
+//! Mirrors `fuse_core::incremental`; types, field order and the chain
+//! hashing must match.
+
+use alloc::vec::Vec;
+use risc0_zkvm::sha::rust_crypto::{Digest, Sha256};
+use serde::{Serialize, Deserialize};
+use crate::aggregate::ChildCommitment;
+use crate::checker::{self, ComplianceResult, ErrorCode, JournalOutput};
+use crate::evidence;
+use crate::stream::{self, Stream, TallyState};
+
+const LOG_DOMAIN: &[u8] = b"fuse.log.v1";
+
+/// The previous period's proof, passed by the host; no previous journal
+/// for the first period
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Incremental {
+    pub image_id: [u32; 8],
+    pub previous_journal: Vec<u8>,
+}
+
+/// State carried from one period to the next, committed to the journal
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LogState {
+    // Hash chain over every period's system data digest; empty unless incremental
+    pub root: Vec<u8>,
+    pub periods: u64,
+    pub tally: TallyState,
+}
+
+/// Next chain root after a period whose records have the given
+/// `system_data` evidence digest
+#[must_use]
+pub fn chain(root: &[u8], digest: &[u8]) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    hasher.update(LOG_DOMAIN);
+    hasher.update((root.len() as u64).to_be_bytes());
+    hasher.update(root);
+    hasher.update(digest);
+    hasher.finalize().to_vec()
+}
+
+/// Check the streamed records of one period, continuing from the previous
+/// period's state; its journal must already be verified
+///
+/// The committed log state still needs the period's records chained in
+/// with [`advance`] once the stream is read to the end.
+#[must_use]
+pub fn check(spec_json: &str, private_params_json: &str, incremental: &Incremental, stream: &mut Stream) -> JournalOutput {
+    let previous = previous(spec_json, incremental);
+    match (previous, checker::parse_spec(spec_json, private_params_json)) {
+        (Err(code), _) | (_, Err(code)) => JournalOutput::error(code),
+        (Ok(previous), Ok((spec, param_commitments))) => {
+            let (state, params_match) = match &previous {
+                Some((journal, _)) => (journal.log_state.clone(), journal.private_params == param_commitments),
+                None => (LogState::default(), true),
+            };
+            let (result, tally) = stream::check_from(&spec, stream, state.tally);
+            let mut output = if params_match { result.unwrap_or_else(JournalOutput::error) } else { JournalOutput::error(ErrorCode::IncrementalState) };
+            output.private_params = param_commitments;
+            output.log_state = LogState { tally, ..state };
+            if let Some((_, commitment)) = previous {
+                output.children = Vec::from([commitment]);
+            }
+            output
+        }
+    }
+}
+
+/// Chain the period's records into the committed log state
+pub fn advance(output: &mut JournalOutput, records: &evidence::EvidenceCommitment) {
+    output.log_state.root = chain(&output.log_state.root, &records.digest);
+    output.log_state.periods += 1;
+}
+
+/// Decode the previous period's journal, if any
+fn previous(spec_json: &str, incremental: &Incremental) -> Result<Option<(JournalOutput, ChildCommitment)>, ErrorCode> {
+    let journal = &incremental.previous_journal;
+    if journal.is_empty() {
+        return Ok(None);
+    }
+    let previous = risc0_zkvm::serde::from_slice::<JournalOutput, u8>(journal).map_err(|_| ErrorCode::ChildJournal)?;
+
+    let spec = evidence::commit_spec(spec_json);
+    let same_spec = previous.evidence.first().is_some_and(|committed| committed.name == spec[0].name && committed.digest == spec[0].digest);
+    if previous.result == ComplianceResult::Error || previous.log_state.root.is_empty() || !same_spec {
+        return Err(ErrorCode::IncrementalState);
+    }
+    let commitment = ChildCommitment { image_id: incremental.image_id.to_vec(), journal_digest: Sha256::digest(journal).to_vec(), result: previous.result };
+    Ok(Some((previous, commitment)))
+}
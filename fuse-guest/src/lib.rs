@@ -7,8 +7,18 @@
 
 extern crate alloc;
 
+pub mod aggregate;
 pub mod checker;
 pub mod checkers;
+pub mod composite;
+pub mod disclosure;
+pub mod evidence;
+pub mod incremental;
+pub mod merkle;
+pub mod private_params;
+pub mod score;
+pub mod stream;
+pub mod typed;
 
 /// Main entry point for the guest program
 /// Reads spec and system data from host, executes checker, commits result
@@ -0,0 +1,102 @@
+//! Salted Merkle commitment over the system data in zkVM
+//!
+//! Every scalar in the system data (and every empty array or object)
+//! becomes a leaf keyed by its JSON Pointer. Leaves are sorted by pointer
+//! and salted with a value derived from a private seed, so the committed
+//! root reveals nothing on its own. The prover can later open individual
+//! fields against the root without re-proving.
+//!
+//! Mirrors `fuse_core::merkle`; the hashing scheme must match exactly.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use serde_json::Value;
+use risc0_zkvm::sha::rust_crypto::{Digest, Sha256};
+
+const SALT_DOMAIN: &[u8] = b"fuse.merkle.salt.v1";
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+/// Root of the salted field tree over `document`
+#[must_use]
+pub fn fields_root(document: &Value, seed: &[u8; 32]) -> [u8; 32] {
+    let mut leaves = Vec::new();
+    collect_leaves(document, &mut String::new(), &mut leaves);
+    leaves.sort_by(|a, b| a.0.as_bytes().cmp(b.0.as_bytes()));
+
+    let hashes: Vec<[u8; 32]> = leaves
+        .iter()
+        .map(|(path, value)| leaf_hash(&salt(seed, path), path, value))
+        .collect();
+    root(hashes)
+}
+
+/// Collect `(pointer, value)` pairs for every leaf under `value`
+fn collect_leaves<'a>(value: &'a Value, path: &mut String, leaves: &mut Vec<(String, &'a Value)>) {
+    let len = path.len();
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (key, child) in map {
+                path.push('/');
+                path.push_str(&key.replace('~', "~0").replace('/', "~1"));
+                collect_leaves(child, path, leaves);
+                path.truncate(len);
+            }
+        }
+        Value::Array(items) if !items.is_empty() => {
+            for (index, child) in items.iter().enumerate() {
+                path.push('/');
+                path.push_str(&index.to_string());
+                collect_leaves(child, path, leaves);
+                path.truncate(len);
+            }
+        }
+        _ => leaves.push((path.clone(), value)),
+    }
+}
+
+/// Per-field salt derived from the private seed
+fn salt(seed: &[u8; 32], path: &str) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(SALT_DOMAIN);
+    hasher.update(seed);
+    hasher.update(path.as_bytes());
+    hasher.finalize().into()
+}
+
+fn leaf_hash(salt: &[u8; 32], path: &str, value: &Value) -> [u8; 32] {
+    let value = serde_json::to_string(value).unwrap_or_default();
+    let mut hasher = Sha256::new();
+    hasher.update([LEAF_PREFIX]);
+    hasher.update(salt);
+    hasher.update((path.len() as u64).to_be_bytes());
+    hasher.update(path.as_bytes());
+    hasher.update(value.as_bytes());
+    hasher.finalize().into()
+}
+
+/// Fold leaf hashes pairwise; an unpaired last node is promoted unchanged
+fn root(mut level: Vec<[u8; 32]>) -> [u8; 32] {
+    if level.is_empty() {
+        return [0; 32];
+    }
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => node_hash(left, right),
+                [single] => *single,
+                _ => unreachable!("chunks(2) yields one or two items"),
+            })
+            .collect();
+    }
+    level[0]
+}
+
+fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([NODE_PREFIX]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
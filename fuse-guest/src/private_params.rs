@@ -0,0 +1,78 @@
+//! Private spec parameters in zkVM
+//!
+//! Confidential constraint values (internal thresholds, approved vendor
+//! lists) stay out of the public spec, which carries only a salted
+//! commitment per name in `private_constraints`. The values and salts
+//! arrive as a separate private input; each is checked against its
+//! commitment and then injected into `constraints` for the checkers.
+//!
+//! Mirrors `fuse_core::private_params`; the commitment scheme must match.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use serde::{Serialize, Deserialize};
+use serde_json::{Map, Value};
+use risc0_zkvm::sha::rust_crypto::{Digest, Sha256};
+use crate::checker::ErrorCode;
+
+const COMMITMENT_DOMAIN: &[u8] = b"fuse.private-param.v1";
+
+/// A private parameter's commitment, committed to the journal
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParamCommitment {
+    pub name: String,
+    pub commitment: Vec<u8>,
+}
+
+/// Check private parameters against the spec's commitments and inject them
+/// into the spec's constraints
+///
+/// Returns the verified commitments in name order.
+pub fn apply(spec: &mut Value, private_params: &Value) -> Result<Vec<ParamCommitment>, ErrorCode> {
+    let Some(commitments) = spec.get("private_constraints").and_then(Value::as_object).cloned() else {
+        return Ok(Vec::new());
+    };
+
+    let mut verified = Vec::with_capacity(commitments.len());
+    let mut values = Map::new();
+    for (name, expected) in &commitments {
+        let expected = expected
+            .as_str()
+            .and_then(|hex_str| hex::decode(hex_str).ok())
+            .ok_or(ErrorCode::InvalidEncoding)?;
+        let param = private_params.get(name).ok_or(ErrorCode::PrivateParam)?;
+        let value = param.get("value").and_then(Value::as_str).ok_or(ErrorCode::PrivateParam)?;
+        let salt = param
+            .get("salt")
+            .and_then(Value::as_str)
+            .and_then(|hex_str| hex::decode(hex_str).ok())
+            .ok_or(ErrorCode::InvalidEncoding)?;
+
+        let commitment = commitment(name, value, &salt);
+        if commitment.as_slice() != expected.as_slice() {
+            return Err(ErrorCode::PrivateParam);
+        }
+        values.insert(name.clone(), Value::String(value.to_string()));
+        verified.push(ParamCommitment { name: name.clone(), commitment: commitment.to_vec() });
+    }
+
+    let constraints = spec
+        .as_object_mut()
+        .ok_or(ErrorCode::SpecParse)?
+        .entry("constraints")
+        .or_insert_with(|| Value::Object(Map::new()));
+    let constraints = constraints.as_object_mut().ok_or(ErrorCode::MalformedRecord)?;
+    constraints.extend(values);
+
+    Ok(verified)
+}
+
+fn commitment(name: &str, value: &str, salt: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(COMMITMENT_DOMAIN);
+    hasher.update(salt);
+    hasher.update((name.len() as u64).to_be_bytes());
+    hasher.update(name.as_bytes());
+    hasher.update(value.as_bytes());
+    hasher.finalize().into()
+}
@@ -0,0 +1,105 @@
+//! Graded compliance scores
+//!
+//! Checkers that count conforming records report a score as an exact
+//! fraction. The spec's `pass_threshold` (e.g. "0.99" or "99.5%") decides
+//! whether the score passes. All arithmetic is integer-only so the guest
+//! result is deterministic.
+
+use serde::{Serialize, Deserialize};
+use serde_json::Value;
+use crate::checker::{ComplianceResult, ErrorCode};
+
+/// Largest number of fractional digits accepted in a threshold
+const MAX_THRESHOLD_DECIMALS: u32 = 9;
+
+/// Numeric score committed to the journal
+///
+/// A zero `denominator` means the checker did not produce a score.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Score {
+    pub numerator: u64,
+    pub denominator: u64,
+    pub threshold_numerator: u64,
+    pub threshold_denominator: u64,
+}
+
+impl Score {
+    #[must_use]
+    pub fn new(numerator: u64, denominator: u64, threshold: (u64, u64)) -> Self {
+        Self {
+            numerator,
+            denominator,
+            threshold_numerator: threshold.0,
+            threshold_denominator: threshold.1,
+        }
+    }
+
+    /// Whether a checker produced this score
+    #[must_use]
+    pub fn is_graded(&self) -> bool {
+        self.denominator > 0
+    }
+
+    /// Derive Pass/Fail by comparing the score to the threshold.
+    /// An empty sample is vacuously compliant.
+    #[must_use]
+    pub fn result(&self) -> ComplianceResult {
+        if !self.is_graded() {
+            return ComplianceResult::Pass;
+        }
+        let lhs = u128::from(self.numerator) * u128::from(self.threshold_denominator);
+        let rhs = u128::from(self.threshold_numerator) * u128::from(self.denominator);
+        if lhs >= rhs {
+            ComplianceResult::Pass
+        } else {
+            ComplianceResult::Fail
+        }
+    }
+}
+
+/// Read the spec's `pass_threshold` as an exact fraction
+///
+/// Without a threshold every record must conform (1/1), which matches
+/// the binary behaviour of the checkers.
+pub fn pass_threshold(spec: &Value) -> Result<(u64, u64), ErrorCode> {
+    match spec.get("pass_threshold") {
+        None | Some(Value::Null) => Ok((1, 1)),
+        Some(Value::String(s)) => parse_threshold(s).ok_or(ErrorCode::InvalidThreshold),
+        Some(_) => Err(ErrorCode::InvalidThreshold),
+    }
+}
+
+/// Parse "0.99", "1" or "99.5%" into (numerator, denominator)
+fn parse_threshold(input: &str) -> Option<(u64, u64)> {
+    let input = input.trim();
+    let (digits, percent) = match input.strip_suffix('%') {
+        Some(rest) => (rest.trim_end(), true),
+        None => (input, false),
+    };
+    let (whole, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    if whole.is_empty() && fraction.is_empty() {
+        return None;
+    }
+    if !whole.bytes().chain(fraction.bytes()).all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let decimals = u32::try_from(fraction.len()).ok()?;
+    if decimals > MAX_THRESHOLD_DECIMALS {
+        return None;
+    }
+
+    let mut numerator: u64 = 0;
+    for b in whole.bytes().chain(fraction.bytes()) {
+        numerator = numerator.checked_mul(10)?.checked_add(u64::from(b - b'0'))?;
+    }
+    let mut denominator = 10u64.pow(decimals);
+    if percent {
+        denominator = denominator.checked_mul(100)?;
+    }
+
+    // A threshold above 100% can never be met
+    if numerator > denominator {
+        return None;
+    }
+    Some((numerator, denominator))
+}
@@ -0,0 +1,251 @@
+//! Streamed system data in zkVM
+//!
+//! Reading millions of log records into one `String` and one `Value`
+//! exhausts guest memory. In streaming mode the host sends the checker's
+//! records as JSON Lines, one record per line, in chunks. The guest hashes
+//! every byte as it arrives and hands each line to the checker, which keeps
+//! only constant-size tallies, so proofs scale to inputs far larger than
+//! guest memory.
+//!
+//! The running hash is the `system_data` evidence digest of the whole
+//! stream, so an auditor checks it against the original JSON Lines file.
+//! Lines may span chunks; the guest reassembles them, so the records seen
+//! are exactly the lines of the committed bytes.
+//!
+//! Each chunk is a `u32` length followed by the bytes padded to a whole
+//! word; a zero length ends the stream.
+
+use risc0_zkvm::guest::env;
+use alloc::vec::Vec;
+use serde::{Serialize, Deserialize};
+use serde::de::IgnoredAny;
+use serde_json::Value;
+use crate::checker::{self, ErrorCode, JournalOutput, Route};
+#[cfg(feature = "gdpr")]
+use crate::checkers::gdpr;
+#[cfg(feature = "ml-model")]
+use crate::checkers::ml_model;
+#[cfg(feature = "soc2")]
+use crate::checkers::soc2;
+#[cfg(feature = "supply-chain")]
+use crate::checkers::supply_chain;
+use crate::evidence::{self, EvidenceCommitment, EvidenceHasher};
+
+/// Counters a checker keeps over the records it has seen; carried from
+/// one period to the next by incremental proofs
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TallyState {
+    pub records: u64,
+    pub conforming: u64,
+}
+
+/// Constant-size state a checker keeps while records stream past
+pub trait RecordTally {
+    /// Tally one record, given as the bytes of its line
+    fn record(&mut self, line: &[u8]);
+
+    /// Result over every record seen
+    fn finish(&self) -> Result<JournalOutput, ErrorCode>;
+
+    /// Counters over every record seen
+    fn state(&self) -> TallyState;
+}
+
+/// First error seen in the stream
+///
+/// A line that is not JSON outranks any record error, as it would fail
+/// parsing of the whole document before the checker ran.
+#[derive(Default)]
+pub struct FirstError(Option<ErrorCode>);
+
+impl FirstError {
+    /// Note that `line` does not fit the checker; `code` applies if it is
+    /// valid JSON
+    pub fn record(&mut self, line: &[u8], code: ErrorCode) {
+        let code = if serde_json::from_slice::<IgnoredAny>(line).is_ok() { code } else { ErrorCode::SystemDataParse };
+        if self.0.is_none() || (code == ErrorCode::SystemDataParse && self.0 != Some(code)) {
+            self.0 = Some(code);
+        }
+    }
+
+    /// Note `line` if it is not JSON
+    pub fn validate(&mut self, line: &[u8]) {
+        if serde_json::from_slice::<IgnoredAny>(line).is_err() {
+            self.0 = Some(ErrorCode::SystemDataParse);
+        }
+    }
+
+    /// Err if some line was not JSON
+    pub fn parse_error(&self) -> Result<(), ErrorCode> {
+        match self.0 {
+            Some(ErrorCode::SystemDataParse) => Err(ErrorCode::SystemDataParse),
+            _ => Ok(()),
+        }
+    }
+
+    /// Err with the first error seen
+    pub fn check(&self) -> Result<(), ErrorCode> {
+        self.0.map_or(Ok(()), Err)
+    }
+}
+
+/// The streamed system data, read chunk by chunk from the host
+pub struct Stream {
+    source: Source,
+    hasher: EvidenceHasher,
+    // Reused for every chunk and partial line; the guest heap never frees
+    chunk: Vec<u8>,
+    partial: Vec<u8>,
+    done: bool,
+}
+
+/// Where the chunks of a stream come from
+enum Source {
+    /// Chunks read from the host
+    Host,
+    /// Records already in memory, split into chunks of `chunk_bytes`
+    Memory { bytes: Vec<u8>, chunk_bytes: usize, offset: usize },
+}
+
+impl Default for Stream {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Stream {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_source(Source::Host)
+    }
+
+    /// A stream over records already in memory, read in chunks of at most
+    /// `chunk_bytes` like the host sends them; for running checks outside
+    /// the zkVM
+    #[must_use]
+    pub fn from_bytes(bytes: Vec<u8>, chunk_bytes: usize) -> Self {
+        Self::with_source(Source::Memory { bytes, chunk_bytes: chunk_bytes.max(1), offset: 0 })
+    }
+
+    fn with_source(source: Source) -> Self {
+        Self { source, hasher: EvidenceHasher::new(evidence::SYSTEM_DATA), chunk: Vec::new(), partial: Vec::new(), done: false }
+    }
+
+    /// Read the rest of the stream, passing each non-blank line to `record`
+    pub fn for_each_line(&mut self, mut record: impl FnMut(&[u8])) {
+        while !self.done {
+            if !self.read_chunk() {
+                self.done = true;
+                break;
+            }
+            let chunk = &self.chunk[..];
+            self.hasher.update(chunk);
+
+            let mut start = 0;
+            while let Some(offset) = chunk[start..].iter().position(|&b| b == b'\n') {
+                let end = start + offset;
+                if self.partial.is_empty() {
+                    emit(&chunk[start..end], &mut record);
+                } else {
+                    self.partial.extend_from_slice(&chunk[start..end]);
+                    emit(&self.partial, &mut record);
+                    self.partial.clear();
+                }
+                start = end + 1;
+            }
+            self.partial.extend_from_slice(&chunk[start..]);
+        }
+        if !self.partial.is_empty() {
+            emit(&self.partial, &mut record);
+            self.partial.clear();
+        }
+    }
+
+    /// Read the next chunk into `self.chunk`; false at the end of the stream
+    fn read_chunk(&mut self) -> bool {
+        match &mut self.source {
+            Source::Host => {
+                let len: u32 = env::read();
+                if len == 0 {
+                    return false;
+                }
+                let len = len as usize;
+                self.chunk.resize(len.next_multiple_of(4), 0);
+                env::read_slice(&mut self.chunk[..]);
+                self.chunk.truncate(len);
+            }
+            Source::Memory { bytes, chunk_bytes, offset } => {
+                let end = bytes.len().min(*offset + *chunk_bytes);
+                self.chunk.clear();
+                self.chunk.extend_from_slice(&bytes[*offset..end]);
+                *offset = end;
+            }
+        }
+        !self.chunk.is_empty()
+    }
+
+    /// Commitment to every byte of the stream, read to the end if a
+    /// check stopped early
+    #[must_use]
+    pub fn finish(mut self) -> EvidenceCommitment {
+        self.for_each_line(|_| {});
+        self.hasher.finish()
+    }
+}
+
+fn emit(line: &[u8], record: &mut impl FnMut(&[u8])) {
+    if !line.iter().all(u8::is_ascii_whitespace) {
+        record(line);
+    }
+}
+
+/// Run the spec's checker over the streamed records
+///
+/// Only the SOC2, GDPR, supply chain and ML model checkers read records;
+/// anything else, or a spec that needs the full document, errors with
+/// `StreamInput`.
+pub fn check(spec: &Value, stream: &mut Stream) -> Result<JournalOutput, ErrorCode> {
+    check_from(spec, stream, TallyState::default()).0
+}
+
+/// Run the spec's checker over the streamed records, continuing from the
+/// counters of records seen earlier; also returns the updated counters
+// Only the log-based checkers read streams
+#[cfg_attr(not(any(feature = "soc2", feature = "gdpr", feature = "supply-chain", feature = "ml-model")), allow(unused_variables))]
+pub fn check_from(spec: &Value, stream: &mut Stream, state: TallyState) -> (Result<JournalOutput, ErrorCode>, TallyState) {
+    if checker::needs_full_document(spec) {
+        return (Err(ErrorCode::StreamInput), state);
+    }
+    let route = Route::for_spec(spec);
+    if !route.is_built() {
+        return (Err(ErrorCode::CheckerNotInGuest), state);
+    }
+    match route {
+        #[cfg(feature = "soc2")]
+        Route::Soc2 => run(soc2::Tally::new(spec, state), stream, state),
+        #[cfg(feature = "gdpr")]
+        Route::Gdpr => run(gdpr::Tally::new(spec, state), stream, state),
+        #[cfg(feature = "supply-chain")]
+        Route::SupplyChain => run(supply_chain::Tally::new(spec, state), stream, state),
+        #[cfg(feature = "ml-model")]
+        Route::MlModel => run(ml_model::Tally::new(spec, state), stream, state),
+        _ => (Err(ErrorCode::StreamInput), state),
+    }
+}
+
+#[cfg_attr(not(any(feature = "soc2", feature = "gdpr", feature = "supply-chain", feature = "ml-model")), allow(dead_code))]
+fn run(tally: Result<impl RecordTally, ErrorCode>, stream: &mut Stream, state: TallyState) -> (Result<JournalOutput, ErrorCode>, TallyState) {
+    match tally {
+        Ok(mut tally) => {
+            stream.for_each_line(|line| tally.record(line));
+            (tally.finish(), tally.state())
+        }
+        // The whole document would have been parsed before the spec's
+        // constraints were read
+        Err(code) => {
+            let mut errors = FirstError::default();
+            stream.for_each_line(|line| errors.validate(line));
+            (errors.parse_error().and(Err(code)), state)
+        }
+    }
+}
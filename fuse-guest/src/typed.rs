@@ -0,0 +1,79 @@
+//! Typed binary system data in zkVM
+//!
+//! Tokenizing large log files as JSON text dominates the cycle count of
+//! the log-based checkers. The host can instead pre-parse the system data
+//! and pass the document tree in the zkVM's binary encoding. The encoding
+//! is lossless, so the guest rebuilds exactly the document the host
+//! parsed, checks it like JSON input, and commits its canonical JSON text
+//! (much cheaper than parsing it) as the `system_data` evidence.
+//!
+//! Mirrors `fuse_core::typed`; types and field order must match.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use serde::{Serialize, Serializer, Deserialize};
+use serde_json::{Map, Number, Value};
+use crate::checker::ErrorCode;
+
+/// A JSON value in the zkVM's binary encoding; numbers keep their JSON text
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Number(String),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    /// The JSON value this encodes, taking its strings without copying
+    pub fn into_value(self) -> Result<Value, ErrorCode> {
+        Ok(match self {
+            Self::Null => Value::Null,
+            Self::Bool(b) => Value::Bool(b),
+            Self::Number(text) => Value::Number(text.parse::<Number>().map_err(|_| ErrorCode::SystemDataParse)?),
+            Self::String(s) => Value::String(s),
+            Self::Array(items) => Value::Array(items.into_iter().map(Self::into_value).collect::<Result<_, _>>()?),
+            Self::Object(fields) => Value::Object(
+                fields.into_iter().map(|(key, value)| Ok((key, value.into_value()?))).collect::<Result<Map<_, _>, _>>()?,
+            ),
+        })
+    }
+}
+
+/// Pre-parsed system data document
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TypedSystemData(Json);
+
+impl TypedSystemData {
+    /// The document this encodes
+    pub fn into_document(self) -> Result<Value, ErrorCode> {
+        self.0.into_value()
+    }
+}
+
+/// Compact JSON text of a document with every object's keys sorted; the
+/// `system_data` evidence committed for typed input
+#[must_use]
+pub fn canonical_json(document: &Value) -> String {
+    serde_json::to_string(&Sorted(document)).unwrap_or_default()
+}
+
+/// Serializes a `Value` with every object's keys in sorted order, whether
+/// or not `serde_json` keeps insertion order in this build
+struct Sorted<'a>(&'a Value);
+
+impl Serialize for Sorted<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.0 {
+            Value::Array(items) => serializer.collect_seq(items.iter().map(Sorted)),
+            Value::Object(fields) => {
+                let mut fields: Vec<_> = fields.iter().collect();
+                fields.sort_unstable_by(|a, b| a.0.cmp(b.0));
+                serializer.collect_map(fields.into_iter().map(|(key, value)| (key, Sorted(value))))
+            }
+            scalar => scalar.serialize(serializer),
+        }
+    }
+}
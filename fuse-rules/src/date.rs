@@ -0,0 +1,92 @@
+//! Date parsing for `date()`
+//!
+//! Accepts `YYYY-MM-DD` (midnight UTC) and RFC 3339 timestamps such as
+//! `2025-06-01T12:30:00Z` or `2025-06-01T12:30:00.250+02:00`. Fractional
+//! seconds are truncated so results stay integral.
+
+/// Seconds since the Unix epoch, or `None` if the text is not a date
+pub(crate) fn parse_timestamp(text: &str) -> Option<i64> {
+    let bytes = text.as_bytes();
+    let year = number(bytes, 0, 4)?;
+    expect(bytes, 4, b'-')?;
+    let month = number(bytes, 5, 2)?;
+    expect(bytes, 7, b'-')?;
+    let day = number(bytes, 8, 2)?;
+    if !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month) {
+        return None;
+    }
+    let days = days_from_civil(year, month, day);
+    if bytes.len() == 10 {
+        return Some(days * 86_400);
+    }
+
+    if !matches!(bytes.get(10), Some(b'T' | b't' | b' ')) {
+        return None;
+    }
+    let hour = number(bytes, 11, 2)?;
+    expect(bytes, 13, b':')?;
+    let minute = number(bytes, 14, 2)?;
+    expect(bytes, 16, b':')?;
+    let second = number(bytes, 17, 2)?;
+    if hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+
+    let mut i = 19;
+    if bytes.get(i) == Some(&b'.') {
+        i += 1;
+        let start = i;
+        while bytes.get(i).is_some_and(u8::is_ascii_digit) {
+            i += 1;
+        }
+        if i == start {
+            return None;
+        }
+    }
+
+    let offset = match bytes.get(i) {
+        Some(b'Z' | b'z') if bytes.len() == i + 1 => 0,
+        Some(sign @ (b'+' | b'-')) if bytes.len() == i + 6 => {
+            let hours = number(bytes, i + 1, 2)?;
+            expect(bytes, i + 3, b':')?;
+            let minutes = number(bytes, i + 4, 2)?;
+            if hours > 23 || minutes > 59 {
+                return None;
+            }
+            let offset = hours * 3600 + minutes * 60;
+            if *sign == b'+' { offset } else { -offset }
+        }
+        _ => return None,
+    };
+
+    Some(days * 86_400 + hour * 3600 + minute * 60 + second - offset)
+}
+
+fn number(bytes: &[u8], start: usize, len: usize) -> Option<i64> {
+    let digits = bytes.get(start..start + len)?;
+    digits.iter().try_fold(0i64, |acc, b| b.is_ascii_digit().then(|| acc * 10 + i64::from(b - b'0')))
+}
+
+fn expect(bytes: &[u8], index: usize, byte: u8) -> Option<()> {
+    (bytes.get(index) == Some(&byte)).then_some(())
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days since 1970-01-01 in the proleptic Gregorian calendar
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month_index = (month + 9) % 12;
+    let day_of_year = (153 * month_index + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
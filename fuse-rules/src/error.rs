@@ -0,0 +1,25 @@
+//! Rule errors
+
+use alloc::string::String;
+use core::fmt;
+
+/// Reason a rule could not be parsed or evaluated
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuleError {
+    /// The rule text is not a valid expression
+    Parse { position: usize, message: &'static str },
+    /// A path in the rule does not exist in the document
+    MissingPath(String),
+    /// An operator or function was applied to values of the wrong type
+    Type(&'static str),
+}
+
+impl fmt::Display for RuleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RuleError::Parse { position, message } => write!(f, "parse error at {position}: {message}"),
+            RuleError::MissingPath(path) => write!(f, "path not found: {path}"),
+            RuleError::Type(message) => write!(f, "type error: {message}"),
+        }
+    }
+}
@@ -0,0 +1,245 @@
+//! Rule evaluation against a JSON document
+//!
+//! Evaluation is deterministic: only integers are supported (floating-point
+//! numbers in the document are a type error), strings compare bytewise, and
+//! paths with `[*]` project over arrays in document order. Comparisons with
+//! a projection on one side apply element-wise, yielding a boolean
+//! projection that `all`, `any` or `count` reduce to a single value.
+//! Regular expressions use a linear-time engine, so `matches` cannot blow
+//! up the cycle count.
+
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use regex_automata::meta::Regex;
+use serde_json::Value;
+use crate::date::parse_timestamp;
+use crate::error::RuleError;
+use crate::parser::{CompareOp, Expr, Function, Path, Segment};
+
+/// Intermediate value during evaluation
+#[derive(Debug, Clone)]
+enum Val<'a> {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Str(&'a str),
+    /// A literal list from the rule
+    List(Vec<Val<'a>>),
+    /// An array or object from the document
+    Json(&'a Value),
+    /// The values selected by a path with `[*]`
+    Projection(Vec<Val<'a>>),
+}
+
+/// Evaluate a parsed rule to a boolean
+pub fn evaluate(expr: &Expr, document: &Value) -> Result<bool, RuleError> {
+    truthy(&eval(expr, document)?)
+}
+
+fn eval<'a>(expr: &'a Expr, document: &'a Value) -> Result<Val<'a>, RuleError> {
+    match expr {
+        Expr::Null => Ok(Val::Null),
+        Expr::Bool(value) => Ok(Val::Bool(*value)),
+        Expr::Int(value) => Ok(Val::Int(*value)),
+        Expr::Str(value) => Ok(Val::Str(value)),
+        Expr::List(items) => items.iter().map(|item| eval(item, document)).collect::<Result<_, _>>().map(Val::List),
+        Expr::Path(path) => resolve(path, document),
+        Expr::Not(inner) => Ok(Val::Bool(!truthy(&eval(inner, document)?)?)),
+        // Short-circuit so guards like `exists(x) && x > 0` work
+        Expr::And(lhs, rhs) => Ok(Val::Bool(truthy(&eval(lhs, document)?)? && truthy(&eval(rhs, document)?)?)),
+        Expr::Or(lhs, rhs) => Ok(Val::Bool(truthy(&eval(lhs, document)?)? || truthy(&eval(rhs, document)?)?)),
+        Expr::Matches(lhs, pattern) => match_projected(eval(lhs, document)?, pattern.regex()),
+        Expr::Compare(op, lhs, rhs) => {
+            let lhs = eval(lhs, document)?;
+            match (op, eval(rhs, document)?) {
+                // A pattern from the document is compiled once for every element
+                (CompareOp::Matches, Val::Str(pattern)) => match_projected(lhs, &compile_regex(pattern)?),
+                (op, rhs) => compare_projected(*op, lhs, &rhs),
+            }
+        }
+        Expr::Call(Function::Exists, argument) => match argument.as_ref() {
+            Expr::Path(path) => match resolve(path, document) {
+                Ok(_) => Ok(Val::Bool(true)),
+                Err(RuleError::MissingPath(_)) => Ok(Val::Bool(false)),
+                Err(e) => Err(e),
+            },
+            _ => Err(RuleError::Type("exists() takes a path")),
+        },
+        Expr::Call(Function::Date, argument) => match eval(argument, document)? {
+            Val::Projection(items) => items.iter().map(date).collect::<Result<_, _>>().map(Val::Projection),
+            value => date(&value),
+        },
+        Expr::Call(function, argument) => {
+            let items = elements(eval(argument, document)?)?;
+            match function {
+                Function::All => items.iter().try_fold(true, |acc, item| Ok(acc && truthy(item)?)).map(Val::Bool),
+                Function::Any => items.iter().try_fold(false, |acc, item| Ok(acc || truthy(item)?)).map(Val::Bool),
+                Function::Count => {
+                    let count = if !items.is_empty() && items.iter().all(is_bool) {
+                        items.iter().filter(|item| truthy(item).unwrap_or(false)).count()
+                    } else {
+                        items.len()
+                    };
+                    i64::try_from(count).map(Val::Int).map_err(|_| RuleError::Type("count overflow"))
+                }
+                Function::Exists | Function::Date => unreachable!("handled above"),
+            }
+        }
+    }
+}
+
+/// Convert a date or timestamp string to seconds since the epoch
+fn date<'a>(value: &Val<'a>) -> Result<Val<'a>, RuleError> {
+    match value {
+        Val::Str(text) => parse_timestamp(text).map(Val::Int).ok_or(RuleError::Type("date() needs YYYY-MM-DD or an RFC 3339 timestamp")),
+        _ => Err(RuleError::Type("date() takes a string")),
+    }
+}
+
+/// Compile a pattern for `matches`
+pub(crate) fn compile_regex(pattern: &str) -> Result<Regex, RuleError> {
+    Regex::new(pattern).map_err(|_| RuleError::Type("invalid regular expression"))
+}
+
+/// Resolve a path against the document
+fn resolve<'a>(path: &Path, document: &'a Value) -> Result<Val<'a>, RuleError> {
+    let missing = || RuleError::MissingPath(alloc::format!("{path}"));
+    let root = document.get(path.root.as_str()).ok_or_else(missing)?;
+
+    let mut current: Vec<&'a Value> = alloc::vec![root];
+    for segment in &path.segments {
+        let mut next = Vec::with_capacity(current.len());
+        for value in current {
+            match segment {
+                Segment::Field(name) => next.push(value.get(name.as_str()).ok_or_else(missing)?),
+                Segment::Index(index) => next.push(value.get(*index).ok_or_else(missing)?),
+                Segment::Wildcard => next.extend(value.as_array().ok_or_else(missing)?),
+            }
+        }
+        current = next;
+    }
+
+    if path.is_projection() {
+        current.into_iter().map(from_json).collect::<Result<_, _>>().map(Val::Projection)
+    } else {
+        from_json(current[0])
+    }
+}
+
+fn from_json(value: &Value) -> Result<Val<'_>, RuleError> {
+    match value {
+        Value::Null => Ok(Val::Null),
+        Value::Bool(value) => Ok(Val::Bool(*value)),
+        Value::Number(number) => number.as_i64().map(Val::Int).ok_or(RuleError::Type("only integers are supported")),
+        Value::String(value) => Ok(Val::Str(value)),
+        Value::Array(_) | Value::Object(_) => Ok(Val::Json(value)),
+    }
+}
+
+/// Elements of a list, array or projection
+fn elements(value: Val<'_>) -> Result<Vec<Val<'_>>, RuleError> {
+    match value {
+        Val::List(items) | Val::Projection(items) => Ok(items),
+        Val::Json(Value::Array(items)) => items.iter().map(from_json).collect(),
+        _ => Err(RuleError::Type("expected a list, array or projection")),
+    }
+}
+
+fn is_bool(value: &Val<'_>) -> bool {
+    matches!(value, Val::Bool(_))
+}
+
+fn truthy(value: &Val<'_>) -> Result<bool, RuleError> {
+    match value {
+        Val::Bool(value) => Ok(*value),
+        Val::Projection(_) => Err(RuleError::Type("reduce a projection with all(), any() or count()")),
+        _ => Err(RuleError::Type("expected a boolean")),
+    }
+}
+
+/// Apply a comparison, element-wise when one side is a projection
+fn compare_projected<'a>(op: CompareOp, lhs: Val<'a>, rhs: &Val<'a>) -> Result<Val<'a>, RuleError> {
+    match (lhs, rhs) {
+        (Val::Projection(_), Val::Projection(_)) if op != CompareOp::In => {
+            Err(RuleError::Type("cannot compare two projections"))
+        }
+        (Val::Projection(items), _) => items
+            .into_iter()
+            .map(|item| compare(op, &item, rhs).map(Val::Bool))
+            .collect::<Result<_, _>>()
+            .map(Val::Projection),
+        (lhs, Val::Projection(items)) if op != CompareOp::In => items
+            .iter()
+            .map(|item| compare(op, &lhs, item).map(Val::Bool))
+            .collect::<Result<_, _>>()
+            .map(Val::Projection),
+        (lhs, rhs) => compare(op, &lhs, rhs).map(Val::Bool),
+    }
+}
+
+/// Apply `matches` with a compiled pattern, element-wise over a projection
+fn match_projected<'a>(lhs: Val<'a>, regex: &Regex) -> Result<Val<'a>, RuleError> {
+    match lhs {
+        Val::Projection(items) => items
+            .iter()
+            .map(|item| is_match(item, regex).map(Val::Bool))
+            .collect::<Result<_, _>>()
+            .map(Val::Projection),
+        lhs => is_match(&lhs, regex).map(Val::Bool),
+    }
+}
+
+fn is_match(text: &Val<'_>, regex: &Regex) -> Result<bool, RuleError> {
+    match text {
+        Val::Str(text) => Ok(regex.is_match(*text)),
+        _ => Err(RuleError::Type("matches needs a string and a pattern")),
+    }
+}
+
+fn compare(op: CompareOp, lhs: &Val<'_>, rhs: &Val<'_>) -> Result<bool, RuleError> {
+    match op {
+        CompareOp::Eq => equal(lhs, rhs),
+        CompareOp::Ne => equal(lhs, rhs).map(|eq| !eq),
+        CompareOp::In => {
+            let items = elements(rhs.clone())?;
+            items.iter().try_fold(false, |found, item| Ok(found || equal(lhs, item)?))
+        }
+        CompareOp::Matches => match (lhs, rhs) {
+            (Val::Str(_), Val::Str(pattern)) => is_match(lhs, &compile_regex(pattern)?),
+            _ => Err(RuleError::Type("matches needs a string and a pattern")),
+        },
+        CompareOp::Lt => order(lhs, rhs).map(Ordering::is_lt),
+        CompareOp::Le => order(lhs, rhs).map(Ordering::is_le),
+        CompareOp::Gt => order(lhs, rhs).map(Ordering::is_gt),
+        CompareOp::Ge => order(lhs, rhs).map(Ordering::is_ge),
+    }
+}
+
+/// Structural equality; values of different types are unequal
+fn equal(lhs: &Val<'_>, rhs: &Val<'_>) -> Result<bool, RuleError> {
+    match (lhs, rhs) {
+        (Val::Null, Val::Null) => Ok(true),
+        (Val::Bool(a), Val::Bool(b)) => Ok(a == b),
+        (Val::Int(a), Val::Int(b)) => Ok(a == b),
+        (Val::Str(a), Val::Str(b)) => Ok(a == b),
+        (Val::Json(Value::Object(a)), Val::Json(Value::Object(b))) => Ok(a == b),
+        (Val::Projection(_), _) | (_, Val::Projection(_)) => Err(RuleError::Type("cannot compare a projection here")),
+        (a @ (Val::List(_) | Val::Json(Value::Array(_))), b @ (Val::List(_) | Val::Json(Value::Array(_)))) => {
+            let a = elements(a.clone())?;
+            let b = elements(b.clone())?;
+            if a.len() != b.len() {
+                return Ok(false);
+            }
+            a.iter().zip(&b).try_fold(true, |eq, (x, y)| Ok(eq && equal(x, y)?))
+        }
+        _ => Ok(false),
+    }
+}
+
+fn order(lhs: &Val<'_>, rhs: &Val<'_>) -> Result<Ordering, RuleError> {
+    match (lhs, rhs) {
+        (Val::Int(a), Val::Int(b)) => Ok(a.cmp(b)),
+        (Val::Str(a), Val::Str(b)) => Ok(a.as_bytes().cmp(b.as_bytes())),
+        _ => Err(RuleError::Type("ordering needs two integers or two strings")),
+    }
+}
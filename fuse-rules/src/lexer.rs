@@ -0,0 +1,146 @@
+//! Tokenizer for rule expressions
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use crate::error::RuleError;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Token {
+    Ident(String),
+    Int(i64),
+    Str(String),
+    True,
+    False,
+    Null,
+    In,
+    Matches,
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    Comma,
+    Dot,
+    Star,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    And,
+    Or,
+    Not,
+}
+
+/// Split a rule into tokens, each paired with its byte offset
+pub(crate) fn tokenize(input: &str) -> Result<Vec<(usize, Token)>, RuleError> {
+    let bytes = input.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let start = i;
+        let c = bytes[i];
+        let token = match c {
+            b' ' | b'\t' | b'\n' | b'\r' => {
+                i += 1;
+                continue;
+            }
+            b'(' => single(&mut i, Token::LParen),
+            b')' => single(&mut i, Token::RParen),
+            b'[' => single(&mut i, Token::LBracket),
+            b']' => single(&mut i, Token::RBracket),
+            b',' => single(&mut i, Token::Comma),
+            b'.' => single(&mut i, Token::Dot),
+            b'*' => single(&mut i, Token::Star),
+            b'=' if bytes.get(i + 1) == Some(&b'=') => double(&mut i, Token::Eq),
+            b'!' if bytes.get(i + 1) == Some(&b'=') => double(&mut i, Token::Ne),
+            b'!' => single(&mut i, Token::Not),
+            b'<' if bytes.get(i + 1) == Some(&b'=') => double(&mut i, Token::Le),
+            b'<' => single(&mut i, Token::Lt),
+            b'>' if bytes.get(i + 1) == Some(&b'=') => double(&mut i, Token::Ge),
+            b'>' => single(&mut i, Token::Gt),
+            b'&' if bytes.get(i + 1) == Some(&b'&') => double(&mut i, Token::And),
+            b'|' if bytes.get(i + 1) == Some(&b'|') => double(&mut i, Token::Or),
+            b'"' | b'\'' => Token::Str(string(input, &mut i)?),
+            b'-' | b'0'..=b'9' => Token::Int(integer(input, &mut i)?),
+            c if c.is_ascii_alphabetic() || c == b'_' => {
+                while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_') {
+                    i += 1;
+                }
+                match &input[start..i] {
+                    "true" => Token::True,
+                    "false" => Token::False,
+                    "null" => Token::Null,
+                    "in" => Token::In,
+                    "matches" => Token::Matches,
+                    ident => Token::Ident(ident.into()),
+                }
+            }
+            _ => return Err(RuleError::Parse { position: i, message: "unexpected character" }),
+        };
+        tokens.push((start, token));
+    }
+
+    Ok(tokens)
+}
+
+fn single(i: &mut usize, token: Token) -> Token {
+    *i += 1;
+    token
+}
+
+fn double(i: &mut usize, token: Token) -> Token {
+    *i += 2;
+    token
+}
+
+/// Read a quoted string; `\\`, `\"` and `\'` are the only escapes
+fn string(input: &str, i: &mut usize) -> Result<String, RuleError> {
+    let bytes = input.as_bytes();
+    let quote = bytes[*i];
+    let start = *i;
+    *i += 1;
+    let mut out = String::new();
+    let mut run = *i;
+
+    while *i < bytes.len() {
+        match bytes[*i] {
+            b'\\' => {
+                out.push_str(&input[run..*i]);
+                match bytes.get(*i + 1) {
+                    Some(&escaped @ (b'\\' | b'"' | b'\'')) => out.push(char::from(escaped)),
+                    _ => return Err(RuleError::Parse { position: *i, message: "invalid escape" }),
+                }
+                *i += 2;
+                run = *i;
+            }
+            c if c == quote => {
+                out.push_str(&input[run..*i]);
+                *i += 1;
+                return Ok(out);
+            }
+            _ => *i += 1,
+        }
+    }
+
+    Err(RuleError::Parse { position: start, message: "unterminated string" })
+}
+
+fn integer(input: &str, i: &mut usize) -> Result<i64, RuleError> {
+    let bytes = input.as_bytes();
+    let start = *i;
+    if bytes[*i] == b'-' {
+        *i += 1;
+    }
+    let digits = *i;
+    while *i < bytes.len() && bytes[*i].is_ascii_digit() {
+        *i += 1;
+    }
+    if *i == digits {
+        return Err(RuleError::Parse { position: start, message: "expected digits" });
+    }
+    input[start..*i]
+        .parse()
+        .map_err(|_| RuleError::Parse { position: start, message: "integer out of range" })
+}
@@ -0,0 +1,143 @@
+//! Declarative rule expressions for compliance specs
+//!
+//! A small, deterministic expression language evaluated against the system
+//! data. The same `no_std` evaluator runs in the zkVM guest and in the host
+//! checkers, so a single guest image can prove customer-defined policies
+//! without new Rust code.
+//!
+//! ```text
+//! all(storage_locations[*].region in ["eu-west-1", "eu-central-1"])
+//! count(usage_logs) <= 1000 && !any(usage_logs[*].domain == "weapons")
+//! ```
+//!
+//! Syntax:
+//! - literals: integers, `"strings"`, `true`, `false`, `null`, `[lists]`
+//! - paths: `a.b`, `a[0]`, `a["odd-key"]`, `a[*].b` (projection over an array)
+//! - operators: `==`, `!=`, `<`, `<=`, `>`, `>=`, `in`, `matches`, `!`, `&&`, `||`
+//! - functions: `all(x)`, `any(x)`, `count(x)`, `exists(path)`, `date(x)`
+
+#![no_std]
+
+extern crate alloc;
+
+mod date;
+mod error;
+mod eval;
+mod lexer;
+mod parser;
+
+pub use error::RuleError;
+pub use parser::{parse, CompareOp, Expr, Function, Path, Pattern, Segment};
+
+/// Maximum rule length in bytes, to bound parsing cost in the zkVM
+pub const MAX_RULE_LEN: usize = 4096;
+
+/// Parse and evaluate a rule against a JSON document
+pub fn evaluate(rule: &str, document: &serde_json::Value) -> Result<bool, RuleError> {
+    eval::evaluate(&compile(rule)?, document)
+}
+
+/// Parse a rule, enforcing the length limit
+pub fn compile(rule: &str) -> Result<Expr, RuleError> {
+    if rule.len() > MAX_RULE_LEN {
+        return Err(RuleError::Parse { position: MAX_RULE_LEN, message: "rule is too long" });
+    }
+    parse(rule)
+}
+
+/// Evaluate an already parsed rule
+pub fn evaluate_expr(expr: &Expr, document: &serde_json::Value) -> Result<bool, RuleError> {
+    eval::evaluate(expr, document)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn document() -> serde_json::Value {
+        json!({
+            "storage_locations": [
+                {"region": "eu-west-1", "bytes": 10},
+                {"region": "eu-central-1", "bytes": 20}
+            ],
+            "usage_logs": [
+                {"domain": "healthcare"},
+                {"domain": "research"},
+                {"domain": "healthcare"}
+            ],
+            "owner": {"team-name": "platform", "verified": true},
+            "ratio": 0.5
+        })
+    }
+
+    #[test]
+    fn test_projection_membership() {
+        let doc = document();
+        assert_eq!(evaluate(r#"all(storage_locations[*].region in ["eu-west-1", "eu-central-1"])"#, &doc), Ok(true));
+        assert_eq!(evaluate(r#"all(storage_locations[*].region in ["eu-west-1"])"#, &doc), Ok(false));
+        assert_eq!(evaluate(r#"any(storage_locations[*].bytes > 15)"#, &doc), Ok(true));
+    }
+
+    #[test]
+    fn test_count() {
+        let doc = document();
+        assert_eq!(evaluate("count(usage_logs) <= 1000", &doc), Ok(true));
+        assert_eq!(evaluate("count(usage_logs) == 3", &doc), Ok(true));
+        assert_eq!(evaluate(r#"count(usage_logs[*].domain == "healthcare") == 2"#, &doc), Ok(true));
+    }
+
+    #[test]
+    fn test_logic_and_paths() {
+        let doc = document();
+        assert_eq!(evaluate(r#"owner["team-name"] == "platform" && owner.verified"#, &doc), Ok(true));
+        assert_eq!(evaluate("!(storage_locations[0].bytes >= 10) || false", &doc), Ok(false));
+        assert_eq!(evaluate(r#"exists(owner.missing) && owner.missing == 1"#, &doc), Ok(false));
+        assert_eq!(evaluate(r#""eu-west-1" in storage_locations[*].region"#, &doc), Ok(true));
+    }
+
+    #[test]
+    fn test_errors() {
+        let doc = document();
+        assert!(matches!(evaluate("owner.missing == 1", &doc), Err(RuleError::MissingPath(_))));
+        assert!(matches!(evaluate("ratio > 0", &doc), Err(RuleError::Type(_))));
+        assert!(matches!(evaluate("storage_locations[*].bytes > 1", &doc), Err(RuleError::Type(_))));
+        assert!(matches!(evaluate("count(usage_logs) <=", &doc), Err(RuleError::Parse { .. })));
+        assert!(matches!(evaluate("sum(usage_logs)", &doc), Err(RuleError::Parse { .. })));
+        assert!(matches!(evaluate("\"unterminated", &doc), Err(RuleError::Parse { .. })));
+    }
+
+    #[test]
+    fn test_matches_and_dates() {
+        let doc = serde_json::json!({
+            "claim": {"issuer": "CN=Acme Root CA", "capture_time": "2025-05-31T23:30:00-01:00"},
+            "logs": [{"at": "2024-01-01"}, {"at": "2024-02-29T10:00:00Z"}]
+        });
+        assert_eq!(evaluate(r#"claim.issuer matches "^CN=Acme""#, &doc), Ok(true));
+        assert_eq!(evaluate(r#"claim.issuer matches "(?i)^cn=other""#, &doc), Ok(false));
+        // 23:30 at -01:00 is already June 1st in UTC
+        assert_eq!(evaluate(r#"date(claim.capture_time) < date("2025-06-01")"#, &doc), Ok(false));
+        assert_eq!(evaluate(r#"all(date(logs[*].at) < date("2024-03-01T00:00:00Z"))"#, &doc), Ok(true));
+        assert_eq!(evaluate(r#"date("1970-01-02") == 86400"#, &doc), Ok(true));
+        assert!(matches!(evaluate(r#"date("2023-02-29") > 0"#, &doc), Err(RuleError::Type(_))));
+        assert!(matches!(compile(r#"claim.issuer matches "(unclosed""#), Err(RuleError::Parse { .. })));
+    }
+
+    #[test]
+    fn test_literal_patterns_compile_when_parsed() {
+        let expr = compile(r#"all(logs[*].user matches "^svc-")"#).unwrap();
+        let Expr::Call(Function::All, argument) = expr else { panic!("expected all()") };
+        assert!(matches!(argument.as_ref(), Expr::Matches(_, pattern) if pattern.source == "^svc-"));
+
+        // A pattern from the document still works, compiled at evaluation
+        let doc = serde_json::json!({"pattern": "^svc-", "logs": [{"user": "svc-backup"}, {"user": "alice"}]});
+        assert_eq!(evaluate(r#"count(logs[*].user matches pattern) == 1"#, &doc), Ok(true));
+        assert_eq!(evaluate(r#"any(logs[*].user matches "^svc-")"#, &doc), Ok(true));
+    }
+
+    #[test]
+    fn test_nesting_limit() {
+        let rule = alloc::format!("{}true{}", "(".repeat(100), ")".repeat(100));
+        assert!(matches!(compile(&rule), Err(RuleError::Parse { .. })));
+    }
+}
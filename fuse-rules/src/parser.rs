@@ -0,0 +1,318 @@
+//! Recursive-descent parser producing the rule AST
+
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use regex_automata::meta::Regex;
+use crate::error::RuleError;
+use crate::lexer::{tokenize, Token};
+
+/// Maximum nesting of parentheses, lists, calls and negations
+const MAX_DEPTH: usize = 64;
+
+/// A parsed rule expression
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expr {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Str(String),
+    List(Vec<Expr>),
+    Path(Path),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Compare(CompareOp, Box<Expr>, Box<Expr>),
+    /// `matches` against a literal pattern, compiled once when parsed
+    Matches(Box<Expr>, Pattern),
+    Call(Function, Box<Expr>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    In,
+    /// Regular expression search (unanchored unless the pattern uses `^`/`$`)
+    Matches,
+}
+
+/// A literal regular expression and its compiled form
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    pub source: String,
+    regex: Regex,
+}
+
+impl Pattern {
+    pub(crate) fn regex(&self) -> &Regex {
+        &self.regex
+    }
+}
+
+/// Patterns are equal when their sources are
+impl PartialEq for Pattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+    }
+}
+
+impl Eq for Pattern {}
+
+/// Built-in functions; each takes a single argument
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Function {
+    /// True when every element is true (vacuously true when empty)
+    All,
+    /// True when at least one element is true
+    Any,
+    /// Number of elements, or of true elements for a boolean projection
+    Count,
+    /// True when the path resolves
+    Exists,
+    /// Seconds since the Unix epoch for an RFC 3339 timestamp or `YYYY-MM-DD` date
+    Date,
+}
+
+/// A path into the document, e.g. `storage_locations[*].region`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path {
+    pub root: String,
+    pub segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Segment {
+    Field(String),
+    Index(usize),
+    /// `[*]`: project over every element of an array
+    Wildcard,
+}
+
+impl Path {
+    /// Whether resolving this path yields a projection rather than one value
+    #[must_use]
+    pub fn is_projection(&self) -> bool {
+        self.segments.contains(&Segment::Wildcard)
+    }
+}
+
+impl fmt::Display for Path {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.root)?;
+        for segment in &self.segments {
+            match segment {
+                Segment::Field(name) if is_identifier(name) => write!(f, ".{name}")?,
+                Segment::Field(name) => write!(f, "[\"{name}\"]")?,
+                Segment::Index(index) => write!(f, "[{index}]")?,
+                Segment::Wildcard => write!(f, "[*]")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Parse a rule into an expression
+pub fn parse(input: &str) -> Result<Expr, RuleError> {
+    let tokens = tokenize(input)?;
+    let mut parser = Parser { tokens, pos: 0, depth: 0, end: input.len() };
+    let expr = parser.or()?;
+    match parser.tokens.get(parser.pos) {
+        None => Ok(expr),
+        Some((position, _)) => Err(RuleError::Parse { position: *position, message: "unexpected trailing input" }),
+    }
+}
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    pos: usize,
+    depth: usize,
+    end: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(_, token)| token)
+    }
+
+    fn position(&self) -> usize {
+        self.tokens.get(self.pos).map_or(self.end, |(position, _)| *position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).map(|(_, token)| token.clone());
+        self.pos += 1;
+        token
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.peek() == Some(token) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: &Token, message: &'static str) -> Result<(), RuleError> {
+        if self.eat(token) {
+            Ok(())
+        } else {
+            Err(self.error(message))
+        }
+    }
+
+    fn error(&self, message: &'static str) -> RuleError {
+        RuleError::Parse { position: self.position(), message }
+    }
+
+    fn enter(&mut self) -> Result<(), RuleError> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err(self.error("expression nested too deeply"));
+        }
+        Ok(())
+    }
+
+    fn or(&mut self) -> Result<Expr, RuleError> {
+        let mut lhs = self.and()?;
+        while self.eat(&Token::Or) {
+            lhs = Expr::Or(Box::new(lhs), Box::new(self.and()?));
+        }
+        Ok(lhs)
+    }
+
+    fn and(&mut self) -> Result<Expr, RuleError> {
+        let mut lhs = self.not()?;
+        while self.eat(&Token::And) {
+            lhs = Expr::And(Box::new(lhs), Box::new(self.not()?));
+        }
+        Ok(lhs)
+    }
+
+    fn not(&mut self) -> Result<Expr, RuleError> {
+        if self.eat(&Token::Not) {
+            self.enter()?;
+            let inner = self.not()?;
+            self.depth -= 1;
+            return Ok(Expr::Not(Box::new(inner)));
+        }
+        self.comparison()
+    }
+
+    fn comparison(&mut self) -> Result<Expr, RuleError> {
+        let lhs = self.primary()?;
+        let op = match self.peek() {
+            Some(Token::Eq) => CompareOp::Eq,
+            Some(Token::Ne) => CompareOp::Ne,
+            Some(Token::Lt) => CompareOp::Lt,
+            Some(Token::Le) => CompareOp::Le,
+            Some(Token::Gt) => CompareOp::Gt,
+            Some(Token::Ge) => CompareOp::Ge,
+            Some(Token::In) => CompareOp::In,
+            Some(Token::Matches) => CompareOp::Matches,
+            _ => return Ok(lhs),
+        };
+        self.pos += 1;
+        let position = self.position();
+        let rhs = self.primary()?;
+        // Compile literal patterns once, rejecting bad ones up front rather
+        // than at evaluation
+        if let (CompareOp::Matches, Expr::Str(source)) = (op, rhs.clone()) {
+            let regex = crate::eval::compile_regex(&source)
+                .map_err(|_| RuleError::Parse { position, message: "invalid regular expression" })?;
+            return Ok(Expr::Matches(Box::new(lhs), Pattern { source, regex }));
+        }
+        Ok(Expr::Compare(op, Box::new(lhs), Box::new(rhs)))
+    }
+
+    fn primary(&mut self) -> Result<Expr, RuleError> {
+        let position = self.position();
+        match self.next() {
+            Some(Token::Null) => Ok(Expr::Null),
+            Some(Token::True) => Ok(Expr::Bool(true)),
+            Some(Token::False) => Ok(Expr::Bool(false)),
+            Some(Token::Int(value)) => Ok(Expr::Int(value)),
+            Some(Token::Str(value)) => Ok(Expr::Str(value)),
+            Some(Token::LParen) => {
+                self.enter()?;
+                let inner = self.or()?;
+                self.expect(&Token::RParen, "expected ')'")?;
+                self.depth -= 1;
+                Ok(inner)
+            }
+            Some(Token::LBracket) => {
+                self.enter()?;
+                let mut items = Vec::new();
+                if !self.eat(&Token::RBracket) {
+                    loop {
+                        items.push(self.primary()?);
+                        if self.eat(&Token::RBracket) {
+                            break;
+                        }
+                        self.expect(&Token::Comma, "expected ',' or ']'")?;
+                    }
+                }
+                self.depth -= 1;
+                Ok(Expr::List(items))
+            }
+            Some(Token::Ident(name)) if self.peek() == Some(&Token::LParen) => {
+                let function = match name.as_str() {
+                    "all" => Function::All,
+                    "any" => Function::Any,
+                    "count" => Function::Count,
+                    "exists" => Function::Exists,
+                    "date" => Function::Date,
+                    _ => return Err(RuleError::Parse { position, message: "unknown function" }),
+                };
+                self.pos += 1;
+                self.enter()?;
+                let argument = self.or()?;
+                self.expect(&Token::RParen, "expected ')'")?;
+                self.depth -= 1;
+                if function == Function::Exists && !matches!(argument, Expr::Path(_)) {
+                    return Err(RuleError::Parse { position, message: "exists() takes a path" });
+                }
+                Ok(Expr::Call(function, Box::new(argument)))
+            }
+            Some(Token::Ident(root)) => self.path(root),
+            _ => Err(RuleError::Parse { position, message: "expected a value" }),
+        }
+    }
+
+    fn path(&mut self, root: String) -> Result<Expr, RuleError> {
+        let mut segments = Vec::new();
+        loop {
+            if self.eat(&Token::Dot) {
+                match self.next() {
+                    Some(Token::Ident(name)) => segments.push(Segment::Field(name)),
+                    _ => return Err(self.error("expected a field name after '.'")),
+                }
+            } else if self.eat(&Token::LBracket) {
+                let segment = match self.next() {
+                    Some(Token::Star) => Segment::Wildcard,
+                    Some(Token::Str(name)) => Segment::Field(name),
+                    Some(Token::Int(index)) => Segment::Index(
+                        usize::try_from(index).map_err(|_| self.error("index must not be negative"))?,
+                    ),
+                    _ => return Err(self.error("expected '*', an index or a quoted field")),
+                };
+                self.expect(&Token::RBracket, "expected ']'")?;
+                segments.push(segment);
+            } else {
+                return Ok(Expr::Path(Path { root, segments }));
+            }
+        }
+    }
+}
@@ -0,0 +1,42 @@
+#!/bin/bash
+# Build the general guest program and one guest program per checker family
+#
+# fuse-core embeds every guest it finds under target/, so rebuild fuse-core
+# (or the CLI) afterwards. Set RUSTC to the RISC Zero toolchain's rustc if
+# it is not the default.
+#
+# Guests are linked against RISC Zero's curve25519-dalek fork, which runs
+# Ed25519 field arithmetic on the zkVM's bigint accelerator. The patch is
+# passed on the command line rather than in the workspace manifest so host
+# builds keep resolving curve25519-dalek from crates.io (and keep working
+# offline). Set CURVE25519_PATCH=0 to build against upstream arithmetic, for
+# example to take the "before" measurement in docs/TESTING_PERFORMANCE.md.
+
+set -e
+
+TARGET=riscv32im-risc0-zkvm-elf
+FAMILIES="soc2 gdpr supply-chain ml-model c2pa"
+CURVE25519_REPO="${CURVE25519_REPO:-https://github.com/risc0/curve25519-dalek}"
+CURVE25519_TAG="${CURVE25519_TAG:-curve25519-4.1.2-risczero.0}"
+
+PATCH=()
+if [ "${CURVE25519_PATCH:-1}" != "0" ]; then
+    PATCH=(--config "patch.crates-io.curve25519-dalek.git=\"$CURVE25519_REPO\""
+           --config "patch.crates-io.curve25519-dalek.tag=\"$CURVE25519_TAG\"")
+    # Resolving the patch rewrites Cargo.lock; put the host's lockfile back
+    # once the guests are built.
+    cp Cargo.lock Cargo.lock.host
+    trap 'mv Cargo.lock.host Cargo.lock' EXIT
+fi
+
+echo "Building fuse-guest (every checker)..."
+cargo build -p fuse-guest --release --target "$TARGET" "${PATCH[@]}" --bin fuse-guest
+
+for family in $FAMILIES; do
+    echo "Building fuse-guest-$family..."
+    cargo build -p fuse-guest --release --target "$TARGET" "${PATCH[@]}" \
+        --no-default-features --features "$family-guest" --bin "fuse-guest-$family"
+done
+
+echo ""
+echo "Guest programs built in target/$TARGET/release/"